@@ -1,25 +1,64 @@
 // Constants and configuration values for the Solana Survivor game
-
-use anchor_lang::prelude::*;
+// Tuning values below are GlobalConfig defaults; games read their snapshot
 
 // Game timing constants (in seconds)
 pub const GAME_START_GRACE_PERIOD: i64 = 1800; // 30 minutes to start game
 pub const PHASE_ADVANCE_BUFFER: i64 = 600; // 10 minutes buffer to advance phase
 pub const PHASE3_READY_WINDOW: i64 = 1800; // 30 minutes to mark ready for purge
 pub const PHASE3_EXTENDED_WINDOW: i64 = 3600; // Additional 1 hour if no one ready
+pub const ACCOUNT_SWEEP_GRACE_PERIOD: i64 = 604800; // 7 days before abandoned accounts can be swept
+
+// Per-game rule bounds (GlobalConfig rule_bounds defaults)
+pub const MIN_PHASE_DURATION: u64 = 600; // 10 minutes per phase for blitz games
+pub const MAX_PHASE_DURATION: u64 = 604800; // 7 days per phase for marathon games
+pub const MAX_BET_PERCENTAGE: u8 = 100; // Challenge bets may stake the whole balance
+pub const MAX_CREATOR_FEE_BPS: u16 = 2000; // Creators may keep up to 20% of the platform fee
+pub const LEAVE_LOCK_WINDOW: i64 = 600; // Players can't leave in the last 10 minutes before start
+pub const MAX_LEAVE_LOCK_WINDOW: i64 = 86400; // Creators may lock entries up to 1 day early
+pub const MAX_ALLOWLIST_PROOF_LEN: usize = 24; // Merkle proofs for allowlists of up to ~16M wallets
+pub const MAX_INVITE_CODE_LEN: usize = 64;
+pub const MIN_TEMPLATE_CADENCE: i64 = 1800; // Recurring games at most every 30 minutes
+pub const MAX_TEMPLATE_LEAD_TIME: i64 = 604800; // Spawned games open for entries up to 7 days early
+pub const MAX_SPONSOR_NAME_LEN: usize = 32;
+pub const MIN_SQUAD_SIZE: u8 = 2;
+pub const MAX_SQUAD_SIZE: usize = 5;
+pub const MAX_SQUAD_NAME_LEN: usize = 32;
+
+// Game name limit (bytes of the zero-copy name field)
+pub const MAX_GAME_NAME_LEN: usize = 64;
 
 // Player requirements
-pub const MIN_PLAYERS_TO_START: u8 = 3;
-pub const MAX_PLAYERS_ALLOWED: u8 = 100;
+pub const MIN_PLAYERS_TO_START: u16 = 3;
+pub const MAX_PLAYERS_ALLOWED: u16 = 1000;
 
 // Phase 2 challenge limits
 pub const MIN_PHASE2_GAMES: u8 = 3;
 pub const MAX_PHASE2_GAMES: u8 = 10;
 pub const MAX_OPPONENT_DECLINES: u8 = 5; // After 5 declines, challenge is forced
+pub const MAX_TRACKED_OPPONENTS: u8 = 10; // Opponent records kept per player
+
+// Creator bond - locked in `create_game`, slashed to players if the creator
+// neither starts nor cancels
+pub const CREATOR_BOND: u64 = 100000000; // 0.1 SOL
+
+// Refund crank - paid per refunded player out of their closed entry's rent
+pub const CRANK_REFUND_TIP: u64 = 100000; // 0.0001 SOL
+
+// Admin council
+pub const MAX_COUNCIL_MEMBERS: usize = 10;
+pub const ADMIN_PROPOSAL_LIFETIME: i64 = 259200; // Proposals lapse after 3 days
+
+// Role registry
+pub const MAX_ROLE_MEMBERS: usize = 20;
+
+// Admin timelock - game interventions are announced before they can run
+pub const ADMIN_ACTION_DELAY: i64 = 3600; // 1 hour public notice
+pub const ADMIN_ACTION_GRACE: i64 = 86400; // Queued actions lapse 1 day after becoming executable
 
 // Platform fees
 pub const PLATFORM_FEE_PERCENTAGE: u64 = 1; // 1% of prize pool
 pub const ADMIN_SHARE_NO_READY: u64 = 25; // 25% to admin if no players ready for purge
+pub const BPS_DENOMINATOR: u64 = 10000; // Basis points in 100%
 
 // Resource multipliers for Phase 1
 pub const MINING_BASE_RATE: f64 = 7.0;
@@ -34,17 +73,25 @@ pub const MAX_SOCIAL_BONUS: f64 = 2.0; // Max 3x multiplier from collaboration
 // Virtual token initial balance multiplier
 pub const INITIAL_BALANCE_MULTIPLIER: u64 = 10; // entry_fee * 10
 
-// Account space allocations (for rent calculation)
-pub const GAME_ACCOUNT_SIZE: usize = 8 + 8 + 64 + 32 + 8 + 1 + 1 + 8 + 8 + 1 + 8 
-    + (32 * 100) + 1 + (32 * 100) + 1 + 8 + 8 + 8 + 24 + 1 + 1 + 8 + 8 + 1 + 1 + 33 + 8;
-
-pub const PLAYER_STATE_SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1 + 40 + 1 + 1 
-    + (33 * 10) + 1 + 1 + 1;
-
-pub const POOL_STATE_SIZE: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 1 + 1 + 8;
-
-pub const CHALLENGE_SIZE: usize = 8 + 8 + 8 + 32 + 32 + 8 + 1 + 1 + 8 + 9 + 9 + 33 + 1;
-
-pub const PHASE3_READY_SIZE: usize = 8 + 8 + 32 + 1 + 8 + 1;
+// GlobalConfig hard bounds - the admin can only tune parameters within these
+pub const MIN_CONFIG_WINDOW: i64 = 60; // Grace periods and windows: 1 minute..
+pub const MAX_CONFIG_WINDOW: i64 = 86400; // ..to 1 day
+pub const MIN_SWEEP_GRACE_PERIOD: i64 = 86400; // 1 day
+pub const MAX_SWEEP_GRACE_PERIOD: i64 = 7776000; // 90 days
+pub const MAX_PLATFORM_FEE_PERCENTAGE: u64 = 10;
+pub const MAX_ADMIN_SHARE_NO_READY: u64 = 50;
+pub const MAX_INITIAL_BALANCE_MULTIPLIER: u64 = 1000;
+pub const MAX_BASE_RATE: f64 = 1000.0;
+pub const MAX_SOCIAL_BONUS_LIMIT: f64 = 10.0;
+pub const MAX_PLAYERS_LIMIT: u16 = 10000;
+pub const MAX_PHASE2_GAMES_LIMIT: u8 = 50;
+pub const MAX_OPPONENT_DECLINES_LIMIT: u8 = 20;
+pub const MIN_PHASE_DURATION_LIMIT: u64 = 60; // 1 minute
+pub const MAX_PHASE_DURATION_LIMIT: u64 = 2592000; // 30 days
+pub const MAX_CREATOR_BOND: u64 = 10000000000; // 10 SOL
+pub const MAX_CRANK_REFUND_TIP: u64 = 1000000; // 0.001 SOL, below a player entry's rent
+pub const MIN_ADMIN_ACTION_DELAY: i64 = 600; // 10 minutes
+pub const MAX_ADMIN_ACTION_DELAY: i64 = 604800; // 7 days
+pub const MAX_TRACKED_OPPONENTS_LIMIT: u8 = 50;
 
-pub const REGISTRY_SIZE: usize = 8 + 8 + 8 + 32;
+// Account sizes are derived with InitSpace - see each account's SIZE
@@ -172,4 +172,210 @@ pub enum GameError {
     
     #[msg("Math operation would overflow")]
     MathOverflow,
+    
+    // Token errors
+    #[msg("Token accounts are required for games played with an SPL token")]
+    MissingTokenAccounts,
+    
+    #[msg("Token account does not match the game mint or owner")]
+    InvalidTokenAccount,
+    
+    #[msg("Mint uses a token extension that is not supported for game stakes")]
+    UnsupportedMintExtension,
+    
+    // Vault errors
+    #[msg("Vault holds less than the game's prize pool and fees")]
+    VaultBalanceMismatch,
+    
+    #[msg("Game still holds player funds and cannot be closed")]
+    GameNotSettled,
+    
+    // Cleanup errors
+    #[msg("Game has not finished yet")]
+    GameNotFinished,
+    
+    #[msg("Accounts can only be swept after the grace period")]
+    SweepGracePeriodActive,
+    
+    // Input errors
+    #[msg("Game name is too long")]
+    NameTooLong,
+    
+    // Migration errors
+    #[msg("Account already uses the current layout")]
+    AlreadyMigrated,
+    
+    #[msg("Account is not the next player entry to migrate")]
+    InvalidRosterEntry,
+    
+    #[msg("Game is still being migrated")]
+    MigrationPending,
+    
+    // Config errors
+    #[msg("Config parameter is outside its allowed bounds")]
+    InvalidConfig,
+    
+    // Rule errors
+    #[msg("Game rules are outside the allowed bounds")]
+    InvalidGameRules,
+    
+    #[msg("This mini-game is not allowed in this game")]
+    MiniGameNotAllowed,
+    
+    #[msg("Bet exceeds the game's maximum bet")]
+    BetTooLarge,
+    
+    // Admin transfer errors
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+    
+    #[msg("Only the proposed admin can accept")]
+    NotPendingAdmin,
+    
+    #[msg("Admin proposal has expired")]
+    AdminProposalExpired,
+    
+    // Council errors
+    #[msg("Council needs 1-10 distinct members and a threshold between 1 and the member count")]
+    InvalidCouncil,
+    
+    #[msg("Only council members can do this")]
+    NotCouncilMember,
+    
+    #[msg("Member already approved this proposal")]
+    AlreadyApproved,
+    
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    
+    #[msg("Proposal is for a different action")]
+    ProposalMismatch,
+    
+    #[msg("Proposal does not have enough approvals")]
+    ThresholdNotMet,
+    
+    #[msg("Admin game controls need council approval once a council is set")]
+    CouncilRequired,
+    
+    // Timelock errors
+    #[msg("Admin action is still in its notice period")]
+    TimelockNotElapsed,
+    
+    #[msg("Queued admin action has lapsed")]
+    QueuedActionExpired,
+    
+    // Pause errors
+    #[msg("Program is paused")]
+    ProgramPaused,
+    
+    #[msg("Game is paused")]
+    GamePaused,
+    
+    #[msg("Pause flag already has that value")]
+    PauseUnchanged,
+    
+    // Role errors
+    #[msg("Signer lacks the required role")]
+    MissingRole,
+    
+    #[msg("Unknown role bits")]
+    InvalidRoles,
+    
+    #[msg("Role registry is full")]
+    RoleRegistryFull,
+    
+    // Treasury errors
+    #[msg("Treasury balance too low for this withdrawal")]
+    InsufficientTreasuryBalance,
+    
+    #[msg("Treasury is required to collect a forfeited creator bond")]
+    TreasuryRequired,
+    
+    // Creator bond errors
+    #[msg("Creator bond account is required while the game holds a bond")]
+    CreatorBondRequired,
+    
+    // Leave errors
+    #[msg("Too close to the start time to leave the game")]
+    LeaveWindowClosed,
+    
+    // Waitlist errors
+    #[msg("Game has free seats, enter it directly")]
+    SeatsAvailable,
+    
+    #[msg("Only the head of the waitlist can be promoted")]
+    WaitlistOutOfOrder,
+    
+    #[msg("Waitlist entry can still be promoted")]
+    WaitlistStillOpen,
+    
+    #[msg("Waitlist accounts don't match this game")]
+    WaitlistMismatch,
+    
+    #[msg("Players are queued for this game, join the waitlist")]
+    WaitlistQueued,
+    
+    #[msg("Waitlist accounts are required while players are queued")]
+    WaitlistRequired,
+    
+    // Access errors
+    #[msg("Access root must be set for private games and empty for public ones")]
+    InvalidGameAccess,
+    
+    #[msg("Wallet is not on this game's allowlist")]
+    NotOnAllowlist,
+    
+    #[msg("Invalid invite code")]
+    InvalidInviteCode,
+    
+    // Template errors
+    #[msg("Invalid template schedule")]
+    InvalidTemplateSchedule,
+    
+    #[msg("Template is paused")]
+    TemplateInactive,
+    
+    #[msg("Next game from this template isn't due yet")]
+    TemplateNotDue,
+    
+    #[msg("Template budget can't cover the new game's rent and creator bond")]
+    TemplateUnderfunded,
+    
+    #[msg("Accounts don't match the template")]
+    TemplateMismatch,
+    
+    // Sponsor errors
+    #[msg("Game no longer accepts sponsorships")]
+    SponsorshipClosed,
+    
+    #[msg("Invalid sponsorship")]
+    InvalidSponsorship,
+    
+    // Squad errors
+    #[msg("Only squad games have squads")]
+    NotSquadGame,
+    
+    #[msg("Squad games need squad accounts")]
+    SquadGame,
+    
+    #[msg("Invalid squad")]
+    InvalidSquad,
+    
+    #[msg("Squad is full")]
+    SquadFull,
+    
+    #[msg("Player is not in this squad")]
+    NotSquadMember,
+    
+    #[msg("Leave your squad first")]
+    LeaveSquadFirst,
+    
+    #[msg("Every player must be in a squad of at least two, with at least two squads")]
+    SquadsIncomplete,
+    
+    #[msg("Can't challenge your own squad")]
+    SameSquad,
+    
+    #[msg("Balance is locked in an allocation")]
+    AllocationActive,
 }
@@ -2,6 +2,7 @@
 // These events help track game state changes and player actions
 
 use anchor_lang::prelude::*;
+use crate::state::{AccessMode, AdminAction, ConfigParams, RuleBounds};
 
 // Game lifecycle events
 
@@ -10,17 +11,60 @@ pub struct GameCreated {
     pub game_id: u64,
     pub creator: Pubkey,
     pub entry_fee: u64,
-    pub max_players: u8,
+    pub max_players: u16,
     pub start_time: i64,
+    pub mint: Option<Pubkey>,
     pub phase2_required_games: u8,
     pub phase2_max_games_per_opponent: u8,
+    pub creator_fee_bps: u16,
+    pub creator_bond: u64,
+    pub access_mode: AccessMode,
 }
 
 #[event]
 pub struct PlayerJoined {
     pub game_id: u64,
     pub player: Pubkey,
-    pub current_players: u8,
+    pub current_players: u16,
+}
+
+#[event]
+pub struct PlayerLeft {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub current_players: u16,
+}
+
+// Waitlist events
+#[event]
+pub struct WaitlistJoined {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub position: u32,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WaitlistPromoted {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub position: u32,
+    pub current_players: u16,
+}
+
+#[event]
+pub struct WaitlistSkipped {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub position: u32,
+}
+
+#[event]
+pub struct WaitlistRefunded {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
 }
 
 #[event]
@@ -43,6 +87,19 @@ pub struct GameExpiredWithPenalty {
     pub reason: String,
 }
 
+#[event]
+pub struct GameClosed {
+    pub game_id: u64,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct GameAccountsSwept {
+    pub game_id: u64,
+    pub authority: Pubkey,
+    pub accounts_closed: u32,
+}
+
 // Refund events
 
 #[event]
@@ -59,6 +116,14 @@ pub struct ForcedRefundClaimed {
     pub amount: u64,
 }
 
+#[event]
+pub struct RefundsCranked {
+    pub game_id: u64,
+    pub cranker: Pubkey,
+    pub players_refunded: u16,
+    pub tips: u64,
+}
+
 // Phase progression events
 
 #[event]
@@ -76,6 +141,7 @@ pub struct ChallengeCreated {
     pub game_id: u64,
     pub challenger: Pubkey,
     pub opponent: Pubkey,
+    pub opponent_squad: Option<Pubkey>,
     pub bet_amount: u64,
 }
 
@@ -115,6 +181,241 @@ pub struct Phase3PrizeClaimed {
 pub struct GameClosedNoReady {
     pub game_id: u64,
     pub platform_fee: u64,
-    pub purge_players: u8,
+    pub purge_players: u16,
     pub share_per_player: u64,
 }
+
+// Admin events
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+// Council events
+
+#[event]
+pub struct CouncilUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct AdminProposalCreated {
+    pub proposal_id: u64,
+    pub game_id: u64,
+    pub action: AdminAction,
+    pub proposer: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AdminProposalApproved {
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct AdminProposalExecuted {
+    pub proposal_id: u64,
+    pub game_id: u64,
+    pub action: AdminAction,
+    pub executor: Pubkey,
+}
+
+// Timelock events
+
+#[event]
+pub struct AdminActionQueued {
+    pub game_id: u64,
+    pub action: AdminAction,
+    pub queued_by: Pubkey,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct AdminActionCancelled {
+    pub game_id: u64,
+    pub action: AdminAction,
+}
+
+// Pause events
+
+#[event]
+pub struct ProgramPauseUpdated {
+    pub paused: bool,
+    pub admin: Pubkey,
+    pub paused_for: i64,
+}
+
+#[event]
+pub struct GamePauseUpdated {
+    pub game_id: u64,
+    pub paused: bool,
+    pub paused_for: i64,
+}
+
+// Role events
+
+#[event]
+pub struct RolesUpdated {
+    pub member: Pubkey,
+    pub roles: u8,
+    pub updated_by: Pubkey,
+}
+
+// Creator bond events
+#[event]
+pub struct CreatorBondReturned {
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorBondSlashed {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+// Creator fee events
+#[event]
+pub struct CreatorFeeAccrued {
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+}
+
+#[event]
+pub struct CreatorFeeClaimed {
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+}
+
+// Template events
+#[event]
+pub struct TemplateUpdated {
+    pub template: Pubkey,
+    pub operator: Pubkey,
+    pub cadence: i64,
+    pub next_start_time: i64,
+    pub active: bool,
+}
+
+#[event]
+pub struct TemplateGameSpawned {
+    pub template: Pubkey,
+    pub game_id: u64,
+    pub start_time: i64,
+    pub spawner: Pubkey,
+    pub reimbursed: u64,
+}
+
+// Sponsor events
+#[event]
+pub struct GameSponsored {
+    pub game_id: u64,
+    pub sponsor: Pubkey,
+    pub display_name: String,
+    pub amount: u64,
+    pub fee_eligible: bool,
+    pub total_sponsored: u64,
+}
+
+#[event]
+pub struct SponsorRefunded {
+    pub game_id: u64,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+}
+
+// Squad events
+#[event]
+pub struct SquadCreated {
+    pub game_id: u64,
+    pub squad: Pubkey,
+    pub leader: Pubkey,
+    pub name: String,
+    pub shares: Vec<u16>,
+}
+
+#[event]
+pub struct SquadChanged {
+    pub game_id: u64,
+    pub squad: Pubkey,
+    pub player: Pubkey,
+    pub joined: bool,
+    pub members: u8,
+}
+
+#[event]
+pub struct SquadBalanceShared {
+    pub game_id: u64,
+    pub squad: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SquadPrizeClaimed {
+    pub game_id: u64,
+    pub squad: Pubkey,
+    pub member: Pubkey,
+    pub amount: u64,
+}
+
+// Treasury events
+#[event]
+pub struct PlatformFeeCollected {
+    pub game_id: u64,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub game_total: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawal {
+    pub treasurer: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+}
+
+// Monitoring events
+
+#[event]
+pub struct AuditResult {
+    pub game_id: u64,
+    pub vault_balance: u64,
+    pub expected_balance: u64,
+    pub balance_ok: bool,
+    pub player_count_ok: bool,
+    pub refunds_ok: bool,
+    pub timestamps_ok: bool,
+    pub passed: bool,
+}
+
+// Config events
+
+#[event]
+pub struct GlobalConfigUpdated {
+    pub admin: Pubkey,
+    pub params: ConfigParams,
+    pub rule_bounds: RuleBounds,
+}
@@ -0,0 +1,82 @@
+// Game fund audit - read-only invariant checks for monitoring
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::Game;
+use crate::events::AuditResult;
+use crate::errors::GameError;
+use crate::utils::{game_vault, vault_balance};
+
+/// Check a game's accounting invariants and emit an AuditResult event
+/// Never modifies state and never fails on a broken invariant - monitoring
+/// jobs read the event (or simulate the transaction) to spot accounting bugs
+pub fn audit_game(ctx: Context<AuditGame>) -> Result<()> {
+    let game = &ctx.accounts.game.load()?;
+    
+    // Vault must hold everything the game still owes
+    // (refunds and payouts are already subtracted from prize_pool)
+    let vault = game_vault(
+        &ctx.accounts.game,
+        game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let vault_balance = vault_balance(&vault)?;
+    let expected_balance = game.prize_pool
+        .checked_add(game.platform_fee_collected)
+        .and_then(|owed| owed.checked_add(game.creator_fee_collected))
+        .and_then(|owed| owed.checked_add(game.waitlist_escrow))
+        .ok_or(GameError::MathOverflow)?;
+    let balance_ok = vault_balance >= expected_balance;
+    
+    // Player counters stay within bounds
+    let player_count_ok = game.current_players <= game.max_players;
+    
+    // Only players who entered can have been refunded
+    let refunds_ok = game.refunded_count <= game.current_players;
+    
+    // Timestamps only move forward
+    let mut timestamps_ok = game.start_time <= game.expire_time;
+    if game.game_started != 0 {
+        timestamps_ok &= game.phase_start_time <= game.phase_end_time
+            && game.phase_end_time <= game.phase_advance_deadline;
+    }
+    if game.phase3_ready_deadline > 0 && game.phase3_extended_deadline > 0 {
+        timestamps_ok &= game.phase3_ready_deadline <= game.phase3_extended_deadline;
+    }
+    
+    emit!(AuditResult {
+        game_id: game.game_id,
+        vault_balance,
+        expected_balance,
+        balance_ok,
+        player_count_ok,
+        refunds_ok,
+        timestamps_ok,
+        passed: balance_ok && player_count_ok && refunds_ok && timestamps_ok,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AuditGame<'info> {
+    pub game: AccountLoader<'info, Game>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,100 @@
+// Global config management - create and tune the GlobalConfig account
+
+use anchor_lang::prelude::*;
+use crate::state::{ConfigParams, GameRegistry, GlobalConfig, RuleBounds};
+use crate::events::GlobalConfigUpdated;
+use crate::errors::GameError;
+
+/// Admin creates the global config with the compile-time defaults
+pub fn initialize_global_config(ctx: Context<InitializeGlobalConfig>) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.game_registry.admin,
+        GameError::NotAdmin
+    );
+    
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.version = GlobalConfig::VERSION;
+    global_config.params = ConfigParams::default();
+    global_config.bump = ctx.bumps.global_config;
+    global_config.rule_bounds = RuleBounds::default();
+    
+    emit!(GlobalConfigUpdated {
+        admin: ctx.accounts.admin.key(),
+        params: global_config.params,
+        rule_bounds: global_config.rule_bounds,
+    });
+    
+    Ok(())
+}
+
+/// Admin replaces the tuning parameters
+/// Running games keep the snapshot they were created with
+pub fn update_global_config(ctx: Context<UpdateGlobalConfig>, params: ConfigParams) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.game_registry.admin,
+        GameError::NotAdmin
+    );
+    params.validate()?;
+    
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.params = params;
+    
+    emit!(GlobalConfigUpdated {
+        admin: ctx.accounts.admin.key(),
+        params,
+        rule_bounds: global_config.rule_bounds,
+    });
+    
+    Ok(())
+}
+
+/// Admin replaces the bounds on per-game rules
+pub fn update_rule_bounds(ctx: Context<UpdateGlobalConfig>, rule_bounds: RuleBounds) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.game_registry.admin,
+        GameError::NotAdmin
+    );
+    rule_bounds.validate()?;
+    
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.rule_bounds = rule_bounds;
+    
+    emit!(GlobalConfigUpdated {
+        admin: ctx.accounts.admin.key(),
+        params: global_config.params,
+        rule_bounds,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = GlobalConfig::SIZE,
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    #[account(mut, seeds = [b"global_config"], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,341 @@
+// Admin council - proposals that need M-of-N approvals before an emergency
+// control runs. Once approved, anyone can execute the proposal.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{AdminAction, AdminCouncil, AdminProposal, Game, GameRegistry};
+use crate::events::{AdminProposalApproved, AdminProposalCreated, AdminProposalExecuted, CouncilUpdated};
+use crate::errors::GameError;
+use crate::constants::{ADMIN_PROPOSAL_LIFETIME, MAX_COUNCIL_MEMBERS};
+use crate::utils::game_vault;
+use super::game_control::{force_advance_phase, force_start, settle_purge_no_ready};
+
+/// Admin creates or replaces the council members and approval threshold
+/// remaining_accounts holds open proposals; approvals of removed members are
+/// dropped from them, so a member re-added later has to approve again
+pub fn set_admin_council<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetAdminCouncil<'info>>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.game_registry.admin,
+        GameError::NotAdmin
+    );
+    require!(
+        !members.is_empty()
+            && members.len() <= MAX_COUNCIL_MEMBERS
+            && threshold >= 1
+            && threshold as usize <= members.len(),
+        GameError::InvalidCouncil
+    );
+    for (i, member) in members.iter().enumerate() {
+        require!(!members[..i].contains(member), GameError::InvalidCouncil);
+    }
+    
+    let council = &mut ctx.accounts.admin_council;
+    if council.version == 0 {
+        council.version = AdminCouncil::VERSION;
+        council.proposal_count = 0;
+        council.bump = ctx.bumps.admin_council;
+    }
+    council.members = members;
+    council.threshold = threshold;
+    
+    for proposal_info in ctx.remaining_accounts {
+        let mut proposal = Account::<AdminProposal>::try_from(proposal_info)?;
+        proposal.approvals.retain(|key| council.is_member(key));
+        proposal.exit(&crate::ID)?;
+    }
+    
+    emit!(CouncilUpdated {
+        members: council.members.clone(),
+        threshold,
+    });
+    
+    Ok(())
+}
+
+/// Council member proposes an emergency action on a game; the proposer's
+/// approval is counted immediately
+pub fn create_admin_proposal(ctx: Context<CreateAdminProposal>, game_id: u64, action: AdminAction) -> Result<()> {
+    let council = &mut ctx.accounts.admin_council;
+    let proposal = &mut ctx.accounts.proposal;
+    let proposer = ctx.accounts.proposer.key();
+    let clock = Clock::get()?;
+    
+    require!(council.is_member(&proposer), GameError::NotCouncilMember);
+    
+    proposal.version = AdminProposal::VERSION;
+    proposal.proposal_id = council.proposal_count;
+    proposal.game_id = game_id;
+    proposal.action = action;
+    proposal.proposer = proposer;
+    proposal.approvals = vec![proposer];
+    proposal.created_at = clock.unix_timestamp;
+    proposal.expires_at = clock.unix_timestamp + ADMIN_PROPOSAL_LIFETIME;
+    proposal.bump = ctx.bumps.proposal;
+    council.proposal_count += 1;
+    
+    emit!(AdminProposalCreated {
+        proposal_id: proposal.proposal_id,
+        game_id,
+        action,
+        proposer,
+        expires_at: proposal.expires_at,
+    });
+    
+    Ok(())
+}
+
+/// Council member approves a pending proposal
+pub fn approve_admin_proposal(ctx: Context<ApproveAdminProposal>) -> Result<()> {
+    let council = &ctx.accounts.admin_council;
+    let proposal = &mut ctx.accounts.proposal;
+    let approver = ctx.accounts.approver.key();
+    let clock = Clock::get()?;
+    
+    require!(council.is_member(&approver), GameError::NotCouncilMember);
+    require!(clock.unix_timestamp < proposal.expires_at, GameError::ProposalExpired);
+    require!(!proposal.approvals.contains(&approver), GameError::AlreadyApproved);
+    
+    // Approvals of members removed since then no longer count or take up room
+    proposal.approvals.retain(|key| council.is_member(key));
+    proposal.approvals.push(approver);
+    
+    emit!(AdminProposalApproved {
+        proposal_id: proposal.proposal_id,
+        approver,
+        approvals: council.count_approvals(&proposal.approvals) as u8,
+        threshold: council.threshold,
+    });
+    
+    Ok(())
+}
+
+/// Proposer withdraws a proposal that was not executed and reclaims its rent
+pub fn cancel_admin_proposal(_ctx: Context<CancelAdminProposal>) -> Result<()> {
+    Ok(())
+}
+
+/// Execute an approved StartGame proposal
+pub fn execute_start_game(ctx: Context<ExecuteGameProposal>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    check_executable(&ctx.accounts.admin_council, &ctx.accounts.proposal, game, AdminAction::StartGame)?;
+    force_start(game, &ctx.accounts.game_registry)?;
+    emit_executed(&ctx.accounts.proposal, ctx.accounts.executor.key());
+    
+    Ok(())
+}
+
+/// Execute an approved AdvancePhase proposal
+pub fn execute_advance_phase(ctx: Context<ExecuteGameProposal>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    check_executable(&ctx.accounts.admin_council, &ctx.accounts.proposal, game, AdminAction::AdvancePhase)?;
+    force_advance_phase(game, &ctx.accounts.game_registry)?;
+    emit_executed(&ctx.accounts.proposal, ctx.accounts.executor.key());
+    
+    Ok(())
+}
+
+/// Execute an approved ClosePurgeNoReady proposal
+/// As with `admin_close_purge_no_ready`, the admin share becomes platform fee
+/// collected into the treasury; remaining_accounts holds (player_state, payout
+/// account) pairs
+pub fn execute_close_purge_no_ready<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteClosePurgeNoReady<'info>>
+) -> Result<()> {
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
+    check_executable(&ctx.accounts.admin_council, &ctx.accounts.proposal, &game, AdminAction::ClosePurgeNoReady)?;
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    settle_purge_no_ready(&mut game, &ctx.accounts.game_registry, &vault, ctx.remaining_accounts)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    emit_executed(&ctx.accounts.proposal, ctx.accounts.executor.key());
+    
+    Ok(())
+}
+
+/// A proposal can run once the game's notice period is over, it is unexpired,
+/// matches the action and has enough approvals from current council members
+fn check_executable(council: &AdminCouncil, proposal: &AdminProposal, game: &Game, action: AdminAction) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(proposal.action == action, GameError::ProposalMismatch);
+    require!(
+        clock.unix_timestamp >= proposal.created_at + game.config.admin_action_delay,
+        GameError::TimelockNotElapsed
+    );
+    require!(clock.unix_timestamp < proposal.expires_at, GameError::ProposalExpired);
+    require!(
+        council.count_approvals(&proposal.approvals) >= council.threshold as usize,
+        GameError::ThresholdNotMet
+    );
+    
+    Ok(())
+}
+
+fn emit_executed(proposal: &AdminProposal, executor: Pubkey) {
+    emit!(AdminProposalExecuted {
+        proposal_id: proposal.proposal_id,
+        game_id: proposal.game_id,
+        action: proposal.action,
+        executor,
+    });
+}
+
+#[derive(Accounts)]
+pub struct SetAdminCouncil<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = AdminCouncil::SIZE,
+        seeds = [b"admin_council"],
+        bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CreateAdminProposal<'info> {
+    #[account(mut, seeds = [b"admin_council"], bump = admin_council.bump)]
+    pub admin_council: Account<'info, AdminCouncil>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = AdminProposal::SIZE,
+        seeds = [b"admin_proposal", admin_council.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    
+    /// Target game - must exist
+    #[account(seeds = [b"game", game_id.to_le_bytes().as_ref()], bump)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAdminProposal<'info> {
+    #[account(seeds = [b"admin_council"], bump = admin_council.bump)]
+    pub admin_council: Account<'info, AdminCouncil>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminProposal<'info> {
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer,
+        seeds = [b"admin_proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteGameProposal<'info> {
+    #[account(seeds = [b"admin_council"], bump = admin_council.bump)]
+    pub admin_council: Account<'info, AdminCouncil>,
+    
+    /// Closed on execution so it can't run twice; rent goes back to the proposer
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer,
+        seeds = [b"admin_proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    
+    /// CHECK: receives the proposal rent; checked by `has_one`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"game", proposal.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteClosePurgeNoReady<'info> {
+    #[account(seeds = [b"admin_council"], bump = admin_council.bump)]
+    pub admin_council: Account<'info, AdminCouncil>,
+    
+    /// Closed on execution so it can't run twice; rent goes back to the proposer
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer,
+        seeds = [b"admin_proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    
+    /// CHECK: receives the proposal rent; checked by `has_one`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"game", proposal.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", proposal.game_id.to_le_bytes().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub executor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
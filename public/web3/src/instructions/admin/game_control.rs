@@ -1,26 +1,48 @@
 // Admin game controls - emergency start, phase advancement, and edge case handling
+// Each one consumes a matching action queued through the timelock
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, GameRegistry, GameStatus, PlayerGameState};
-use crate::events::{GameStarted, PhaseAdvanced, GameClosedNoReady};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    require_role, AdminAction, AdminCouncil, Game, GameRegistry, GameStatus, PlayerGameState, QueuedAdminAction,
+    Role, RoleRegistry,
+};
+use crate::events::{CreatorFeeAccrued, GameStarted, PhaseAdvanced, GameClosedNoReady};
 use crate::errors::GameError;
-use crate::constants::{MIN_PLAYERS_TO_START, PHASE_ADVANCE_BUFFER, ADMIN_SHARE_NO_READY};
+use crate::utils::{check_vault_balance, game_vault, pay_from_game, GameVault};
 
-/// Admin can start a game if creator is unresponsive
+/// Operator can start a game if creator is unresponsive
 pub fn admin_start_game(ctx: Context<AdminStartGame>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &mut ctx.accounts.game.load_mut()?;
     let game_registry = &ctx.accounts.game_registry;
     let clock = Clock::get()?;
     
-    // Verify admin
+    // Verify operator
+    require_role(
+        game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.admin.key(),
+        Role::Operator,
+    )?;
+    AdminCouncil::require_absent(&ctx.accounts.admin_council)?;
+    ctx.accounts.queued_action.check_ready(AdminAction::StartGame, clock.unix_timestamp)?;
+    
+    force_start(game, game_registry)
+}
+
+/// Start a game on the admin's behalf - shared with council execution
+pub(crate) fn force_start(game: &mut Game, game_registry: &GameRegistry) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    game.sync_pause(game_registry)?;
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
     require!(
-        ctx.accounts.admin.key() == game_registry.admin,
-        GameError::NotAdmin
+        game.current_players >= game.config.min_players_to_start,
+        GameError::NotEnoughPlayers
     );
-    require!(!game.game_started, GameError::GameAlreadyStarted);
     require!(
-        game.current_players >= MIN_PLAYERS_TO_START,
-        GameError::NotEnoughPlayers
+        !game.is_squad_game() || game.squads_complete(),
+        GameError::SquadsIncomplete
     );
     require!(
         clock.unix_timestamp >= game.start_time,
@@ -28,12 +50,12 @@ pub fn admin_start_game(ctx: Context<AdminStartGame>) -> Result<()> {
     );
     
     // Start the game
-    game.game_started = true;
-    game.status = GameStatus::InProgress;
+    game.game_started = 1;
+    game.status = GameStatus::InProgress as u8;
     game.current_phase = 1;
     game.phase_start_time = clock.unix_timestamp;
     game.phase_end_time = clock.unix_timestamp + game.phases.phase1_duration as i64;
-    game.phase_advance_deadline = game.phase_end_time + PHASE_ADVANCE_BUFFER;
+    game.phase_advance_deadline = game.phase_end_time + game.config.phase_advance_buffer;
     
     emit!(GameStarted {
         game_id: game.game_id,
@@ -44,18 +66,32 @@ pub fn admin_start_game(ctx: Context<AdminStartGame>) -> Result<()> {
     Ok(())
 }
 
-/// Admin can advance phase anytime (emergency control)
+/// Operator can advance phase once the queued notice has passed
 pub fn admin_advance_phase(ctx: Context<AdminAdvancePhase>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &mut ctx.accounts.game.load_mut()?;
     let game_registry = &ctx.accounts.game_registry;
     let clock = Clock::get()?;
     
-    // Verify admin
-    require!(
-        ctx.accounts.admin.key() == game_registry.admin,
-        GameError::NotAdmin
-    );
-    require!(game.game_started, GameError::GameNotStarted);
+    // Verify operator
+    require_role(
+        game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.admin.key(),
+        Role::Operator,
+    )?;
+    AdminCouncil::require_absent(&ctx.accounts.admin_council)?;
+    ctx.accounts.queued_action.check_ready(AdminAction::AdvancePhase, clock.unix_timestamp)?;
+    
+    force_advance_phase(game, game_registry)
+}
+
+/// Advance a game to its next phase regardless of timing - shared with
+/// council execution
+pub(crate) fn force_advance_phase(game: &mut Game, game_registry: &GameRegistry) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    game.sync_pause(game_registry)?;
+    require!(game.game_started != 0, GameError::GameNotStarted);
     require!(game.current_phase < 3, GameError::InvalidPhase);
     
     game.current_phase += 1;
@@ -64,12 +100,13 @@ pub fn admin_advance_phase(ctx: Context<AdminAdvancePhase>) -> Result<()> {
     match game.current_phase {
         2 => {
             game.phase_end_time = clock.unix_timestamp + game.phases.phase2_duration as i64;
-            game.phase_advance_deadline = game.phase_end_time + PHASE_ADVANCE_BUFFER;
+            game.phase_advance_deadline = game.phase_end_time + game.config.phase_advance_buffer;
         }
         3 => {
             game.phase_end_time = clock.unix_timestamp + game.phases.phase3_duration as i64;
-            game.phase3_ready_deadline = clock.unix_timestamp + 300; // 5 minutes
-            game.phase3_extended_deadline = clock.unix_timestamp + 900; // 15 minutes total
+            // Same ready windows as `start_phase3`; the extension starts once nobody is ready
+            game.phase3_ready_deadline = clock.unix_timestamp + game.config.phase3_ready_window;
+            game.phase3_extended_deadline = 0;
         }
         _ => {}
     }
@@ -83,19 +120,53 @@ pub fn admin_advance_phase(ctx: Context<AdminAdvancePhase>) -> Result<()> {
     Ok(())
 }
 
-/// Admin closes purge if no players ready - redistributes 25% to admin, 75% to eligible players
+/// Operator closes purge if no players ready - 25% becomes platform fee, 75% goes to eligible players
 pub fn admin_close_purge_no_ready<'info>(
     ctx: Context<'_, '_, 'info, 'info, AdminClosePurgeNoReady<'info>>
 ) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
     let game_registry = &ctx.accounts.game_registry;
     let clock = Clock::get()?;
     
-    // Verify admin
-    require!(
-        ctx.accounts.admin.key() == game_registry.admin,
-        GameError::NotAdmin
-    );
+    // Verify operator
+    require_role(
+        game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.admin.key(),
+        Role::Operator,
+    )?;
+    AdminCouncil::require_absent(&ctx.accounts.admin_council)?;
+    ctx.accounts.queued_action.check_ready(AdminAction::ClosePurgeNoReady, clock.unix_timestamp)?;
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    settle_purge_no_ready(&mut game, game_registry, &vault, ctx.remaining_accounts)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    Ok(())
+}
+
+/// Pay out a purge nobody was ready for: the admin share (plus rounding dust)
+/// becomes platform fee for the treasury, the rest is split between players
+/// who met Phase 2. Shared with council execution; the caller stores `game`
+/// afterwards.
+pub(crate) fn settle_purge_no_ready<'info>(
+    game: &mut Game,
+    game_registry: &GameRegistry,
+    vault: &GameVault<'_, 'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    game.sync_pause(game_registry)?;
     require!(game.current_phase == 3, GameError::InvalidPhase);
     require!(
         clock.unix_timestamp > game.phase3_extended_deadline,
@@ -103,60 +174,88 @@ pub fn admin_close_purge_no_ready<'info>(
     );
     require!(game.phase3_players_ready == 0, GameError::SomePlayersReady);
     
-    // Calculate redistribution
-    let platform_fee = game.prize_pool
-        .checked_mul(ADMIN_SHARE_NO_READY)
+    // Calculate redistribution - fee-exempt sponsor funds all go to players
+    let admin_share = game.fee_base()
+        .checked_mul(game.config.admin_share_no_ready)
         .ok_or(GameError::MathOverflow)?
         .checked_div(100)
         .ok_or(GameError::InvalidCalculation)?;
     
     let player_share_total = game.prize_pool
-        .checked_sub(platform_fee)
+        .checked_sub(admin_share)
         .ok_or(GameError::MathOverflow)?;
     
     // Find players who met Phase 2 requirements
-    let mut purge_players = Vec::new();
-    for player_pubkey in &game.players {
-        for acc in ctx.remaining_accounts.iter() {
-            if acc.key() == *player_pubkey {
-                if let Ok(player_state) = Account::<PlayerGameState>::try_from(acc) {
-                    if player_state.phase2_requirement_met {
-                        purge_players.push(*player_pubkey);
-                    }
-                }
-                break;
-            }
+    // remaining_accounts holds (player_state, payout account) pairs - the payout
+    // account is the player's wallet for SOL games and token account for token games
+    let mut purge_players: Vec<(Pubkey, &AccountInfo<'info>)> = Vec::new();
+    for pair in remaining_accounts.chunks_exact(2) {
+        let (state_info, payout_info) = (&pair[0], &pair[1]);
+        
+        let player_state = match Account::<PlayerGameState>::try_from(state_info) {
+            Ok(player_state) => player_state,
+            Err(_) => continue,
+        };
+        let player_pubkey = player_state.player;
+        
+        // Player states are only created for players holding a PlayerEntry
+        if player_state.game_id != game.game_id
+            || !player_state.phase2_requirement_met
+            || purge_players.iter().any(|(key, _)| *key == player_pubkey)
+        {
+            continue;
+        }
+        
+        let payout_valid = match game.stake_mint() {
+            Some(mint) => InterfaceAccount::<TokenAccount>::try_from(payout_info)
+                .map(|token_account| token_account.mint == mint && token_account.owner == player_pubkey)
+                .unwrap_or(false),
+            None => payout_info.key() == player_pubkey,
+        };
+        
+        if payout_valid {
+            purge_players.push((player_pubkey, payout_info));
         }
     }
     
-    require!(purge_players.len() > 0, GameError::NoPurgePlayersFound);
+    require!(!purge_players.is_empty(), GameError::NoPurgePlayersFound);
     
     let share_per_player = player_share_total
         .checked_div(purge_players.len() as u64)
         .ok_or(GameError::InvalidCalculation)?;
     
-    // Transfer platform fee to admin
-    **game.to_account_info().try_borrow_mut_lamports()? -= platform_fee;
-    **ctx.accounts.admin.to_account_info().try_borrow_mut_lamports()? += platform_fee;
+    // Rounding dust from the split goes to the platform so the pool empties
+    let distributed = share_per_player
+        .checked_mul(purge_players.len() as u64)
+        .ok_or(GameError::MathOverflow)?;
+    let platform_fee = game.prize_pool
+        .checked_sub(distributed)
+        .ok_or(GameError::MathOverflow)?;
     
     // Distribute shares to eligible players
-    for player_pubkey in &purge_players {
-        for acc in ctx.remaining_accounts.iter() {
-            if acc.key() == *player_pubkey {
-                **game.to_account_info().try_borrow_mut_lamports()? -= share_per_player;
-                **acc.try_borrow_mut_lamports()? += share_per_player;
-                break;
-            }
-        }
+    for (_, payout_info) in &purge_players {
+        pay_from_game(game, vault, payout_info, share_per_player)?;
     }
     
-    game.status = GameStatus::Completed;
-    game.platform_fee_collected = platform_fee;
+    // Players were paid directly; the fees wait for their claim instructions
+    game.finish(GameStatus::Completed, clock.unix_timestamp);
+    game.prize_pool = 0;
+    let creator_fee = game.accrue_platform_fee(platform_fee);
+    check_vault_balance(game, vault)?;
+    
+    if creator_fee > 0 {
+        emit!(CreatorFeeAccrued {
+            game_id: game.game_id,
+            creator: game.creator,
+            platform_fee,
+            creator_fee,
+        });
+    }
     
     emit!(GameClosedNoReady {
         game_id: game.game_id,
         platform_fee,
-        purge_players: purge_players.len() as u8,
+        purge_players: purge_players.len() as u16,
         share_per_player,
     });
     
@@ -166,30 +265,98 @@ pub fn admin_close_purge_no_ready<'info>(
 #[derive(Accounts)]
 pub struct AdminStartGame<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
     
     pub game_registry: Account<'info, GameRegistry>,
     
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    /// Timelocked announcement of this action, consumed here
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"admin_action", game.load()?.game_id.to_le_bytes().as_ref(), &[AdminAction::StartGame as u8]],
+        bump = queued_action.bump
+    )]
+    pub queued_action: Account<'info, QueuedAdminAction>,
+    
+    /// CHECK: admin council PDA - must not exist for single-key controls
+    #[account(seeds = [b"admin_council"], bump)]
+    pub admin_council: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub admin: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct AdminAdvancePhase<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
     
     pub game_registry: Account<'info, GameRegistry>,
     
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    /// Timelocked announcement of this action, consumed here
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"admin_action", game.load()?.game_id.to_le_bytes().as_ref(), &[AdminAction::AdvancePhase as u8]],
+        bump = queued_action.bump
+    )]
+    pub queued_action: Account<'info, QueuedAdminAction>,
+    
+    /// CHECK: admin council PDA - must not exist for single-key controls
+    #[account(seeds = [b"admin_council"], bump)]
+    pub admin_council: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub admin: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct AdminClosePurgeNoReady<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
     
     pub game_registry: Account<'info, GameRegistry>,
     
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    /// Timelocked announcement of this action, consumed here
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"admin_action", game.load()?.game_id.to_le_bytes().as_ref(), &[AdminAction::ClosePurgeNoReady as u8]],
+        bump = queued_action.bump
+    )]
+    pub queued_action: Account<'info, QueuedAdminAction>,
+    
+    /// CHECK: admin council PDA - must not exist for single-key controls
+    #[account(seeds = [b"admin_council"], bump)]
+    pub admin_council: UncheckedAccount<'info>,
+    
     #[account(mut)]
     pub admin: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,432 @@
+// Account migration - upgrade live accounts to the current layouts
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use crate::state::{
+    AccessMode, Challenge, Game, GamePoolState, GameRegistry, GlobalConfig, LegacyChallenge, LegacyGame,
+    LegacyGamePoolState, LegacyGameRegistry, LegacyPhase3ReadyState, LegacyPlayerGameState, LegacyRoster,
+    MiniGameType, Phase3ReadyState, PlayerEntry, PlayerGameState, RosterPlayer,
+};
+use crate::errors::GameError;
+use crate::constants::{
+    LEAVE_LOCK_WINDOW, MAX_BET_PERCENTAGE, MAX_GAME_NAME_LEN, MAX_TRACKED_OPPONENTS, MIN_PLAYERS_TO_START,
+};
+
+/// Size of the deployed registry, which had no version byte
+const LEGACY_REGISTRY_SIZE: usize = 8 + 8 + 8 + 32;
+
+/// Read the pre-version layout of an account
+/// Accounts already at the current size were created or migrated with a version
+fn read_legacy<L: AnchorDeserialize>(info: &AccountInfo, discriminator: &[u8], size: usize) -> Result<L> {
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == *discriminator,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(data.len() != size, GameError::AlreadyMigrated);
+    
+    Ok(L::deserialize(&mut &data[8..])?)
+}
+
+/// Grow an account to `size`, topping up rent from `payer`
+fn resize_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    size: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(size);
+    if info.lamports() < rent {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            rent - info.lamports(),
+        )?;
+    }
+    info.resize(size)?;
+    
+    Ok(())
+}
+
+/// Upgrade a Borsh account from its legacy layout `L` to `T`
+fn migrate_account<'info, L, T>(
+    accounts: &MigrateAccount<'info>,
+    size: usize,
+    upgrade: impl FnOnce(L) -> T,
+) -> Result<()>
+where
+    L: AnchorDeserialize,
+    T: AccountSerialize + Discriminator,
+{
+    require!(
+        accounts.admin.key() == accounts.game_registry.admin,
+        GameError::NotAdmin
+    );
+    
+    let info = accounts.account.to_account_info();
+    let upgraded = upgrade(read_legacy::<L>(&info, T::DISCRIMINATOR, size)?);
+    resize_account(&info, &accounts.admin, &accounts.system_program, size)?;
+    
+    let mut data = info.try_borrow_mut_data()?;
+    upgraded.try_serialize(&mut &mut data[..])
+}
+
+/// Admin rewrites a game of the deployed Borsh layout in place as the
+/// zero-copy `Game`, deriving the PDA bumps the old layout didn't store.
+/// Settings added since then take their defaults: the global config
+/// snapshot, unrestricted challenges, a public solo game with no creator fee.
+/// Stakes held on the game account move to the vault PDA.
+/// The inline player list moves to a `LegacyRoster`; the game stays paused
+/// until `migrate_player_entries` has turned it into `PlayerEntry` PDAs, so
+/// no listed player can enter a second time in the meantime.
+pub fn migrate_game(ctx: Context<MigrateGame>, _game_id: u64) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.game_registry.admin,
+        GameError::NotAdmin
+    );
+    
+    let game_info = ctx.accounts.game.to_account_info();
+    let legacy: LegacyGame = read_legacy(&game_info, Game::DISCRIMINATOR, Game::SIZE)?;
+    
+    // Deployed games had unbounded names - keep the longest prefix that fits
+    let mut name_len = legacy.name.len().min(MAX_GAME_NAME_LEN);
+    while !legacy.name.is_char_boundary(name_len) {
+        name_len -= 1;
+    }
+    
+    // The deployed program never took refunds out of the prize pool, booked
+    // the platform fee inside it, and left both set once the no-ready purge
+    // had paid everything out
+    let refunded = legacy.refunded_players.len() as u64 * legacy.entry_fee;
+    let (prize_pool, platform_fee_collected) = match legacy.phase3_winner {
+        None if legacy.platform_fee_collected > 0 => (0, 0),
+        _ => (
+            legacy.prize_pool.saturating_sub(refunded).saturating_sub(legacy.platform_fee_collected),
+            legacy.platform_fee_collected,
+        ),
+    };
+    
+    // Deployed games always needed the default minimum to start, and their
+    // player states only have room for the default opponent records
+    let mut config = ctx.accounts.global_config.params;
+    config.min_players_to_start = MIN_PLAYERS_TO_START;
+    config.max_tracked_opponents = MAX_TRACKED_OPPONENTS;
+    resize_account(&game_info, &ctx.accounts.admin, &ctx.accounts.system_program, Game::SIZE)?;
+    
+    let mut data = game_info.try_borrow_mut_data()?;
+    let game: &mut Game = bytemuck::from_bytes_mut(&mut data[8..Game::SIZE]);
+    *game = Game {
+        game_id: legacy.game_id,
+        entry_fee: legacy.entry_fee,
+        start_time: legacy.start_time,
+        expire_time: legacy.expire_time,
+        prize_pool,
+        phase_start_time: legacy.phase_start_time,
+        phase_end_time: legacy.phase_end_time,
+        phase_advance_deadline: legacy.phase_advance_deadline,
+        phases: legacy.phases,
+        phase3_ready_deadline: legacy.phase3_ready_deadline,
+        phase3_extended_deadline: legacy.phase3_extended_deadline,
+        platform_fee_collected,
+        finished_at: 0,
+        creator: legacy.creator,
+        phase3_winner: legacy.phase3_winner.unwrap_or_default(),
+        mint: Pubkey::default(),
+        name: [0; MAX_GAME_NAME_LEN],
+        max_players: legacy.max_players as u16,
+        current_players: legacy.current_players as u16,
+        refunded_count: legacy.refunded_players.len() as u16,
+        phase3_players_ready: legacy.phase3_players_ready as u16,
+        status: legacy.status as u8,
+        game_started: legacy.game_started as u8,
+        creator_joined: legacy.players.contains(&legacy.creator) as u8,
+        current_phase: legacy.current_phase,
+        phase2_required_games: legacy.phase2_required_games,
+        phase2_max_games_per_opponent: legacy.phase2_max_games_per_opponent,
+        phase3_started: legacy.phase3_started as u8,
+        phase3_prize_claimed: legacy.phase3_prize_claimed as u8,
+        bump: ctx.bumps.game,
+        vault_bump: ctx.bumps.vault,
+        version: Game::VERSION,
+        max_bet_percentage: MAX_BET_PERCENTAGE,
+        allowed_mini_games: MiniGameType::ALL,
+        paused: 0,
+        creator_fee_bps: 0,
+        config,
+        paused_at: 0,
+        global_pause_applied: ctx.accounts.game_registry.total_paused,
+        creator_fee_collected: 0,
+        leave_lock_window: LEAVE_LOCK_WINDOW,
+        waitlist_escrow: 0,
+        access_root: [0; 32],
+        access_mode: AccessMode::Public as u8,
+        creator_bonded: 0,
+        _access_padding: [0; 6],
+        sponsored_amount: 0,
+        sponsor_fee_exempt: 0,
+        squad_size: 0,
+        _squad_padding: 0,
+        squad_count: 0,
+        squad_players: 0,
+        short_squads: 0,
+    };
+    game.set_name(&legacy.name[..name_len]);
+    // Players refunded before the upgrade may have left only the creator's
+    // forfeited stake behind
+    game.forfeit_creator_stake();
+    
+    // Stakes sat on the game account itself - move everything above its new
+    // rent into the vault PDA, which returns any surplus to the creator on close
+    let excess = game_info.lamports().saturating_sub(Rent::get()?.minimum_balance(Game::SIZE));
+    let vault = ctx.accounts.vault.to_account_info();
+    **game_info.try_borrow_mut_lamports()? -= excess;
+    **vault.try_borrow_mut_lamports()? += excess;
+    require!(
+        vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0)) >= game.prize_pool + game.platform_fee_collected,
+        GameError::VaultBalanceMismatch
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    if game.has_final_status() {
+        game.finished_at = now;
+    }
+    if legacy.players.is_empty() {
+        return Ok(());
+    }
+    game.version = 0;
+    game.paused = 1;
+    game.paused_at = now;
+    drop(data);
+    
+    let players = legacy.players.iter()
+        .map(|player| RosterPlayer {
+            player: *player,
+            refunded: legacy.refunded_players.contains(player),
+        })
+        .collect();
+    create_roster(&ctx, legacy.game_id, players)
+}
+
+/// Create the roster PDA holding a migrated game's players
+fn create_roster(ctx: &Context<MigrateGame>, game_id: u64, players: Vec<RosterPlayer>) -> Result<()> {
+    let roster_info = ctx.accounts.roster.to_account_info();
+    let size = LegacyRoster::size(players.len());
+    let game_id_bytes = game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[b"legacy_roster", game_id_bytes.as_ref(), &[ctx.bumps.roster]];
+    
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.admin.to_account_info(),
+                to: roster_info.clone(),
+            },
+            &[seeds],
+        ),
+        Rent::get()?.minimum_balance(size),
+        size as u64,
+        &crate::ID,
+    )?;
+    let roster = LegacyRoster {
+        version: LegacyRoster::VERSION,
+        game_id,
+        players,
+        bump: ctx.bumps.roster,
+    };
+    let mut data = roster_info.try_borrow_mut_data()?;
+    roster.try_serialize(&mut &mut data[..])
+}
+
+/// Admin creates the `PlayerEntry` of the next players on a migrated game's
+/// roster, in batches. `remaining_accounts` are their entry PDAs, starting
+/// from the back of the roster. Deployed games staked exactly the entry fee;
+/// the join time wasn't recorded and is left at 0. Once the roster is empty
+/// it is closed to the admin and the game resumes with its deadlines pushed
+/// back by the time it spent migrating.
+pub fn migrate_player_entries<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigratePlayerEntries<'info>>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.game_registry.admin,
+        GameError::NotAdmin
+    );
+    
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let roster = &mut ctx.accounts.roster;
+    let game_id = game.game_id.to_le_bytes();
+    let rent = Rent::get()?.minimum_balance(PlayerEntry::SIZE);
+    
+    for entry_info in ctx.remaining_accounts {
+        let listed = roster.players.pop().ok_or(GameError::InvalidRosterEntry)?;
+        let (entry_key, entry_bump) = Pubkey::find_program_address(
+            &[b"player_entry", game_id.as_ref(), listed.player.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(entry_info.key(), entry_key, GameError::InvalidRosterEntry);
+        
+        let seeds: &[&[u8]] = &[b"player_entry", game_id.as_ref(), listed.player.as_ref(), &[entry_bump]];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: entry_info.clone(),
+                },
+                &[seeds],
+            ),
+            rent,
+            PlayerEntry::SIZE as u64,
+            &crate::ID,
+        )?;
+        let entry = PlayerEntry {
+            version: PlayerEntry::VERSION,
+            game_id: game.game_id,
+            player: listed.player,
+            entered_at: 0,
+            amount: game.entry_fee,
+            refunded: listed.refunded,
+            bump: entry_bump,
+        };
+        entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+    }
+    
+    if roster.players.is_empty() {
+        let now = Clock::get()?.unix_timestamp;
+        let migrating_for = now - game.paused_at;
+        game.extend_deadlines(migrating_for);
+        game.paused = 0;
+        game.paused_at = 0;
+        game.version = Game::VERSION;
+        roster.close(ctx.accounts.admin.to_account_info())?;
+    }
+    
+    Ok(())
+}
+
+/// Admin upgrades the registry to the current layout
+/// Must run first - every other instruction reads the registry
+pub fn migrate_registry(ctx: Context<MigrateRegistry>) -> Result<()> {
+    let info = ctx.accounts.game_registry.to_account_info();
+    let upgraded: GameRegistry = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *GameRegistry::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        require!(data.len() == LEGACY_REGISTRY_SIZE, GameError::AlreadyMigrated);
+        
+        LegacyGameRegistry::deserialize(&mut &data[8..])?.into()
+    };
+    require!(ctx.accounts.admin.key() == upgraded.admin, GameError::NotAdmin);
+    
+    resize_account(&info, &ctx.accounts.admin, &ctx.accounts.system_program, GameRegistry::SIZE)?;
+    
+    let mut data = info.try_borrow_mut_data()?;
+    upgraded.try_serialize(&mut &mut data[..])
+}
+
+pub fn migrate_player_state(ctx: Context<MigrateAccount>) -> Result<()> {
+    migrate_account::<LegacyPlayerGameState, _>(ctx.accounts, PlayerGameState::size(MAX_TRACKED_OPPONENTS), PlayerGameState::from)
+}
+
+/// The deployed pool didn't record who paid its rent; the migrating admin
+/// tops it up and gets it back on close
+pub fn migrate_pool_state(ctx: Context<MigrateAccount>) -> Result<()> {
+    let payer = ctx.accounts.admin.key();
+    migrate_account(ctx.accounts, GamePoolState::SIZE, |legacy: LegacyGamePoolState| legacy.upgrade(payer))
+}
+
+pub fn migrate_challenge(ctx: Context<MigrateAccount>) -> Result<()> {
+    migrate_account::<LegacyChallenge, _>(ctx.accounts, Challenge::SIZE, Challenge::from)
+}
+
+pub fn migrate_phase3_ready_state(ctx: Context<MigrateAccount>) -> Result<()> {
+    migrate_account::<LegacyPhase3ReadyState, _>(ctx.accounts, Phase3ReadyState::SIZE, Phase3ReadyState::from)
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct MigrateGame<'info> {
+    /// CHECK: legacy layout can't be loaded as `Game`; checked in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(seeds = [b"global_config"], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    /// Vault PDA that holds the stakes once the game is migrated
+    #[account(mut, seeds = [b"vault", game_id.to_le_bytes().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// CHECK: created by the handler if the game has players
+    #[account(mut, seeds = [b"legacy_roster", game_id.to_le_bytes().as_ref()], bump)]
+    pub roster: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePlayerEntries<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(
+        mut,
+        seeds = [b"legacy_roster", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = roster.bump
+    )]
+    pub roster: Account<'info, LegacyRoster>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRegistry<'info> {
+    /// CHECK: legacy layout can't be loaded as `GameRegistry`; checked in the handler
+    #[account(mut, owner = crate::ID, seeds = [b"game_registry"], bump)]
+    pub game_registry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for migrating a Borsh account (player state, pool state,
+/// challenge or ready state)
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: program-owned account in a legacy layout; the handler checks its discriminator
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -2,6 +2,24 @@
 
 pub mod game_control;
 pub mod fee_management;
+pub mod audit;
+pub mod migrate;
+pub mod config;
+pub mod transfer;
+pub mod council;
+pub mod timelock;
+pub mod pause;
+pub mod roles;
+pub mod treasury;
 
 pub use game_control::*;
 pub use fee_management::*;
+pub use audit::*;
+pub use migrate::*;
+pub use config::*;
+pub use transfer::*;
+pub use council::*;
+pub use timelock::*;
+pub use pause::*;
+pub use roles::*;
+pub use treasury::*;
@@ -0,0 +1,93 @@
+// Emergency pause - the only admin control that skips the timelock
+// Halts play without moving funds; refunds and account cleanup stay open
+
+use anchor_lang::prelude::*;
+use crate::state::{require_role, Game, GameRegistry, Role, RoleRegistry};
+use crate::events::{GamePauseUpdated, ProgramPauseUpdated};
+use crate::errors::GameError;
+
+/// Admin pauses or resumes every game at once
+/// Games push their deadlines back by the paused time the next time they're played
+pub fn set_program_paused(ctx: Context<SetProgramPaused>, paused: bool) -> Result<()> {
+    let game_registry = &mut ctx.accounts.game_registry;
+    
+    require_role(
+        game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.admin.key(),
+        Role::Admin,
+    )?;
+    require!(game_registry.paused != paused, GameError::PauseUnchanged);
+    
+    let paused_for = game_registry.set_paused(paused, Clock::get()?.unix_timestamp);
+    
+    emit!(ProgramPauseUpdated {
+        paused,
+        admin: ctx.accounts.admin.key(),
+        paused_for,
+    });
+    
+    Ok(())
+}
+
+/// Admin pauses or resumes a single game
+/// Resuming pushes the game's deadlines back by the paused time
+pub fn set_game_paused(ctx: Context<SetGamePaused>, paused: bool) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let clock = Clock::get()?;
+    
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.admin.key(),
+        Role::Admin,
+    )?;
+    require!((game.paused != 0) != paused, GameError::PauseUnchanged);
+    // Migrated games resume on their own once every player has an entry
+    require!(game.version == Game::VERSION, GameError::MigrationPending);
+    
+    let mut paused_for = 0;
+    if paused {
+        game.paused_at = clock.unix_timestamp;
+    } else {
+        paused_for = clock.unix_timestamp - game.paused_at;
+        game.extend_deadlines(paused_for);
+        game.paused_at = 0;
+    }
+    game.paused = paused as u8;
+    
+    emit!(GamePauseUpdated {
+        game_id: game.game_id,
+        paused,
+        paused_for,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetProgramPaused<'info> {
+    #[account(mut, seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGamePaused<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    pub admin: Signer<'info>,
+}
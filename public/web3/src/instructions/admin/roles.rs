@@ -0,0 +1,52 @@
+// Role management - grant and revoke operator, referee, treasurer and admin roles
+
+use anchor_lang::prelude::*;
+use crate::state::{require_role, GameRegistry, Role, RoleRegistry};
+use crate::events::RolesUpdated;
+use crate::errors::GameError;
+
+/// Role admin sets the roles held by `member` (0 revokes all of them)
+pub fn set_roles(ctx: Context<SetRoles>, member: Pubkey, roles: u8) -> Result<()> {
+    require_role(
+        &ctx.accounts.game_registry,
+        Some(&ctx.accounts.role_registry),
+        &ctx.accounts.authority.key(),
+        Role::Admin,
+    )?;
+    require!(roles & !Role::ALL == 0, GameError::InvalidRoles);
+    
+    let role_registry = &mut ctx.accounts.role_registry;
+    if role_registry.version == 0 {
+        role_registry.version = RoleRegistry::VERSION;
+        role_registry.bump = ctx.bumps.role_registry;
+    }
+    role_registry.set_roles(member, roles)?;
+    
+    emit!(RolesUpdated {
+        member,
+        roles,
+        updated_by: ctx.accounts.authority.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRoles<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RoleRegistry::SIZE,
+        seeds = [b"roles"],
+        bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
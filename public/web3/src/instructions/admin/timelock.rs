@@ -0,0 +1,113 @@
+// Admin timelock - game interventions are queued publicly and can only run
+// after a notice period, so players see them coming
+
+use anchor_lang::prelude::*;
+use crate::state::{require_role, AdminAction, AdminCouncil, Game, GameRegistry, QueuedAdminAction, Role, RoleRegistry};
+use crate::events::{AdminActionCancelled, AdminActionQueued};
+
+/// Operator announces an intervention on a game
+/// The matching `admin_*` instruction consumes it after the game's `admin_action_delay`
+pub fn queue_admin_action(ctx: Context<QueueAdminAction>, game_id: u64, action: AdminAction) -> Result<()> {
+    let queued_action = &mut ctx.accounts.queued_action;
+    let clock = Clock::get()?;
+    
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.admin.key(),
+        Role::Operator,
+    )?;
+    AdminCouncil::require_absent(&ctx.accounts.admin_council)?;
+    
+    queued_action.version = QueuedAdminAction::VERSION;
+    queued_action.game_id = game_id;
+    queued_action.action = action;
+    queued_action.queued_by = ctx.accounts.admin.key();
+    queued_action.queued_at = clock.unix_timestamp;
+    queued_action.executable_at = clock.unix_timestamp + ctx.accounts.game.load()?.config.admin_action_delay;
+    queued_action.bump = ctx.bumps.queued_action;
+    
+    emit!(AdminActionQueued {
+        game_id,
+        action,
+        queued_by: queued_action.queued_by,
+        executable_at: queued_action.executable_at,
+    });
+    
+    Ok(())
+}
+
+/// Operator withdraws a queued intervention
+pub fn cancel_admin_action(ctx: Context<CancelAdminAction>) -> Result<()> {
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.admin.key(),
+        Role::Operator,
+    )?;
+    
+    emit!(AdminActionCancelled {
+        game_id: ctx.accounts.queued_action.game_id,
+        action: ctx.accounts.queued_action.action,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, action: AdminAction)]
+pub struct QueueAdminAction<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = QueuedAdminAction::SIZE,
+        seeds = [b"admin_action", game_id.to_le_bytes().as_ref(), &[action as u8]],
+        bump
+    )]
+    pub queued_action: Account<'info, QueuedAdminAction>,
+    
+    /// Target game - must exist
+    #[account(seeds = [b"game", game_id.to_le_bytes().as_ref()], bump)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    /// CHECK: admin council PDA - must not exist for single-key controls
+    #[account(seeds = [b"admin_council"], bump)]
+    pub admin_council: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminAction<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            b"admin_action",
+            queued_action.game_id.to_le_bytes().as_ref(),
+            &[queued_action.action as u8]
+        ],
+        bump = queued_action.bump
+    )]
+    pub queued_action: Account<'info, QueuedAdminAction>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,81 @@
+// Admin transfer - two-step handover of the registry admin key
+
+use anchor_lang::prelude::*;
+use crate::state::GameRegistry;
+use crate::events::{AdminProposed, AdminTransferred};
+use crate::errors::GameError;
+
+/// Admin nominates a new admin, optionally only until `expires_at`
+/// A new proposal replaces the pending one; proposing the current admin
+/// cancels the handover
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey, expires_at: Option<i64>) -> Result<()> {
+    let game_registry = &mut ctx.accounts.game_registry;
+    let clock = Clock::get()?;
+    
+    require!(
+        ctx.accounts.admin.key() == game_registry.admin,
+        GameError::NotAdmin
+    );
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > clock.unix_timestamp, GameError::AdminProposalExpired);
+    }
+    
+    if new_admin == game_registry.admin {
+        game_registry.pending_admin = None;
+        game_registry.pending_admin_expires_at = None;
+    } else {
+        game_registry.pending_admin = Some(new_admin);
+        game_registry.pending_admin_expires_at = expires_at;
+    }
+    
+    emit!(AdminProposed {
+        admin: game_registry.admin,
+        pending_admin: game_registry.pending_admin,
+        expires_at: game_registry.pending_admin_expires_at,
+    });
+    
+    Ok(())
+}
+
+/// The proposed admin accepts and takes over the registry
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let game_registry = &mut ctx.accounts.game_registry;
+    let clock = Clock::get()?;
+    
+    let pending_admin = game_registry.pending_admin.ok_or(GameError::NoPendingAdmin)?;
+    require!(
+        ctx.accounts.new_admin.key() == pending_admin,
+        GameError::NotPendingAdmin
+    );
+    if let Some(expires_at) = game_registry.pending_admin_expires_at {
+        require!(clock.unix_timestamp < expires_at, GameError::AdminProposalExpired);
+    }
+    
+    let previous_admin = game_registry.admin;
+    game_registry.admin = pending_admin;
+    game_registry.pending_admin = None;
+    game_registry.pending_admin_expires_at = None;
+    
+    emit!(AdminTransferred {
+        previous_admin,
+        new_admin: pending_admin,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut, seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut, seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    pub new_admin: Signer<'info>,
+}
@@ -0,0 +1,104 @@
+// Treasury withdrawals - treasurers move collected platform fees out
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{require_role, GameRegistry, Role, RoleRegistry, Treasury};
+use crate::events::TreasuryWithdrawal;
+use crate::errors::GameError;
+
+/// Treasurer withdraws `amount` of SOL, or of `mint` when the token accounts are passed
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.treasurer.key(),
+        Role::Treasurer,
+    )?;
+    require!(amount > 0, GameError::InsufficientTreasuryBalance);
+    
+    let treasury = &mut ctx.accounts.treasury;
+    let recipient = match &ctx.accounts.mint {
+        None => {
+            // Keep the treasury rent exempt
+            let info = treasury.to_account_info();
+            let rent = Rent::get()?.minimum_balance(info.data_len());
+            require!(
+                info.lamports().saturating_sub(rent) >= amount,
+                GameError::InsufficientTreasuryBalance
+            );
+            
+            **info.try_borrow_mut_lamports()? -= amount;
+            **ctx.accounts.recipient.try_borrow_mut_lamports()? += amount;
+            treasury.total_sol_withdrawn += amount;
+            ctx.accounts.recipient.key()
+        }
+        Some(mint) => {
+            let source = ctx.accounts.treasury_token_account.as_ref().ok_or(GameError::MissingTokenAccounts)?;
+            let destination = ctx.accounts.recipient_token_account.as_ref().ok_or(GameError::MissingTokenAccounts)?;
+            let token_program = ctx.accounts.token_program.as_ref().ok_or(GameError::MissingTokenAccounts)?;
+            require!(
+                source.mint == mint.key() && source.owner == treasury.key() && destination.mint == mint.key(),
+                GameError::InvalidTokenAccount
+            );
+            require!(source.amount >= amount, GameError::InsufficientTreasuryBalance);
+            
+            let seeds: &[&[u8]] = &[b"treasury", &[treasury.bump]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: source.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: destination.to_account_info(),
+                        authority: treasury.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+                mint.decimals,
+            )?;
+            destination.key()
+        }
+    };
+    
+    emit!(TreasuryWithdrawal {
+        treasurer: ctx.accounts.treasurer.key(),
+        recipient,
+        mint: ctx.accounts.mint.as_ref().map(|mint| mint.key()),
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    pub treasurer: Signer<'info>,
+    
+    /// CHECK: any wallet; receives SOL withdrawals
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// Mint being withdrawn (token withdrawals only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Treasury's token account for the mint (token withdrawals only)
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Destination token account for the mint (token withdrawals only)
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
@@ -0,0 +1,369 @@
+// Account cleanup - closing per-player accounts and reclaiming rent after a game ends
+
+use anchor_lang::prelude::*;
+use crate::state::{
+    Game, GameRegistry, GameStatus, PlayerEntry, PlayerGameState, GamePoolState, Challenge, Phase3ReadyState, Squad,
+    SquadMember, SponsorRecord, Waitlist, WaitlistEntry,
+};
+use crate::events::GameAccountsSwept;
+use crate::errors::GameError;
+
+/// Load a game PDA that may already have been closed (None once closed)
+/// The caller checks the PDA seeds, so only this program can own it
+fn load_game(game_info: &AccountInfo) -> Result<Option<Game>> {
+    if game_info.data_is_empty() {
+        return Ok(None);
+    }
+    
+    let data = game_info.try_borrow_data()?;
+    require!(data.len() == Game::SIZE, ErrorCode::AccountDidNotDeserialize);
+    Ok(Some(Game::try_deserialize(&mut &data[..])?))
+}
+
+/// Whether accounts belonging to a game can be closed
+/// A game account that no longer exists was closed after it settled
+fn game_accounts_closable(game_info: &AccountInfo) -> Result<bool> {
+    Ok(load_game(game_info)?.is_none_or(|game| game.is_finished()))
+}
+
+/// Whether a player entry can be closed without stranding its stake
+/// Entries of a finished game stay open until refunded, unless the stake
+/// went to the winner or only sponsor funds are left in the pool
+fn entry_closable(game: Option<&Game>, entry: &PlayerEntry) -> bool {
+    match game {
+        None => true,
+        Some(game) => game.is_finished()
+            && (entry.refunded || game.has_status(GameStatus::Completed) || game.prize_pool <= game.sponsored_amount),
+    }
+}
+
+/// Whether a squad can be closed without losing a prize its members haven't claimed
+fn squad_closable(game: Option<&Game>, squad: &Account<Squad>) -> bool {
+    game.is_none_or(|game| {
+        game.is_finished() && (game.winner() != Some(squad.key()) || squad.all_claimed())
+    })
+}
+
+/// Whether a sponsor record can be closed without stranding its funds
+/// Refunds close the record themselves, so one left in a refunded game still
+/// holds funds; in a completed game they went to the winner
+fn sponsor_record_closable(game: Option<&Game>) -> bool {
+    game.is_none_or(|game| game.is_finished() && game.has_status(GameStatus::Completed))
+}
+
+/// Player closes their entry once the game is over and their stake is settled
+pub fn close_player_entry(ctx: Context<ClosePlayerEntry>) -> Result<()> {
+    let game = load_game(&ctx.accounts.game)?;
+    require!(
+        entry_closable(game.as_ref(), &ctx.accounts.player_entry),
+        GameError::GameNotFinished
+    );
+    
+    Ok(())
+}
+
+/// Player closes their own player state once the game is over
+pub fn close_player_state(ctx: Context<ClosePlayerState>) -> Result<()> {
+    require!(
+        game_accounts_closable(&ctx.accounts.game)?,
+        GameError::GameNotFinished
+    );
+    
+    Ok(())
+}
+
+/// Whoever initialized the pool state closes it once the game is over
+pub fn close_pool_state(ctx: Context<ClosePoolState>) -> Result<()> {
+    require!(
+        game_accounts_closable(&ctx.accounts.game)?,
+        GameError::GameNotFinished
+    );
+    
+    Ok(())
+}
+
+/// Challenger closes a challenge once the game is over
+pub fn close_challenge(ctx: Context<CloseChallenge>) -> Result<()> {
+    require!(
+        game_accounts_closable(&ctx.accounts.game)?,
+        GameError::GameNotFinished
+    );
+    
+    Ok(())
+}
+
+/// Player closes their Phase 3 ready state once the game is over
+pub fn close_phase3_ready_state(ctx: Context<ClosePhase3ReadyState>) -> Result<()> {
+    require!(
+        game_accounts_closable(&ctx.accounts.game)?,
+        GameError::GameNotFinished
+    );
+    
+    Ok(())
+}
+
+/// Leader closes their squad once the game is over and any prize the
+/// squad won has been claimed
+pub fn close_squad(ctx: Context<CloseSquad>) -> Result<()> {
+    let game = load_game(&ctx.accounts.game)?;
+    require!(
+        squad_closable(game.as_ref(), &ctx.accounts.squad),
+        GameError::GameNotFinished
+    );
+    
+    Ok(())
+}
+
+/// Player closes their squad membership once the game is over
+pub fn close_squad_member(ctx: Context<CloseSquadMember>) -> Result<()> {
+    require!(
+        game_accounts_closable(&ctx.accounts.game)?,
+        GameError::GameNotFinished
+    );
+    
+    Ok(())
+}
+
+/// Creator or admin closes abandoned accounts of a finished game in bulk
+/// remaining_accounts holds (account, rent recipient) pairs; rent always goes
+/// back to whoever paid it. Pairs that don't belong to the game are skipped.
+/// Waitlist entries still hold a deposit until `refund_waitlist` closes them,
+/// so only those of an already closed (fully settled) game are swept
+pub fn sweep_game_accounts<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SweepGameAccounts<'info>>,
+    game_id: u64,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let is_admin = authority == ctx.accounts.game_registry.admin;
+    let clock = Clock::get()?;
+    
+    // Closed games already settled; only the admin can sweep them
+    let game = load_game(&ctx.accounts.game)?;
+    match &game {
+        None => require!(is_admin, GameError::NotAdmin),
+        Some(game) => {
+            require!(is_admin || authority == game.creator, GameError::Unauthorized);
+            require!(game.is_finished(), GameError::GameNotFinished);
+            require!(
+                clock.unix_timestamp >= game.finished_at + game.config.account_sweep_grace_period,
+                GameError::SweepGracePeriodActive
+            );
+        }
+    }
+    
+    let mut accounts_closed: u32 = 0;
+    for pair in ctx.remaining_accounts.chunks_exact(2) {
+        let (account_info, recipient) = (&pair[0], &pair[1]);
+        
+        let closed = if let Ok(entry) = Account::<PlayerEntry>::try_from(account_info) {
+            entry.game_id == game_id
+                && entry.player == recipient.key()
+                && entry_closable(game.as_ref(), &entry)
+                && entry.close(recipient.clone()).is_ok()
+        } else if let Ok(state) = Account::<PlayerGameState>::try_from(account_info) {
+            state.game_id == game_id && state.player == recipient.key() && state.close(recipient.clone()).is_ok()
+        } else if let Ok(pool) = Account::<GamePoolState>::try_from(account_info) {
+            pool.game_id == game_id && pool.payer == recipient.key() && pool.close(recipient.clone()).is_ok()
+        } else if let Ok(challenge) = Account::<Challenge>::try_from(account_info) {
+            challenge.game_id == game_id && challenge.challenger == recipient.key() && challenge.close(recipient.clone()).is_ok()
+        } else if let Ok(ready) = Account::<Phase3ReadyState>::try_from(account_info) {
+            ready.game_id == game_id && ready.player == recipient.key() && ready.close(recipient.clone()).is_ok()
+        } else if let Ok(waitlist) = Account::<Waitlist>::try_from(account_info) {
+            waitlist.game_id == game_id
+                && waitlist.payer == recipient.key()
+                && waitlist.waiting == 0
+                && waitlist.close(recipient.clone()).is_ok()
+        } else if let Ok(entry) = Account::<WaitlistEntry>::try_from(account_info) {
+            entry.game_id == game_id
+                && entry.player == recipient.key()
+                && game.is_none()
+                && entry.close(recipient.clone()).is_ok()
+        } else if let Ok(record) = Account::<SponsorRecord>::try_from(account_info) {
+            record.game_id == game_id
+                && record.sponsor == recipient.key()
+                && sponsor_record_closable(game.as_ref())
+                && record.close(recipient.clone()).is_ok()
+        } else if let Ok(squad) = Account::<Squad>::try_from(account_info) {
+            squad.game_id == game_id
+                && squad.leader == recipient.key()
+                && squad_closable(game.as_ref(), &squad)
+                && squad.close(recipient.clone()).is_ok()
+        } else if let Ok(member) = Account::<SquadMember>::try_from(account_info) {
+            member.game_id == game_id && member.player == recipient.key() && member.close(recipient.clone()).is_ok()
+        } else {
+            false
+        };
+        
+        if closed {
+            accounts_closed += 1;
+        }
+    }
+    
+    emit!(GameAccountsSwept {
+        game_id,
+        authority,
+        accounts_closed,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClosePlayerEntry<'info> {
+    #[account(
+        mut,
+        close = player,
+        has_one = player,
+        seeds = [
+            b"player_entry",
+            player_entry.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_entry.bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    /// CHECK: game PDA for the entry's game_id - may already be closed
+    #[account(seeds = [b"game", player_entry.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePlayerState<'info> {
+    #[account(
+        mut,
+        close = player,
+        has_one = player,
+        seeds = [
+            b"player_state",
+            player_state.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerGameState>,
+    
+    /// CHECK: game PDA for the state's game_id - may already be closed
+    #[account(seeds = [b"game", player_state.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePoolState<'info> {
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        seeds = [b"pool_state", pool_state.game_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, GamePoolState>,
+    
+    /// CHECK: game PDA for the pool's game_id - may already be closed
+    #[account(seeds = [b"game", pool_state.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseChallenge<'info> {
+    #[account(mut, close = challenger, has_one = challenger)]
+    pub challenge: Account<'info, Challenge>,
+    
+    /// CHECK: game PDA for the challenge's game_id - may already be closed
+    #[account(seeds = [b"game", challenge.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePhase3ReadyState<'info> {
+    #[account(
+        mut,
+        close = player,
+        has_one = player,
+        seeds = [
+            b"phase3_ready",
+            ready_state.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub ready_state: Account<'info, Phase3ReadyState>,
+    
+    /// CHECK: game PDA for the state's game_id - may already be closed
+    #[account(seeds = [b"game", ready_state.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct SweepGameAccounts<'info> {
+    /// CHECK: game PDA - deserialized in the handler when it still exists
+    #[account(seeds = [b"game", game_id.to_le_bytes().as_ref()], bump)]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSquad<'info> {
+    #[account(
+        mut,
+        close = leader,
+        has_one = leader,
+        seeds = [
+            b"squad",
+            squad.game_id.to_le_bytes().as_ref(),
+            leader.key().as_ref()
+        ],
+        bump = squad.bump
+    )]
+    pub squad: Account<'info, Squad>,
+    
+    /// CHECK: game PDA for the squad's game_id - may already be closed
+    #[account(seeds = [b"game", squad.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub leader: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSquadMember<'info> {
+    #[account(
+        mut,
+        close = player,
+        has_one = player,
+        seeds = [
+            b"squad_member",
+            squad_member.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = squad_member.bump
+    )]
+    pub squad_member: Account<'info, SquadMember>,
+    
+    /// CHECK: game PDA for the membership's game_id - may already be closed
+    #[account(seeds = [b"game", squad_member.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
@@ -0,0 +1,139 @@
+// Game closing - reclaim rent once every stake has been paid out
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+use crate::state::{CreatorBond, Game, GameStatus, Treasury};
+use crate::events::{CreatorBondSlashed, GameClosed};
+use crate::errors::GameError;
+use crate::utils::{game_vault, pay_from_game, payout_recipient, vault_balance};
+
+/// Close a settled game and its vaults, returning all rent to the creator
+/// Anyone can call this once prizes, refunds and fees have been paid out.
+/// A bond the players couldn't take after a penalty goes to the treasury.
+pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+    // Copy out - the game PDA signs token payouts, so it must not stay borrowed
+    let game = *ctx.accounts.game.load()?;
+    let creator = &ctx.accounts.creator;
+    
+    require!(game.is_settled(), GameError::GameNotSettled);
+    require!(
+        game.creator_bonded == 0 || ctx.accounts.creator_bond.is_some(),
+        GameError::CreatorBondRequired
+    );
+    
+    // The creator forfeited the bond - only its rent goes back to them
+    if let Some(bond) = &mut ctx.accounts.creator_bond {
+        if game.has_status(GameStatus::ExpiredWithPenalty) && bond.amount > 0 {
+            let treasury = ctx.accounts.treasury.as_mut().ok_or(GameError::TreasuryRequired)?;
+            let amount = bond.amount;
+            **bond.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **treasury.to_account_info().try_borrow_mut_lamports()? += amount;
+            treasury.total_sol_collected += amount;
+            bond.amount = 0;
+            
+            emit!(CreatorBondSlashed {
+                game_id: game.game_id,
+                player: treasury.key(),
+                amount,
+                remaining: 0,
+            });
+        }
+    }
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let game_id = game.game_id.to_le_bytes();
+    
+    // Token vault: hand untracked tokens to the creator, then close it
+    if let Some(accounts) = &vault.token {
+        let leftover = vault_balance(&vault)?;
+        if leftover > 0 {
+            let recipient = payout_recipient(&game, &vault, &creator.to_account_info(), &ctx.accounts.creator_token_account)?;
+            pay_from_game(&game, &vault, &recipient, leftover)?;
+        }
+        
+        let seeds: &[&[u8]] = &[b"game", game_id.as_ref(), &[game.bump]];
+        token_interface::close_account(CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: accounts.vault.to_account_info(),
+                destination: creator.to_account_info(),
+                authority: ctx.accounts.game.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+    }
+    
+    // SOL vault: return the rent reserve (and any untracked lamports)
+    let reclaimed = ctx.accounts.vault.lamports();
+    let seeds: &[&[u8]] = &[b"vault", game_id.as_ref(), &[game.vault_bump]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: creator.to_account_info(),
+            },
+            &[seeds],
+        ),
+        reclaimed,
+    )?;
+    
+    emit!(GameClosed {
+        game_id: game.game_id,
+        creator: creator.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    #[account(mut, close = creator)]
+    pub game: AccountLoader<'info, Game>,
+    
+    /// CHECK: rent destination, must be the game creator
+    #[account(mut, address = game.load()?.creator @ GameError::NotCreator)]
+    pub creator: UncheckedAccount<'info>,
+    
+    /// Creator bond left after an admin start, an omitted return or a penalty;
+    /// required while `creator_bonded` is set
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"creator_bond", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = creator_bond.bump
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
+    
+    /// Program treasury - only needed to collect a forfeited bond
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Option<Account<'info, Treasury>>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Creator's token account, only needed if the token vault holds untracked tokens
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
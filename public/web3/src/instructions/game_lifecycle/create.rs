@@ -1,45 +1,148 @@
 // Game creation logic
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, GameRegistry, GameStatus, PhaseDurations};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{CreatorBond, Game, GameAccess, GameRegistry, GameRules, GameStatus, GlobalConfig, PhaseDurations};
 use crate::events::GameCreated;
 use crate::errors::GameError;
-use crate::constants::{MIN_PLAYERS_TO_START, MAX_PLAYERS_ALLOWED, GAME_ACCOUNT_SIZE, GAME_START_GRACE_PERIOD};
+use crate::constants::MAX_GAME_NAME_LEN;
+use crate::utils::validate_stake_mint;
 
 pub fn create_game(
     ctx: Context<CreateGame>,
     name: String,
     entry_fee: u64,
-    max_players: u8,
+    max_players: u16,
     start_time: i64,
-    game_duration_hours: u8,
+    rules: GameRules,
+    access: GameAccess,
 ) -> Result<()> {
-    let game = &mut ctx.accounts.game;
-    let game_registry = &mut ctx.accounts.game_registry;
+    let game = &mut ctx.accounts.game.load_init()?;
+    if let Some(mint) = &ctx.accounts.mint {
+        validate_stake_mint(mint)?;
+        // A token game without its vault could never move stakes
+        require!(
+            ctx.accounts.token_vault.is_some() && ctx.accounts.token_program.is_some(),
+            GameError::MissingTokenAccounts
+        );
+    }
+    
+    let settings = GameSettings {
+        name: &name,
+        entry_fee,
+        max_players,
+        start_time,
+        rules: &rules,
+        access: &access,
+    };
+    let (required_games, max_per_opponent) = init_game(
+        game,
+        &mut ctx.accounts.game_registry,
+        &ctx.accounts.global_config,
+        ctx.accounts.creator.key(),
+        &settings,
+        ctx.accounts.mint.as_ref().map(|mint| mint.key()),
+    )?;
+    game.bump = ctx.bumps.game;
+    game.vault_bump = ctx.bumps.vault;
+    
+    // Creator funds the vault PDA's rent reserve; it is returned on close_game
+    fund_vault_reserve(&ctx.accounts.vault, &ctx.accounts.creator, &ctx.accounts.system_program)?;
+    
+    // Creator bond, held until the game starts or is cancelled
+    let bond = &mut ctx.accounts.creator_bond;
+    bond.version = CreatorBond::VERSION;
+    bond.game_id = game.game_id;
+    bond.creator = game.creator;
+    bond.amount = game.config.creator_bond;
+    bond.bump = ctx.bumps.creator_bond;
+    game.creator_bonded = 1;
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: bond.to_account_info(),
+            },
+        ),
+        bond.amount,
+    )?;
+    
+    emit!(GameCreated {
+        game_id: game.game_id,
+        creator: game.creator,
+        entry_fee: game.entry_fee,
+        max_players: game.max_players,
+        start_time: game.start_time,
+        mint: game.stake_mint(),
+        phase2_required_games: required_games,
+        phase2_max_games_per_opponent: max_per_opponent,
+        creator_fee_bps: game.creator_fee_bps,
+        creator_bond: bond.amount,
+        access_mode: access.mode,
+    });
+    
+    Ok(())
+}
+
+/// Creator-chosen settings for a new game
+pub(crate) struct GameSettings<'a> {
+    pub name: &'a str,
+    pub entry_fee: u64,
+    pub max_players: u16,
+    pub start_time: i64,
+    pub rules: &'a GameRules,
+    pub access: &'a GameAccess,
+}
+
+/// Validate `settings` and fill in a freshly initialized game, taking the
+/// next id from the registry. Bumps are left to the caller.
+/// Returns the Phase 2 (required games, max games per opponent).
+pub(crate) fn init_game(
+    game: &mut Game,
+    game_registry: &mut GameRegistry,
+    global_config: &GlobalConfig,
+    creator: Pubkey,
+    settings: &GameSettings,
+    mint: Option<Pubkey>,
+) -> Result<(u8, u8)> {
+    let GameSettings { name, entry_fee, max_players, start_time, rules, access } = *settings;
+    let mut config = global_config.params;
     let clock = Clock::get()?;
     
     // Validate inputs
     require!(
-        max_players >= MIN_PLAYERS_TO_START && max_players <= MAX_PLAYERS_ALLOWED,
+        (config.min_players_to_start..=config.max_players_allowed).contains(&max_players),
         GameError::InvalidMaxPlayers
     );
+    rules.validate(&config, &global_config.rule_bounds, max_players)?;
+    access.validate()?;
+    require!(name.len() <= MAX_GAME_NAME_LEN, GameError::NameTooLong);
     require!(entry_fee > 0, GameError::InvalidEntryFee);
     require!(start_time > clock.unix_timestamp, GameError::InvalidStartTime);
+    require!(!game_registry.paused, GameError::ProgramPaused);
+    
+    // Snapshot the tuning parameters so config updates don't affect this game;
+    // the creator's minimum player count replaces the global default
+    config.min_players_to_start = rules.min_players;
+    game.config = config;
     
     // Initialize game state
     game.game_id = game_registry.next_game_id();
-    game.name = name;
-    game.creator = ctx.accounts.creator.key();
+    game.set_name(name);
+    game.creator = creator;
     game.entry_fee = entry_fee;
     game.max_players = max_players;
     game.current_players = 0;
     game.start_time = start_time;
-    game.expire_time = start_time + GAME_START_GRACE_PERIOD;
-    game.status = GameStatus::WaitingForPlayers;
+    game.expire_time = start_time + config.game_start_grace_period;
+    game.status = GameStatus::WaitingForPlayers as u8;
     game.prize_pool = 0;
-    game.players = Vec::new();
-    game.game_started = false;
-    game.refunded_players = Vec::new();
+    game.game_started = 0;
+    game.refunded_count = 0;
+    game.creator_joined = 0;
     
     // Phase configuration
     game.current_phase = 0;
@@ -47,39 +150,79 @@ pub fn create_game(
     game.phase_end_time = 0;
     game.phase_advance_deadline = 0;
     
-    let total_duration = (game_duration_hours as u64) * 3600;
     game.phases = PhaseDurations {
-        phase1_duration: total_duration / 3,
-        phase2_duration: total_duration / 3,
-        phase3_duration: total_duration / 3,
+        phase1_duration: rules.phase1_duration,
+        phase2_duration: rules.phase2_duration,
+        phase3_duration: rules.phase3_duration,
     };
     
-    // Calculate Phase 2 requirements
-    let (required_games, max_per_opponent) = game.calculate_phase2_requirements();
+    // Calculate Phase 2 requirements unless the creator fixed them
+    let (mut required_games, max_per_opponent) = game.calculate_phase2_requirements();
+    if rules.phase2_required_games != 0 {
+        required_games = rules.phase2_required_games;
+    }
     game.phase2_required_games = required_games;
     game.phase2_max_games_per_opponent = max_per_opponent;
+    game.max_bet_percentage = rules.max_bet_percentage;
+    game.allowed_mini_games = rules.allowed_mini_games;
+    game.creator_fee_bps = rules.creator_fee_bps;
+    game.leave_lock_window = rules.leave_lock_window;
     
     // Phase 3 initialization
     game.phase3_ready_deadline = 0;
     game.phase3_extended_deadline = 0;
     game.phase3_players_ready = 0;
-    game.phase3_started = false;
-    game.phase3_winner = None;
-    game.phase3_prize_claimed = false;
+    game.phase3_started = 0;
+    game.phase3_winner = Pubkey::default();
+    game.phase3_prize_claimed = 0;
     
     game.platform_fee_collected = 0;
+    game.creator_fee_collected = 0;
+    game.waitlist_escrow = 0;
+    game.access_root = access.root;
+    game.access_mode = access.mode as u8;
+    game.sponsored_amount = 0;
+    game.sponsor_fee_exempt = 0;
+    game.squad_size = rules.squad_size;
+    game.squad_count = 0;
+    game.squad_players = 0;
+    game.short_squads = 0;
+    game.finished_at = 0;
     
-    emit!(GameCreated {
-        game_id: game.game_id,
-        creator: game.creator,
-        entry_fee: game.entry_fee,
-        max_players: game.max_players,
-        start_time: game.start_time,
-        phase2_required_games: required_games,
-        phase2_max_games_per_opponent: max_per_opponent,
-    });
+    // Pause state - earlier global pauses don't extend this game
+    game.paused = 0;
+    game.paused_at = 0;
+    game.global_pause_applied = game_registry.total_paused;
     
-    Ok(())
+    // Stake currency - the token vault ATA is created by the account constraints
+    game.mint = mint.unwrap_or_default();
+    game.version = Game::VERSION;
+    
+    Ok((required_games, max_per_opponent))
+}
+
+/// Top the vault PDA up to its rent reserve from `payer`
+/// Returns the lamports transferred
+pub(crate) fn fund_vault_reserve<'info>(
+    vault: &SystemAccount<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u64> {
+    let rent_reserve = Rent::get()?.minimum_balance(0);
+    let shortfall = rent_reserve.saturating_sub(vault.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: vault.to_account_info(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    Ok(shortfall)
 }
 
 #[derive(Accounts)]
@@ -87,17 +230,50 @@ pub struct CreateGame<'info> {
     #[account(
         init,
         payer = creator,
-        space = GAME_ACCOUNT_SIZE,
+        space = Game::SIZE,
         seeds = [b"game", game_registry.game_count.to_le_bytes().as_ref()],
         bump
     )]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
     
     #[account(mut, seeds = [b"game_registry"], bump)]
     pub game_registry: Account<'info, GameRegistry>,
     
+    #[account(seeds = [b"global_config"], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = CreatorBond::SIZE,
+        seeds = [b"creator_bond", game_registry.game_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+    
     #[account(mut)]
     pub creator: Signer<'info>,
     
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game_registry.game_count.to_le_bytes().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Optional SPL / Token-2022 mint the game is played in (omit for SOL games)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault holding token stakes, owned by the game PDA
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = game,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    
     pub system_program: Program<'info, System>,
 }
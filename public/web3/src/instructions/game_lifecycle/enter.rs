@@ -1,59 +1,74 @@
 // Player entry logic - joining a game
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::system_instruction;
-use crate::state::{Game, GameStatus};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Game, GameRegistry, GameStatus, PlayerEntry};
 use crate::events::PlayerJoined;
 use crate::errors::GameError;
+use crate::utils::{check_vault_balance, deposit_to_game, game_vault, user_token_account};
 
-pub fn enter_game(ctx: Context<EnterGame>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+pub fn enter_game(ctx: Context<EnterGame>, proof: Vec<[u8; 32]>, invite_code: String) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
     let player = &ctx.accounts.player;
     let clock = Clock::get()?;
     
     // Validate game state
+    game.sync_pause(&ctx.accounts.game_registry)?;
     require!(
-        game.status == GameStatus::WaitingForPlayers,
+        game.has_status(GameStatus::WaitingForPlayers),
         GameError::GameNotOpen
     );
-    require!(!game.game_started, GameError::GameAlreadyStarted);
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
     require!(
         game.current_players < game.max_players,
         GameError::GameFull
     );
-    require!(
-        !game.players.contains(&player.key()),
-        GameError::AlreadyJoined
-    );
+    // Freed seats belong to the head of the waitlist
+    require!(game.waitlist_escrow == 0, GameError::WaitlistQueued);
+    game.check_access(&player.key(), &proof, &invite_code)?;
+    // A second entry by the same player fails on the PlayerEntry init
     require!(
         clock.unix_timestamp < game.start_time,
         GameError::GameExpired
     );
     
-    // Transfer entry fee to game account
-    let transfer_ix = system_instruction::transfer(
-        &player.key(),
-        &game.key(),
-        game.entry_fee,
-    );
-    
-    anchor_lang::solana_program::program::invoke(
-        &transfer_ix,
-        &[
-            player.to_account_info(),
-            game.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    // Transfer entry fee to the game vault (lamports or tokens)
+    // Token-2022 transfer fees mean the vault may receive less than entry_fee
+    let vault = game_vault(
+        &ctx.accounts.game,
+        game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
     )?;
+    let source = match vault.token {
+        Some(_) => Some(user_token_account(game, &ctx.accounts.player_token_account, &player.key())?),
+        None => None,
+    };
+    let received = deposit_to_game(&vault, player, source, game.entry_fee)?;
+    
+    // Record the entry and add player to game
+    let entry = &mut ctx.accounts.player_entry;
+    entry.version = PlayerEntry::VERSION;
+    entry.game_id = game.game_id;
+    entry.player = player.key();
+    entry.entered_at = clock.unix_timestamp;
+    entry.amount = received;
+    entry.refunded = false;
+    entry.bump = ctx.bumps.player_entry;
     
-    // Add player to game
-    game.players.push(player.key());
     game.current_players += 1;
-    game.prize_pool += game.entry_fee;
+    game.prize_pool += received;
+    if player.key() == game.creator {
+        game.creator_joined = 1;
+    }
+    check_vault_balance(game, &vault)?;
     
     // Update status if game is full
     if game.current_players == game.max_players {
-        game.status = GameStatus::ReadyToStart;
+        game.status = GameStatus::ReadyToStart as u8;
     }
     
     emit!(PlayerJoined {
@@ -68,10 +83,43 @@ pub fn enter_game(ctx: Context<EnterGame>) -> Result<()> {
 #[derive(Accounts)]
 pub struct EnterGame<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = PlayerEntry::SIZE,
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
     
     #[account(mut)]
     pub player: Signer<'info>,
     
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Player's token account for the game mint (token games only)
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
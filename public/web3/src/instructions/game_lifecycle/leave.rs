@@ -0,0 +1,161 @@
+// Leaving a game - withdrawing an entry before the game starts
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Game, GameRegistry, GameStatus, PlayerEntry, Waitlist, WaitlistEntry};
+use crate::events::PlayerLeft;
+use crate::errors::GameError;
+use crate::utils::{check_vault_balance, game_vault, pay_from_game, payout_recipient};
+use super::waitlist::promote_waitlist_entry;
+
+/// Player withdraws from a game that hasn't started, up to the game's lock
+/// window before `start_time`. The stake is refunded and the entry closed.
+/// While players are queued the waitlist accounts are required, and the
+/// freed seat goes to the head of the queue.
+pub fn leave_game(ctx: Context<LeaveGame>) -> Result<()> {
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
+    let player = &ctx.accounts.player;
+    let clock = Clock::get()?;
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require!(
+        game.has_status(GameStatus::WaitingForPlayers) || game.has_status(GameStatus::ReadyToStart),
+        GameError::GameNotOpen
+    );
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
+    require!(
+        clock.unix_timestamp < game.start_time - game.leave_lock_window,
+        GameError::LeaveWindowClosed
+    );
+    // A squad can't be left with a member who is no longer in the game
+    if game.is_squad_game() {
+        let squad_member = ctx.accounts.squad_member.as_ref().ok_or(GameError::SquadGame)?;
+        require!(squad_member.data_is_empty(), GameError::LeaveSquadFirst);
+    }
+    
+    // Refund the stake the vault received for this entry
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(&game, &vault, &player.to_account_info(), &ctx.accounts.player_token_account)?;
+    let refund_amount = ctx.accounts.player_entry.amount;
+    pay_from_game(&game, &vault, &recipient, refund_amount)?;
+    
+    game.current_players -= 1;
+    game.prize_pool -= refund_amount;
+    if player.key() == game.creator {
+        game.creator_joined = 0;
+    }
+    
+    // A full game has a free seat again
+    game.status = GameStatus::WaitingForPlayers as u8;
+    if game.waitlist_escrow > 0 {
+        let waitlist = ctx.accounts.waitlist.as_mut().ok_or(GameError::WaitlistRequired)?;
+        // Deposits of skipped entries may be all that is left in escrow
+        if waitlist.head < waitlist.tail {
+            let (Some(waitlist_entry), Some(promoted_entry)) = (
+                &ctx.accounts.waitlist_entry,
+                &ctx.accounts.promoted_entry,
+            ) else {
+                return err!(GameError::WaitlistRequired);
+            };
+            promote_waitlist_entry(
+                &mut game,
+                waitlist,
+                waitlist_entry,
+                promoted_entry,
+                player,
+                &ctx.accounts.system_program,
+            )?;
+        }
+    }
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    emit!(PlayerLeft {
+        game_id: game.game_id,
+        player: player.key(),
+        amount: refund_amount,
+        current_players: game.current_players,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LeaveGame<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Entry is closed - the player gets its rent back
+    #[account(
+        mut,
+        close = player,
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_entry.bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Player's token account for the game mint (token games only)
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Game waitlist (required while players are queued)
+    #[account(
+        mut,
+        seeds = [b"waitlist", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+    
+    /// Head of the waitlist (required while someone is left to promote)
+    #[account(mut)]
+    pub waitlist_entry: Option<Account<'info, WaitlistEntry>>,
+    
+    /// CHECK: the promoted player's entry, created and checked in the instruction
+    #[account(mut)]
+    pub promoted_entry: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: the player's squad membership (squad games only), must not exist
+    #[account(
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub squad_member: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -1,11 +1,25 @@
-// Game lifecycle instructions - creating, entering, starting, and cancelling games
+// Game lifecycle instructions - creating (directly or from templates), entering, leaving, starting, cancelling and closing games
 
 pub mod create;
 pub mod enter;
+pub mod leave;
+pub mod waitlist;
+pub mod template;
+pub mod sponsor;
+pub mod squad;
 pub mod start;
 pub mod refund;
+pub mod close;
+pub mod cleanup;
 
 pub use create::*;
 pub use enter::*;
+pub use leave::*;
+pub use waitlist::*;
+pub use template::*;
+pub use sponsor::*;
+pub use squad::*;
 pub use start::*;
 pub use refund::*;
+pub use close::*;
+pub use cleanup::*;
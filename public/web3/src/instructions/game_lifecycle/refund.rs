@@ -1,82 +1,149 @@
 // Refund logic - cancellation and forced refunds
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, GameStatus};
-use crate::events::{GameCancelled, RefundClaimed, ForcedRefundClaimed, GameExpiredWithPenalty};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{CreatorBond, Game, GameRegistry, GameStatus, PlayerEntry};
+use crate::events::{
+    CreatorBondReturned, CreatorBondSlashed, GameCancelled, RefundClaimed, ForcedRefundClaimed, GameExpiredWithPenalty,
+    RefundsCranked,
+};
 use crate::errors::GameError;
-use crate::constants::{GAME_START_GRACE_PERIOD, MIN_PLAYERS_TO_START};
+use crate::utils::{check_vault_balance, game_vault, pay_from_game, payout_recipient};
 
 /// Creator cancels the game before it starts (within 30-minute window)
 pub fn creator_cancel_game(ctx: Context<CreatorCancelGame>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &mut ctx.accounts.game.load_mut()?;
     let clock = Clock::get()?;
     
     require!(
         game.creator == ctx.accounts.creator.key(),
         GameError::NotCreator
     );
-    require!(!game.game_started, GameError::GameAlreadyStarted);
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
     
     // Creator can only cancel within the 30-minute grace period
-    let cancel_deadline = game.start_time + GAME_START_GRACE_PERIOD;
+    let cancel_deadline = game.start_time + game.config.game_start_grace_period;
     require!(
         clock.unix_timestamp <= cancel_deadline,
         GameError::CancelWindowExpired
     );
     
-    game.status = GameStatus::Cancelled;
+    game.finish(GameStatus::Cancelled, clock.unix_timestamp);
     
     emit!(GameCancelled {
         game_id: game.game_id,
         cancelled_at: clock.unix_timestamp,
     });
     
+    // Cancelled in time - the bond goes back to the creator (closed by the constraint)
+    if let Some(bond) = &ctx.accounts.creator_bond {
+        game.creator_bonded = 0;
+        emit!(CreatorBondReturned {
+            game_id: game.game_id,
+            creator: game.creator,
+            amount: bond.amount,
+        });
+    }
+    
+    Ok(())
+}
+
+/// Refunds of a game that expired with penalty must slash the bond, so the
+/// caller can't skip the players' share by leaving the bond account out
+pub(crate) fn require_slashed_bond(game: &Game, bond: &Option<Account<CreatorBond>>) -> Result<()> {
+    require!(
+        bond.is_some() || game.creator_bonded == 0 || !game.has_status(GameStatus::ExpiredWithPenalty),
+        GameError::CreatorBondRequired
+    );
+    
+    Ok(())
+}
+
+/// Pay `player` their share of a slashed creator bond: what is left of it,
+/// split evenly between the players still owed a refund. Call before counting
+/// the player's own refund; the last refund takes the rounding dust.
+pub(crate) fn pay_bond_share(game: &Game, bond: &mut Account<CreatorBond>, player: &AccountInfo) -> Result<()> {
+    // The creator can't claim a refund once the game expired with penalty
+    let owed_players = game.current_players - game.creator_joined as u16 - game.refunded_count;
+    if bond.amount == 0 || owed_players == 0 {
+        return Ok(());
+    }
+    
+    let share = bond.amount / owed_players as u64;
+    **bond.to_account_info().try_borrow_mut_lamports()? -= share;
+    **player.try_borrow_mut_lamports()? += share;
+    bond.amount -= share;
+    
+    emit!(CreatorBondSlashed {
+        game_id: game.game_id,
+        player: player.key(),
+        amount: share,
+        remaining: bond.amount,
+    });
+    
     Ok(())
 }
 
 /// Claim refund from a cancelled or expired game
 pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
     let player = &ctx.accounts.player;
     let clock = Clock::get()?;
     
     // Check game status allows refunds
     require!(
-        game.status == GameStatus::Cancelled
-            || game.status == GameStatus::Expired
-            || game.status == GameStatus::ExpiredWithPenalty,
+        game.has_status(GameStatus::Cancelled)
+            || game.has_status(GameStatus::ExpiredWithPenalty),
         GameError::GameNotCancelled
     );
     
     require!(
-        game.players.contains(&player.key()),
-        GameError::NotInGame
-    );
-    require!(
-        !game.refunded_players.contains(&player.key()),
+        !ctx.accounts.player_entry.refunded,
         GameError::AlreadyRefunded
     );
+    require_slashed_bond(&game, &ctx.accounts.creator_bond)?;
     
     // Special rule: creator forfeits funds if they missed their obligations
     if player.key() == game.creator {
-        let deadline = game.start_time + GAME_START_GRACE_PERIOD;
+        let deadline = game.start_time + game.config.game_start_grace_period;
         
         if clock.unix_timestamp > deadline
-            && game.status == GameStatus::ExpiredWithPenalty
+            && game.has_status(GameStatus::ExpiredWithPenalty)
         {
             return Err(GameError::CreatorForfeitedFunds.into());
         }
     }
     
     // Process refund
-    **game.to_account_info().try_borrow_mut_lamports()? -= game.entry_fee;
-    **player.to_account_info().try_borrow_mut_lamports()? += game.entry_fee;
-    game.refunded_players.push(player.key());
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(&game, &vault, &player.to_account_info(), &ctx.accounts.player_token_account)?;
+    let refund_amount = ctx.accounts.player_entry.amount;
+    pay_from_game(&game, &vault, &recipient, refund_amount)?;
+    if let Some(bond) = &mut ctx.accounts.creator_bond {
+        if game.has_status(GameStatus::ExpiredWithPenalty) {
+            pay_bond_share(&game, bond, &player.to_account_info())?;
+        }
+    }
+    ctx.accounts.player_entry.refunded = true;
+    game.refunded_count += 1;
+    game.prize_pool -= refund_amount;
+    game.forfeit_creator_stake();
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
     
     emit!(RefundClaimed {
         game_id: game.game_id,
         player: player.key(),
-        amount: game.entry_fee,
+        amount: refund_amount,
     });
     
     Ok(())
@@ -84,48 +151,72 @@ pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
 
 /// Players can force refund if creator didn't fulfill their obligations
 pub fn force_refund_expired_game(ctx: Context<ForceRefund>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
     let player = &ctx.accounts.player;
     let clock = Clock::get()?;
     
+    // Paused time doesn't count towards the creator's grace period
+    game.sync_pause(&ctx.accounts.game_registry)?;
     require!(
-        game.players.contains(&player.key()),
-        GameError::NotInGame
+        !ctx.accounts.player_entry.refunded,
+        GameError::AlreadyRefunded
     );
     require!(
-        !game.refunded_players.contains(&player.key()),
-        GameError::AlreadyRefunded
+        player.key() != game.creator,
+        GameError::CreatorForfeitedFunds
     );
-    require!(!game.game_started, GameError::GameAlreadyStarted);
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
     require!(
-        game.status == GameStatus::WaitingForPlayers,
+        game.has_status(GameStatus::WaitingForPlayers),
         GameError::GameNotOpen
     );
     
     // Must wait 30 minutes after start time
-    let refund_available_time = game.start_time + GAME_START_GRACE_PERIOD;
+    let refund_available_time = game.start_time + game.config.game_start_grace_period;
     require!(
         clock.unix_timestamp >= refund_available_time,
         GameError::RefundNotYetAvailable
     );
     
     // Determine why refund is valid
-    let should_have_started = game.current_players >= MIN_PLAYERS_TO_START;
-    let should_have_cancelled = game.current_players < MIN_PLAYERS_TO_START;
+    let should_have_started = game.current_players >= game.config.min_players_to_start;
+    let should_have_cancelled = game.current_players < game.config.min_players_to_start;
     
     require!(
         should_have_started || should_have_cancelled,
         GameError::InvalidRefundCondition
     );
+    // A forced refund always expires the game with penalty
+    require!(
+        game.creator_bonded == 0 || ctx.accounts.creator_bond.is_some(),
+        GameError::CreatorBondRequired
+    );
     
     // Process forced refund
-    **game.to_account_info().try_borrow_mut_lamports()? -= game.entry_fee;
-    **player.to_account_info().try_borrow_mut_lamports()? += game.entry_fee;
-    game.refunded_players.push(player.key());
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(&game, &vault, &player.to_account_info(), &ctx.accounts.player_token_account)?;
+    let refund_amount = ctx.accounts.player_entry.amount;
+    pay_from_game(&game, &vault, &recipient, refund_amount)?;
+    // The creator's bond is slashed to the players
+    if let Some(bond) = &mut ctx.accounts.creator_bond {
+        pay_bond_share(&game, bond, &player.to_account_info())?;
+    }
+    ctx.accounts.player_entry.refunded = true;
+    game.refunded_count += 1;
+    game.prize_pool -= refund_amount;
     
-    // Mark game as expired with penalty (creator loses their entry fee)
-    if game.status != GameStatus::ExpiredWithPenalty {
-        game.status = GameStatus::ExpiredWithPenalty;
+    // Mark game as expired with penalty (creator loses their entry fee and bond)
+    if !game.has_status(GameStatus::ExpiredWithPenalty) {
+        game.finish(GameStatus::ExpiredWithPenalty, clock.unix_timestamp);
         
         let reason = if should_have_started {
             "Creator failed to start game with sufficient players"
@@ -140,10 +231,109 @@ pub fn force_refund_expired_game(ctx: Context<ForceRefund>) -> Result<()> {
         });
     }
     
+    game.forfeit_creator_stake();
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
     emit!(ForcedRefundClaimed {
         game_id: game.game_id,
         player: player.key(),
-        amount: game.entry_fee,
+        amount: refund_amount,
+    });
+    
+    Ok(())
+}
+
+/// Anyone pushes refunds to players of a cancelled or expired game
+/// remaining_accounts holds (player_entry, player wallet) pairs, plus the
+/// player's token account as a third account for token games. Each refunded
+/// entry is closed: the caller keeps a small tip from its rent and the rest
+/// goes back to the player. Entries that aren't owed a refund are skipped.
+pub fn crank_refunds<'info>(ctx: Context<'_, '_, 'info, 'info, CrankRefunds<'info>>) -> Result<()> {
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
+    let cranker = ctx.accounts.cranker.to_account_info();
+    
+    require!(
+        game.has_status(GameStatus::Cancelled)
+            || game.has_status(GameStatus::ExpiredWithPenalty),
+        GameError::GameNotCancelled
+    );
+    require_slashed_bond(&game, &ctx.accounts.creator_bond)?;
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    let chunk = if game.is_token_game() { 3 } else { 2 };
+    let mut players_refunded: u16 = 0;
+    let mut tips: u64 = 0;
+    for accounts in ctx.remaining_accounts.chunks_exact(chunk) {
+        let (entry_info, wallet) = (&accounts[0], &accounts[1]);
+        
+        let entry = match Account::<PlayerEntry>::try_from(entry_info) {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        // The creator forfeited their stake once the game expired with penalty
+        let forfeited = game.has_status(GameStatus::ExpiredWithPenalty) && entry.player == game.creator;
+        if entry.game_id != game.game_id || entry.player != wallet.key() || entry.refunded || forfeited {
+            continue;
+        }
+        
+        let recipient = match game.stake_mint() {
+            Some(mint) => {
+                let valid = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])
+                    .map(|token_account| token_account.mint == mint && token_account.owner == entry.player)
+                    .unwrap_or(false);
+                if !valid {
+                    continue;
+                }
+                accounts[2].clone()
+            }
+            None => wallet.clone(),
+        };
+        
+        pay_from_game(&game, &vault, &recipient, entry.amount)?;
+        if let Some(bond) = &mut ctx.accounts.creator_bond {
+            if game.has_status(GameStatus::ExpiredWithPenalty) {
+                pay_bond_share(&game, bond, wallet)?;
+            }
+        }
+        game.refunded_count += 1;
+        game.prize_pool -= entry.amount;
+        game.forfeit_creator_stake();
+        
+        emit!(RefundClaimed {
+            game_id: game.game_id,
+            player: entry.player,
+            amount: entry.amount,
+        });
+        
+        // Tip the caller out of the entry's rent, return the rest to the player
+        let tip = game.config.crank_refund_tip.min(entry_info.lamports());
+        **entry_info.try_borrow_mut_lamports()? -= tip;
+        **cranker.try_borrow_mut_lamports()? += tip;
+        entry.close(wallet.clone())?;
+        
+        players_refunded += 1;
+        tips += tip;
+    }
+    
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    emit!(RefundsCranked {
+        game_id: game.game_id,
+        cranker: cranker.key(),
+        players_refunded,
+        tips,
     });
     
     Ok(())
@@ -152,25 +342,147 @@ pub fn force_refund_expired_game(ctx: Context<ForceRefund>) -> Result<()> {
 #[derive(Accounts)]
 pub struct CreatorCancelGame<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    /// Creator bond - absent for games created before bonds; if omitted it is
+    /// returned on `close_game`
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"creator_bond", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = creator_bond.bump
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
     
+    #[account(mut)]
     pub creator: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct ClaimRefund<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_entry.bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    /// Creator bond - pays each player a share once the game expired with penalty
+    #[account(
+        mut,
+        seeds = [b"creator_bond", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = creator_bond.bump
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
     
     #[account(mut)]
     pub player: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Player's token account for the game mint (token games only)
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct ForceRefund<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_entry.bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    /// Creator bond - pays each player a share once the game expired with penalty
+    #[account(
+        mut,
+        seeds = [b"creator_bond", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = creator_bond.bump
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
     
     #[account(mut)]
     pub player: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Player's token account for the game mint (token games only)
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankRefunds<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    /// Creator bond - pays each player a share once the game expired with penalty
+    #[account(
+        mut,
+        seeds = [b"creator_bond", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = creator_bond.bump
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
 }
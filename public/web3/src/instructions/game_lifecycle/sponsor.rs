@@ -0,0 +1,202 @@
+// Sponsorship - partners topping up a game's prize pool
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Game, GameRegistry, GameStatus, SponsorRecord};
+use crate::events::{GameSponsored, SponsorRefunded};
+use crate::errors::GameError;
+use crate::constants::MAX_SPONSOR_NAME_LEN;
+use crate::utils::{
+    check_vault_balance, deposit_to_game, game_vault, pay_from_game, payout_recipient, user_token_account,
+};
+
+/// Any wallet adds `amount` of the game's currency to the prize pool
+/// Unless `fee_eligible` is set the funds are excluded from the platform fee.
+/// Refunded if the game never starts.
+pub fn sponsor_game(ctx: Context<SponsorGame>, amount: u64, display_name: String, fee_eligible: bool) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let sponsor = &ctx.accounts.sponsor;
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require!(
+        !game.has_final_status() && game.winner().is_none(),
+        GameError::SponsorshipClosed
+    );
+    require!(amount > 0, GameError::InvalidSponsorship);
+    require!(display_name.len() <= MAX_SPONSOR_NAME_LEN, GameError::NameTooLong);
+    
+    let record = &mut ctx.accounts.sponsor_record;
+    if record.version == 0 {
+        record.version = SponsorRecord::VERSION;
+        record.game_id = game.game_id;
+        record.sponsor = sponsor.key();
+        record.fee_eligible = fee_eligible;
+        record.bump = ctx.bumps.sponsor_record;
+    }
+    // One record per sponsor, so its funds are either all fee-exempt or not
+    require!(record.fee_eligible == fee_eligible, GameError::InvalidSponsorship);
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let source = match vault.token {
+        Some(_) => Some(user_token_account(game, &ctx.accounts.sponsor_token_account, &sponsor.key())?),
+        None => None,
+    };
+    let received = deposit_to_game(&vault, sponsor, source, amount)?;
+    
+    record.display_name = display_name;
+    record.amount += received;
+    record.sponsored_at = Clock::get()?.unix_timestamp;
+    
+    game.prize_pool += received;
+    game.sponsored_amount += received;
+    if !fee_eligible {
+        game.sponsor_fee_exempt += received;
+    }
+    check_vault_balance(game, &vault)?;
+    
+    emit!(GameSponsored {
+        game_id: game.game_id,
+        sponsor: sponsor.key(),
+        display_name: record.display_name.clone(),
+        amount: received,
+        fee_eligible,
+        total_sponsored: game.sponsored_amount,
+    });
+    
+    Ok(())
+}
+
+/// Return a sponsor's funds once the game was cancelled or expired
+/// Permissionless - the funds and record rent always go to the sponsor
+pub fn refund_sponsor(ctx: Context<RefundSponsor>) -> Result<()> {
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
+    let record = &ctx.accounts.sponsor_record;
+    
+    require!(
+        game.has_status(GameStatus::Cancelled)
+            || game.has_status(GameStatus::ExpiredWithPenalty),
+        GameError::InvalidRefundCondition
+    );
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(&game, &vault, &ctx.accounts.sponsor, &ctx.accounts.sponsor_token_account)?;
+    pay_from_game(&game, &vault, &recipient, record.amount)?;
+    
+    game.prize_pool -= record.amount;
+    game.sponsored_amount -= record.amount;
+    if !record.fee_eligible {
+        game.sponsor_fee_exempt -= record.amount;
+    }
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    emit!(SponsorRefunded {
+        game_id: game.game_id,
+        sponsor: record.sponsor,
+        amount: record.amount,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SponsorGame<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = SponsorRecord::SIZE,
+        seeds = [
+            b"sponsor",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            sponsor.key().as_ref()
+        ],
+        bump
+    )]
+    pub sponsor_record: Account<'info, SponsorRecord>,
+    
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Sponsor's token account for the game mint (token games only)
+    #[account(mut)]
+    pub sponsor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundSponsor<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(
+        mut,
+        close = sponsor,
+        seeds = [
+            b"sponsor",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            sponsor_record.sponsor.as_ref()
+        ],
+        bump = sponsor_record.bump
+    )]
+    pub sponsor_record: Account<'info, SponsorRecord>,
+    
+    /// CHECK: refund destination, must be the sponsor
+    #[account(mut, address = sponsor_record.sponsor)]
+    pub sponsor: UncheckedAccount<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Sponsor's token account for the game mint (token games only)
+    #[account(mut)]
+    pub sponsor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,283 @@
+// Squad formation - players teaming up in a squad game before it starts
+
+use anchor_lang::prelude::*;
+use crate::state::{Game, GameRegistry, GameStatus, PlayerEntry, Squad, SquadMember};
+use crate::events::{SquadChanged, SquadCreated};
+use crate::errors::GameError;
+use crate::constants::{BPS_DENOMINATOR, MAX_SQUAD_NAME_LEN, MIN_SQUAD_SIZE};
+
+/// Squads form and change only while the game is open for entries
+fn require_squads_open(game: &Game) -> Result<()> {
+    require!(game.is_squad_game(), GameError::NotSquadGame);
+    require!(
+        game.has_status(GameStatus::WaitingForPlayers) || game.has_status(GameStatus::ReadyToStart),
+        GameError::GameNotOpen
+    );
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
+    Ok(())
+}
+
+/// A player who entered the game founds a squad and becomes its first member
+/// `shares` has one prize share per slot (basis points summing to 100%),
+/// and its length is the squad's size
+pub fn create_squad(ctx: Context<CreateSquad>, name: String, shares: Vec<u16>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let leader = ctx.accounts.leader.key();
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require_squads_open(game)?;
+    require!(name.len() <= MAX_SQUAD_NAME_LEN, GameError::NameTooLong);
+    require!(
+        (MIN_SQUAD_SIZE as usize..=game.squad_size as usize).contains(&shares.len())
+            && shares.iter().all(|s| *s > 0)
+            && shares.iter().map(|s| *s as u64).sum::<u64>() == BPS_DENOMINATOR,
+        GameError::InvalidSquad
+    );
+    
+    let squad = &mut ctx.accounts.squad;
+    squad.version = Squad::VERSION;
+    squad.game_id = game.game_id;
+    squad.leader = leader;
+    squad.name = name;
+    squad.shares = shares;
+    squad.members = vec![leader];
+    squad.phase3_ready = false;
+    squad.prize_total = 0;
+    squad.claimed_mask = 0;
+    squad.bump = ctx.bumps.squad;
+    
+    let member = &mut ctx.accounts.squad_member;
+    member.version = SquadMember::VERSION;
+    member.game_id = game.game_id;
+    member.player = leader;
+    member.squad = squad.key();
+    member.bump = ctx.bumps.squad_member;
+    
+    game.squad_count += 1;
+    game.squad_players += 1;
+    game.short_squads += 1;
+    
+    emit!(SquadCreated {
+        game_id: game.game_id,
+        squad: squad.key(),
+        leader,
+        name: squad.name.clone(),
+        shares: squad.shares.clone(),
+    });
+    
+    Ok(())
+}
+
+/// A player who entered the game takes the next free slot of a squad
+pub fn join_squad(ctx: Context<JoinSquad>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let squad = &mut ctx.accounts.squad;
+    let player = ctx.accounts.player.key();
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require_squads_open(game)?;
+    require!(squad.members.len() < squad.shares.len(), GameError::SquadFull);
+    
+    squad.members.push(player);
+    
+    let member = &mut ctx.accounts.squad_member;
+    member.version = SquadMember::VERSION;
+    member.game_id = game.game_id;
+    member.player = player;
+    member.squad = squad.key();
+    member.bump = ctx.bumps.squad_member;
+    
+    game.squad_players += 1;
+    if squad.members.len() == MIN_SQUAD_SIZE as usize {
+        game.short_squads -= 1;
+    }
+    
+    emit!(SquadChanged {
+        game_id: game.game_id,
+        squad: squad.key(),
+        player,
+        joined: true,
+        members: squad.members.len() as u8,
+    });
+    
+    Ok(())
+}
+
+/// Member leaves their squad before the game starts; later members move up
+/// a slot and keep their share. The leader leaves last, which disbands the squad.
+pub fn leave_squad(ctx: Context<LeaveSquad>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let squad = &mut ctx.accounts.squad;
+    let player = ctx.accounts.player.key();
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require_squads_open(game)?;
+    let index = squad.member_index(&player).ok_or(GameError::NotSquadMember)?;
+    require!(
+        player != squad.leader || squad.members.len() == 1,
+        GameError::InvalidSquad
+    );
+    
+    squad.members.remove(index);
+    let share = squad.shares.remove(index);
+    squad.shares.push(share);
+    game.squad_players -= 1;
+    match squad.members.len() {
+        0 => {
+            game.squad_count -= 1;
+            game.short_squads -= 1;
+            squad.close(ctx.accounts.player.to_account_info())?;
+        }
+        len if len + 1 == MIN_SQUAD_SIZE as usize => game.short_squads += 1,
+        _ => {}
+    }
+    
+    emit!(SquadChanged {
+        game_id: game.game_id,
+        squad: squad.key(),
+        player,
+        joined: false,
+        members: squad.members.len() as u8,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateSquad<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        init,
+        payer = leader,
+        space = Squad::SIZE,
+        seeds = [
+            b"squad",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            leader.key().as_ref()
+        ],
+        bump
+    )]
+    pub squad: Account<'info, Squad>,
+    
+    /// One per player - creating it fails if the leader is already in a squad
+    #[account(
+        init,
+        payer = leader,
+        space = SquadMember::SIZE,
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            leader.key().as_ref()
+        ],
+        bump
+    )]
+    pub squad_member: Account<'info, SquadMember>,
+    
+    /// Proves the leader joined this game
+    #[account(
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            leader.key().as_ref()
+        ],
+        bump = player_entry.bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    #[account(mut)]
+    pub leader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinSquad<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"squad",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            squad.leader.as_ref()
+        ],
+        bump = squad.bump
+    )]
+    pub squad: Account<'info, Squad>,
+    
+    /// One per player - creating it fails if the player is already in a squad
+    #[account(
+        init,
+        payer = player,
+        space = SquadMember::SIZE,
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub squad_member: Account<'info, SquadMember>,
+    
+    /// Proves the player joined this game
+    #[account(
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_entry.bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveSquad<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"squad",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            squad.leader.as_ref()
+        ],
+        bump = squad.bump
+    )]
+    pub squad: Account<'info, Squad>,
+    
+    /// Membership is closed - the player gets its rent back
+    #[account(
+        mut,
+        close = player,
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = squad_member.bump,
+        constraint = squad_member.squad == squad.key() @ GameError::NotSquadMember
+    )]
+    pub squad_member: Account<'info, SquadMember>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
@@ -1,44 +1,49 @@
 // Game start logic - transitioning from waiting to active
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, GameStatus};
-use crate::events::GameStarted;
+use crate::state::{CreatorBond, Game, GameRegistry, GameStatus};
+use crate::events::{CreatorBondReturned, GameStarted};
 use crate::errors::GameError;
-use crate::constants::{MIN_PLAYERS_TO_START, GAME_START_GRACE_PERIOD, PHASE_ADVANCE_BUFFER};
 
 pub fn start_game(ctx: Context<StartGame>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &mut ctx.accounts.game.load_mut()?;
     let clock = Clock::get()?;
     
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    
     // Only creator can start
     require!(
         game.creator == ctx.accounts.creator.key(),
         GameError::NotCreator
     );
-    require!(!game.game_started, GameError::GameAlreadyStarted);
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
     require!(
-        game.current_players >= MIN_PLAYERS_TO_START,
+        game.current_players >= game.config.min_players_to_start,
         GameError::NotEnoughPlayers
     );
+    require!(
+        !game.is_squad_game() || game.squads_complete(),
+        GameError::SquadsIncomplete
+    );
     require!(
         clock.unix_timestamp >= game.start_time,
         GameError::GameNotStarted
     );
     
     // Check 30-minute start window
-    let start_deadline = game.start_time + GAME_START_GRACE_PERIOD;
+    let start_deadline = game.start_time + game.config.game_start_grace_period;
     require!(
         clock.unix_timestamp <= start_deadline,
         GameError::StartWindowExpired
     );
     
     // Start the game
-    game.game_started = true;
-    game.status = GameStatus::InProgress;
+    game.game_started = 1;
+    game.status = GameStatus::InProgress as u8;
     game.current_phase = 1;
     game.phase_start_time = clock.unix_timestamp;
     game.phase_end_time = clock.unix_timestamp + game.phases.phase1_duration as i64;
-    game.phase_advance_deadline = game.phase_end_time + PHASE_ADVANCE_BUFFER;
+    game.phase_advance_deadline = game.phase_end_time + game.config.phase_advance_buffer;
     
     emit!(GameStarted {
         game_id: game.game_id,
@@ -46,13 +51,37 @@ pub fn start_game(ctx: Context<StartGame>) -> Result<()> {
         phase1_end_time: game.phase_end_time,
     });
     
+    // Started on time - the bond goes back to the creator (closed by the constraint)
+    if let Some(bond) = &ctx.accounts.creator_bond {
+        game.creator_bonded = 0;
+        emit!(CreatorBondReturned {
+            game_id: game.game_id,
+            creator: game.creator,
+            amount: bond.amount,
+        });
+    }
+    
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct StartGame<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
     
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Creator bond - absent for games created before bonds; if omitted it is
+    /// returned on `close_game`
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"creator_bond", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = creator_bond.bump
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
+    
+    #[account(mut)]
     pub creator: Signer<'info>,
 }
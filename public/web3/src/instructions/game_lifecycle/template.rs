@@ -0,0 +1,365 @@
+// Game templates - operator-owned recurring games spawned on a schedule
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{require_role, CreatorBond, Game, GameRegistry, GameTemplate, GlobalConfig, Role, RoleRegistry, TemplateParams};
+use crate::events::{GameCreated, TemplateGameSpawned, TemplateUpdated};
+use crate::errors::GameError;
+use crate::constants::{MAX_GAME_NAME_LEN, MAX_TEMPLATE_LEAD_TIME, MIN_TEMPLATE_CADENCE};
+use crate::utils::validate_stake_mint;
+use super::create::{fund_vault_reserve, init_game, GameSettings};
+
+/// Operator creates a recurring game template
+/// Game settings are checked against the current config now, and again on
+/// every spawn in case the config changed in between
+pub fn create_template(ctx: Context<CreateTemplate>, template_id: u64, params: TemplateParams) -> Result<()> {
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.operator.key(),
+        Role::Operator,
+    )?;
+    if let Some(mint) = &ctx.accounts.mint {
+        validate_stake_mint(mint)?;
+    }
+    validate_template(&params, &ctx.accounts.global_config, true)?;
+    
+    let template = &mut ctx.accounts.template;
+    template.version = GameTemplate::VERSION;
+    template.template_id = template_id;
+    template.operator = ctx.accounts.operator.key();
+    template.mint = ctx.accounts.mint.as_ref().map(|mint| mint.key());
+    template.games_spawned = 0;
+    template.last_game_id = None;
+    template.bump = ctx.bumps.template;
+    apply_params(template, params, true);
+    
+    emit_template_updated(template);
+    
+    Ok(())
+}
+
+/// Operator changes a template's settings or schedule, or pauses it
+/// A paused template may keep a start time that has already passed.
+pub fn update_template(ctx: Context<UpdateTemplate>, params: TemplateParams, active: bool) -> Result<()> {
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.operator.key(),
+        Role::Operator,
+    )?;
+    validate_template(&params, &ctx.accounts.global_config, active)?;
+    
+    let template = &mut ctx.accounts.template;
+    apply_params(template, params, active);
+    
+    emit_template_updated(template);
+    
+    Ok(())
+}
+
+/// Anyone tops up a template's spawn budget
+pub fn fund_template(ctx: Context<FundTemplate>, amount: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.template.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Operator closes a template; the remaining budget and rent go back to them
+pub fn close_template(_ctx: Context<CloseTemplate>) -> Result<()> {
+    Ok(())
+}
+
+/// Anyone creates the template's next game once it is due
+/// The spawner pays the rent up front and is paid back from the template's
+/// budget, which also posts the operator's creator bond. Missed slots are
+/// skipped so the game always starts in the future.
+pub fn spawn_from_template(ctx: Context<SpawnFromTemplate>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_init()?;
+    let template = &mut ctx.accounts.template;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // A revoked operator's templates stop spawning
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &template.operator,
+        Role::Operator,
+    )?;
+    require!(template.active, GameError::TemplateInactive);
+    require!(
+        now >= template.next_start_time - template.lead_time,
+        GameError::TemplateNotDue
+    );
+    require!(
+        ctx.accounts.mint.as_ref().map(|mint| mint.key()) == template.mint,
+        GameError::TemplateMismatch
+    );
+    if template.mint.is_some() {
+        require!(
+            ctx.accounts.token_vault.is_some() && ctx.accounts.token_program.is_some(),
+            GameError::MissingTokenAccounts
+        );
+    }
+    
+    let mut start_time = template.next_start_time;
+    if start_time <= now {
+        start_time += ((now - start_time) / template.cadence + 1) * template.cadence;
+    }
+    
+    let settings = GameSettings {
+        name: &template.name,
+        entry_fee: template.entry_fee,
+        max_players: template.max_players,
+        start_time,
+        rules: &template.rules,
+        access: &template.access,
+    };
+    let (required_games, max_per_opponent) = init_game(
+        game,
+        &mut ctx.accounts.game_registry,
+        &ctx.accounts.global_config,
+        template.operator,
+        &settings,
+        template.mint,
+    )?;
+    game.bump = ctx.bumps.game;
+    game.vault_bump = ctx.bumps.vault;
+    
+    // Creator bond on behalf of the operator, returned to them on start or cancel
+    let bond = &mut ctx.accounts.creator_bond;
+    bond.version = CreatorBond::VERSION;
+    bond.game_id = game.game_id;
+    bond.creator = game.creator;
+    bond.amount = game.config.creator_bond;
+    bond.bump = ctx.bumps.creator_bond;
+    game.creator_bonded = 1;
+    
+    // Pay the spawner back for every account it funded, keeping the template rent exempt
+    let rent = Rent::get()?;
+    let mut reimbursed = rent.minimum_balance(Game::SIZE)
+        + rent.minimum_balance(CreatorBond::SIZE)
+        + fund_vault_reserve(&ctx.accounts.vault, &ctx.accounts.spawner, &ctx.accounts.system_program)?;
+    if let Some(token_vault) = &ctx.accounts.token_vault {
+        reimbursed += rent.minimum_balance(token_vault.to_account_info().data_len());
+    }
+    let template_info = template.to_account_info();
+    let budget = template_info.lamports().saturating_sub(rent.minimum_balance(template_info.data_len()));
+    require!(budget >= reimbursed + bond.amount, GameError::TemplateUnderfunded);
+    **template_info.try_borrow_mut_lamports()? -= reimbursed + bond.amount;
+    **ctx.accounts.spawner.try_borrow_mut_lamports()? += reimbursed;
+    **bond.to_account_info().try_borrow_mut_lamports()? += bond.amount;
+    
+    template.next_start_time = start_time + template.cadence;
+    template.games_spawned += 1;
+    template.last_game_id = Some(game.game_id);
+    
+    emit!(GameCreated {
+        game_id: game.game_id,
+        creator: game.creator,
+        entry_fee: game.entry_fee,
+        max_players: game.max_players,
+        start_time: game.start_time,
+        mint: game.stake_mint(),
+        phase2_required_games: required_games,
+        phase2_max_games_per_opponent: max_per_opponent,
+        creator_fee_bps: game.creator_fee_bps,
+        creator_bond: bond.amount,
+        access_mode: template.access.mode,
+    });
+    emit!(TemplateGameSpawned {
+        template: template.key(),
+        game_id: game.game_id,
+        start_time,
+        spawner: ctx.accounts.spawner.key(),
+        reimbursed,
+    });
+    
+    Ok(())
+}
+
+/// Check a template's schedule and the game settings it will spawn with
+/// The start time only has to be ahead for templates that will spawn.
+fn validate_template(params: &TemplateParams, global_config: &GlobalConfig, active: bool) -> Result<()> {
+    let config = &global_config.params;
+    
+    require!(params.cadence >= MIN_TEMPLATE_CADENCE, GameError::InvalidTemplateSchedule);
+    require!(
+        (1..=MAX_TEMPLATE_LEAD_TIME).contains(&params.lead_time),
+        GameError::InvalidTemplateSchedule
+    );
+    require!(
+        !active || params.next_start_time > Clock::get()?.unix_timestamp,
+        GameError::InvalidStartTime
+    );
+    require!(
+        (config.min_players_to_start..=config.max_players_allowed).contains(&params.max_players),
+        GameError::InvalidMaxPlayers
+    );
+    params.rules.validate(config, &global_config.rule_bounds, params.max_players)?;
+    params.access.validate()?;
+    require!(params.name.len() <= MAX_GAME_NAME_LEN, GameError::NameTooLong);
+    require!(params.entry_fee > 0, GameError::InvalidEntryFee);
+    
+    Ok(())
+}
+
+fn apply_params(template: &mut GameTemplate, params: TemplateParams, active: bool) {
+    template.name = params.name;
+    template.entry_fee = params.entry_fee;
+    template.max_players = params.max_players;
+    template.rules = params.rules;
+    template.access = params.access;
+    template.cadence = params.cadence;
+    template.lead_time = params.lead_time;
+    template.next_start_time = params.next_start_time;
+    template.active = active;
+}
+
+fn emit_template_updated(template: &Account<GameTemplate>) {
+    emit!(TemplateUpdated {
+        template: template.key(),
+        operator: template.operator,
+        cadence: template.cadence,
+        next_start_time: template.next_start_time,
+        active: template.active,
+    });
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct CreateTemplate<'info> {
+    #[account(
+        init,
+        payer = operator,
+        space = GameTemplate::SIZE,
+        seeds = [b"game_template", operator.key().as_ref(), template_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub template: Account<'info, GameTemplate>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(seeds = [b"global_config"], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    
+    /// Optional SPL / Token-2022 mint spawned games are played in (omit for SOL games)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTemplate<'info> {
+    #[account(mut, has_one = operator @ GameError::Unauthorized)]
+    pub template: Account<'info, GameTemplate>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(seeds = [b"global_config"], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundTemplate<'info> {
+    #[account(mut)]
+    pub template: Account<'info, GameTemplate>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTemplate<'info> {
+    #[account(mut, close = operator, has_one = operator @ GameError::Unauthorized)]
+    pub template: Account<'info, GameTemplate>,
+    
+    #[account(mut)]
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SpawnFromTemplate<'info> {
+    #[account(mut)]
+    pub template: Account<'info, GameTemplate>,
+    
+    #[account(
+        init,
+        payer = spawner,
+        space = Game::SIZE,
+        seeds = [b"game", game_registry.game_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(mut, seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(seeds = [b"global_config"], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    #[account(
+        init,
+        payer = spawner,
+        space = CreatorBond::SIZE,
+        seeds = [b"creator_bond", game_registry.game_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+    
+    #[account(mut)]
+    pub spawner: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game_registry.game_count.to_le_bytes().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// The template's mint (token templates only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault holding token stakes, owned by the game PDA
+    #[account(
+        init,
+        payer = spawner,
+        associated_token::mint = mint,
+        associated_token::authority = game,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    
+    pub system_program: Program<'info, System>,
+}
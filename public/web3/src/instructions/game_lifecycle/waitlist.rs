@@ -0,0 +1,380 @@
+// Waitlist - queueing for a full game, promotion into free seats and refunds
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Game, GameRegistry, GameStatus, PlayerEntry, Waitlist, WaitlistEntry};
+use crate::events::{WaitlistJoined, WaitlistPromoted, WaitlistRefunded, WaitlistSkipped};
+use crate::errors::GameError;
+use crate::utils::{
+    check_vault_balance, deposit_to_game, game_vault, pay_from_game, payout_recipient, user_token_account,
+};
+
+/// Player queues for a full game, depositing the entry fee up front
+/// Once anyone is queued, newcomers queue behind them even if a seat is free.
+/// The deposit is held in the game vault until promotion or refund
+pub fn join_waitlist(ctx: Context<JoinWaitlist>, proof: Vec<[u8; 32]>, invite_code: String) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let player = &ctx.accounts.player;
+    let clock = Clock::get()?;
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require!(
+        game.has_status(GameStatus::ReadyToStart)
+            || (game.has_status(GameStatus::WaitingForPlayers) && game.waitlist_escrow > 0),
+        GameError::SeatsAvailable
+    );
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
+    require!(
+        clock.unix_timestamp < game.start_time,
+        GameError::GameExpired
+    );
+    require!(
+        ctx.accounts.player_entry.data_is_empty(),
+        GameError::AlreadyJoined
+    );
+    game.check_access(&player.key(), &proof, &invite_code)?;
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let source = match vault.token {
+        Some(_) => Some(user_token_account(game, &ctx.accounts.player_token_account, &player.key())?),
+        None => None,
+    };
+    let received = deposit_to_game(&vault, player, source, game.entry_fee)?;
+    game.waitlist_escrow += received;
+    check_vault_balance(game, &vault)?;
+    
+    let waitlist = &mut ctx.accounts.waitlist;
+    if waitlist.version == 0 {
+        waitlist.version = Waitlist::VERSION;
+        waitlist.game_id = game.game_id;
+        waitlist.payer = player.key();
+        waitlist.bump = ctx.bumps.waitlist;
+    }
+    
+    let entry = &mut ctx.accounts.waitlist_entry;
+    entry.version = WaitlistEntry::VERSION;
+    entry.game_id = game.game_id;
+    entry.player = player.key();
+    entry.position = waitlist.tail;
+    entry.amount = received;
+    entry.joined_at = clock.unix_timestamp;
+    entry.bump = ctx.bumps.waitlist_entry;
+    
+    waitlist.tail += 1;
+    waitlist.waiting += 1;
+    
+    emit!(WaitlistJoined {
+        game_id: game.game_id,
+        player: player.key(),
+        position: entry.position,
+        amount: received,
+    });
+    
+    Ok(())
+}
+
+/// Anyone can move the head of the waitlist into a free seat
+/// `leave_game` does this itself when given the waitlist accounts; this
+/// covers seats freed without them
+pub fn promote_from_waitlist(ctx: Context<PromoteFromWaitlist>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    promote_waitlist_entry(
+        game,
+        &mut ctx.accounts.waitlist,
+        &ctx.accounts.waitlist_entry,
+        &ctx.accounts.player_entry,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )
+}
+
+/// Move the next waitlisted player into the game
+/// The deposit already sits in the vault, so it just moves from escrow to
+/// the prize pool. `payer` funds the new `PlayerEntry` and gets the larger
+/// `WaitlistEntry` rent back. A head that already holds a seat is skipped
+/// and left for `refund_waitlist`
+pub(crate) fn promote_waitlist_entry<'info>(
+    game: &mut Game,
+    waitlist: &mut Account<'info, Waitlist>,
+    waitlist_entry: &Account<'info, WaitlistEntry>,
+    player_entry: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(
+        game.has_status(GameStatus::WaitingForPlayers),
+        GameError::GameFull
+    );
+    require!(game.game_started == 0, GameError::GameAlreadyStarted);
+    require!(
+        clock.unix_timestamp < game.start_time,
+        GameError::GameExpired
+    );
+    require!(
+        waitlist.game_id == game.game_id && waitlist_entry.game_id == game.game_id,
+        GameError::WaitlistMismatch
+    );
+    require!(
+        waitlist_entry.position == waitlist.head,
+        GameError::WaitlistOutOfOrder
+    );
+    
+    // Create the player's entry exactly as `enter_game` would have
+    let game_id = game.game_id.to_le_bytes();
+    let (entry_key, entry_bump) = Pubkey::find_program_address(
+        &[b"player_entry", game_id.as_ref(), waitlist_entry.player.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(player_entry.key(), entry_key, GameError::WaitlistMismatch);
+    if !player_entry.data_is_empty() {
+        waitlist.head += 1;
+        emit!(WaitlistSkipped {
+            game_id: game.game_id,
+            player: waitlist_entry.player,
+            position: waitlist_entry.position,
+        });
+        return Ok(());
+    }
+    
+    let seeds: &[&[u8]] = &[b"player_entry", game_id.as_ref(), waitlist_entry.player.as_ref(), &[entry_bump]];
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: player_entry.clone(),
+            },
+            &[seeds],
+        ),
+        Rent::get()?.minimum_balance(PlayerEntry::SIZE),
+        PlayerEntry::SIZE as u64,
+        &crate::ID,
+    )?;
+    let entry = PlayerEntry {
+        version: PlayerEntry::VERSION,
+        game_id: game.game_id,
+        player: waitlist_entry.player,
+        entered_at: clock.unix_timestamp,
+        amount: waitlist_entry.amount,
+        refunded: false,
+        bump: entry_bump,
+    };
+    entry.try_serialize(&mut &mut player_entry.try_borrow_mut_data()?[..])?;
+    
+    game.waitlist_escrow -= waitlist_entry.amount;
+    game.prize_pool += waitlist_entry.amount;
+    game.current_players += 1;
+    if waitlist_entry.player == game.creator {
+        game.creator_joined = 1;
+    }
+    if game.current_players == game.max_players {
+        game.status = GameStatus::ReadyToStart as u8;
+    }
+    
+    waitlist.head += 1;
+    waitlist.waiting -= 1;
+    waitlist_entry.close(payer.to_account_info())?;
+    
+    emit!(WaitlistPromoted {
+        game_id: game.game_id,
+        player: waitlist_entry.player,
+        position: waitlist_entry.position,
+        current_players: game.current_players,
+    });
+    
+    Ok(())
+}
+
+/// Refund a waitlist deposit once the player can no longer be promoted,
+/// either because the game closed for entries or their turn was skipped
+/// Permissionless - the deposit and entry rent always go to the player
+pub fn refund_waitlist(ctx: Context<RefundWaitlist>) -> Result<()> {
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
+    let clock = Clock::get()?;
+    
+    let still_open = game.game_started == 0
+        && (game.has_status(GameStatus::WaitingForPlayers) || game.has_status(GameStatus::ReadyToStart))
+        && clock.unix_timestamp < game.start_time;
+    let skipped = ctx.accounts.waitlist_entry.position < ctx.accounts.waitlist.head;
+    require!(!still_open || skipped, GameError::WaitlistStillOpen);
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(&game, &vault, &ctx.accounts.player, &ctx.accounts.player_token_account)?;
+    let amount = ctx.accounts.waitlist_entry.amount;
+    pay_from_game(&game, &vault, &recipient, amount)?;
+    
+    game.waitlist_escrow -= amount;
+    ctx.accounts.waitlist.waiting -= 1;
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    emit!(WaitlistRefunded {
+        game_id: game.game_id,
+        player: ctx.accounts.player.key(),
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct JoinWaitlist<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = Waitlist::SIZE,
+        seeds = [b"waitlist", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = WaitlistEntry::SIZE,
+        seeds = [
+            b"waitlist_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    
+    /// CHECK: must not exist yet - players already in the game can't queue
+    #[account(
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub player_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Player's token account for the game mint (token games only)
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PromoteFromWaitlist<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"waitlist", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    
+    #[account(mut)]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    
+    /// CHECK: the promoted player's entry, created and checked in the instruction
+    #[account(mut)]
+    pub player_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundWaitlist<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(
+        mut,
+        seeds = [b"waitlist", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    
+    #[account(
+        mut,
+        close = player,
+        seeds = [
+            b"waitlist_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            waitlist_entry.player.as_ref()
+        ],
+        bump = waitlist_entry.bump
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    
+    /// CHECK: refund destination, must be the waitlisted player
+    #[account(mut, address = waitlist_entry.player)]
+    pub player: UncheckedAccount<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Player's token account for the game mint (token games only)
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -2,16 +2,18 @@
 
 use anchor_lang::prelude::*;
 use crate::state::GameRegistry;
-use crate::constants::REGISTRY_SIZE;
 
 /// Initialize the game registry with an admin address
 /// This should only be called once during program deployment
 pub fn initialize(ctx: Context<Initialize>, admin: Pubkey) -> Result<()> {
     let game_registry = &mut ctx.accounts.game_registry;
     
+    game_registry.version = GameRegistry::VERSION;
     game_registry.game_count = 0;
     game_registry.total_games_created = 0;
     game_registry.admin = admin;
+    game_registry.pending_admin = None;
+    game_registry.pending_admin_expires_at = None;
     
     msg!("Game Registry initialized with admin: {}", admin);
     
@@ -23,7 +25,7 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
-        space = REGISTRY_SIZE,
+        space = GameRegistry::SIZE,
         seeds = [b"game_registry"],
         bump
     )]
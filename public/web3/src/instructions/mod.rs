@@ -8,7 +8,9 @@ pub mod phase3;
 pub mod admin;
 
 // Re-export all instruction contexts for easy access
+// (module names such as `start` overlap between phases; handlers are called by full path)
 pub use initialize::*;
+#[allow(ambiguous_glob_reexports)]
 pub use game_lifecycle::*;
 pub use phase1::*;
 pub use phase2::*;
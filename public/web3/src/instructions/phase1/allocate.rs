@@ -1,7 +1,7 @@
 // Phase 1 resource allocation - players distribute tokens across activities
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, PlayerGameState, GamePoolState, ResourceAllocations};
+use crate::state::{Game, GameRegistry, PlayerGameState, GamePoolState, ResourceAllocations};
 use crate::errors::GameError;
 
 /// Submit or update resource allocations for Phase 1
@@ -16,8 +16,9 @@ pub fn submit_allocations(
 ) -> Result<()> {
     let player_state = &mut ctx.accounts.player_state;
     let pool_state = &mut ctx.accounts.pool_state;
-    let game = &ctx.accounts.game;
+    let game = &ctx.accounts.game.load()?;
     
+    game.require_not_paused(&ctx.accounts.game_registry)?;
     // Only allowed in Phase 1
     require!(game.current_phase == 1, GameError::InvalidPhase);
     
@@ -74,7 +75,10 @@ pub struct SubmitAllocations<'info> {
     #[account(mut)]
     pub pool_state: Account<'info, GamePoolState>,
     
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     pub player: Signer<'info>,
 }
@@ -1,24 +1,20 @@
 // Phase 1 initialization - setting up player and pool states
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, PlayerGameState, GamePoolState, ResourceAllocations};
+use crate::state::{Game, PlayerEntry, PlayerGameState, GamePoolState, ResourceAllocations};
 use crate::errors::GameError;
-use crate::constants::{PLAYER_STATE_SIZE, POOL_STATE_SIZE, INITIAL_BALANCE_MULTIPLIER};
 
 /// Initialize player state when they first join Phase 1
 pub fn initialize_player_state(ctx: Context<InitializePlayerState>) -> Result<()> {
     let player_state = &mut ctx.accounts.player_state;
-    let game = &ctx.accounts.game;
+    let game = &ctx.accounts.game.load()?;
     
-    require!(game.game_started, GameError::GameNotStarted);
-    require!(
-        game.players.contains(&ctx.accounts.player.key()),
-        GameError::NotInGame
-    );
+    require!(game.game_started != 0, GameError::GameNotStarted);
     
+    player_state.version = PlayerGameState::VERSION;
     player_state.player = ctx.accounts.player.key();
     player_state.game_id = game.game_id;
-    player_state.virtual_balance = game.entry_fee * INITIAL_BALANCE_MULTIPLIER;
+    player_state.virtual_balance = game.entry_fee * game.config.initial_balance_multiplier;
     player_state.total_earned = 0;
     player_state.last_claim_time = Clock::get()?.unix_timestamp;
     player_state.has_active_allocation = false;
@@ -36,11 +32,12 @@ pub fn initialize_player_state(ctx: Context<InitializePlayerState>) -> Result<()
 /// Initialize global pool state (usually called once per game)
 pub fn initialize_pool_state(ctx: Context<InitializePoolState>) -> Result<()> {
     let pool_state = &mut ctx.accounts.pool_state;
-    let game = &ctx.accounts.game;
+    let game = &ctx.accounts.game.load()?;
     let clock = Clock::get()?;
     
-    require!(game.game_started, GameError::GameNotStarted);
+    require!(game.game_started != 0, GameError::GameNotStarted);
     
+    pool_state.version = GamePoolState::VERSION;
     pool_state.game_id = game.game_id;
     pool_state.mining_pool_total = 0;
     pool_state.farming_pool_total = 0;
@@ -51,6 +48,7 @@ pub fn initialize_pool_state(ctx: Context<InitializePoolState>) -> Result<()> {
     pool_state.farming_season = 0;
     pool_state.trading_market_state = 1; // Start with normal market
     pool_state.last_event_time = clock.unix_timestamp;
+    pool_state.payer = ctx.accounts.authority.key();
     
     Ok(())
 }
@@ -60,17 +58,28 @@ pub struct InitializePlayerState<'info> {
     #[account(
         init,
         payer = player,
-        space = PLAYER_STATE_SIZE,
+        space = PlayerGameState::size(game.load()?.config.max_tracked_opponents),
         seeds = [
             b"player_state",
-            game.game_id.to_le_bytes().as_ref(),
+            game.load()?.game_id.to_le_bytes().as_ref(),
             player.key().as_ref()
         ],
         bump
     )]
     pub player_state: Account<'info, PlayerGameState>,
     
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    /// Proves the player joined this game
+    #[account(
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_entry.bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
     
     #[account(mut)]
     pub player: Signer<'info>,
@@ -83,13 +92,13 @@ pub struct InitializePoolState<'info> {
     #[account(
         init,
         payer = authority,
-        space = POOL_STATE_SIZE,
-        seeds = [b"pool_state", game.game_id.to_le_bytes().as_ref()],
+        space = GamePoolState::SIZE,
+        seeds = [b"pool_state", game.load()?.game_id.to_le_bytes().as_ref()],
         bump
     )]
     pub pool_state: Account<'info, GamePoolState>,
     
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
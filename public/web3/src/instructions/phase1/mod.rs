@@ -3,7 +3,9 @@
 pub mod initialize;
 pub mod allocate;
 pub mod rewards;
+pub mod share;
 
 pub use initialize::*;
 pub use allocate::*;
 pub use rewards::*;
+pub use share::*;
@@ -1,23 +1,19 @@
 // Phase 1 reward claiming - calculate and distribute earnings from resource allocations
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, PlayerGameState, GamePoolState};
+use crate::state::{Game, GameRegistry, PlayerGameState, GamePoolState};
 use crate::events::Phase2PenaltyApplied;
 use crate::errors::GameError;
 use crate::utils::{get_effective_claim_time, seconds_to_hours};
-use crate::constants::{
-    MINING_BASE_RATE,
-    FARMING_BASE_RATE,
-    SOCIAL_BASE_RATE,
-};
 
 /// Claim accumulated rewards from Phase 1 activities
 pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     let player_state = &mut ctx.accounts.player_state;
     let pool_state = &ctx.accounts.pool_state;
-    let game = &ctx.accounts.game;
+    let game = &ctx.accounts.game.load()?;
     let clock = Clock::get()?;
     
+    game.require_not_paused(&ctx.accounts.game_registry)?;
     // Only works in Phase 1
     require!(game.current_phase == 1, GameError::InvalidPhase);
     
@@ -28,7 +24,8 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     
     // Calculate time elapsed since last claim
     let current_time = clock.unix_timestamp;
-    let phase_end = game.phase_end_time;
+    // Count global pause time even before the game's deadlines catch up
+    let phase_end = game.phase_end_time + game.pending_global_pause(&ctx.accounts.game_registry);
     let effective_time = get_effective_claim_time(current_time, phase_end);
     let time_elapsed = effective_time - player_state.last_claim_time;
     
@@ -41,8 +38,8 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     
     // Mining rewards - slows with competition
     if player_state.allocations.mining > 0 {
-        let difficulty_factor = pool_state.get_mining_difficulty_factor();
-        let mining_rate = MINING_BASE_RATE * difficulty_factor;
+        let difficulty_factor = pool_state.get_mining_difficulty_factor(game.config.max_mining_difficulty);
+        let mining_rate = game.config.mining_base_rate * difficulty_factor;
         total_rewards += (player_state.allocations.mining as f64 
             * mining_rate 
             * hours_elapsed) as u64;
@@ -51,7 +48,7 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     // Farming rewards - seasonal multiplier
     if player_state.allocations.farming > 0 {
         let season_multiplier = pool_state.get_farming_multiplier();
-        let farming_rate = FARMING_BASE_RATE * season_multiplier;
+        let farming_rate = game.config.farming_base_rate * season_multiplier;
         total_rewards += (player_state.allocations.farming as f64 
             * farming_rate 
             * hours_elapsed) as u64;
@@ -73,8 +70,8 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     
     // Social rewards - collaboration bonus
     if player_state.allocations.social > 0 {
-        let collaboration_multiplier = pool_state.get_social_multiplier();
-        let social_rate = SOCIAL_BASE_RATE * collaboration_multiplier;
+        let collaboration_multiplier = pool_state.get_social_multiplier(game.config.max_social_bonus);
+        let social_rate = game.config.social_base_rate * collaboration_multiplier;
         total_rewards += (player_state.allocations.social as f64 
             * social_rate 
             * hours_elapsed) as u64;
@@ -91,8 +88,9 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
 /// Claim end-of-phase rewards and apply penalties
 pub fn claim_phase_end_rewards(ctx: Context<ClaimPhaseEndRewards>) -> Result<()> {
     let player_state = &mut ctx.accounts.player_state;
-    let game = &ctx.accounts.game;
+    let game = &ctx.accounts.game.load()?;
     
+    game.require_not_paused(&ctx.accounts.game_registry)?;
     // Must be past Phase 1
     require!(game.current_phase > 1, GameError::PhaseNotEnded);
     
@@ -130,7 +128,10 @@ pub struct ClaimRewards<'info> {
     
     pub pool_state: Account<'info, GamePoolState>,
     
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     pub player: Signer<'info>,
 }
@@ -142,7 +143,10 @@ pub struct ClaimPhaseEndRewards<'info> {
     
     pub pool_state: Account<'info, GamePoolState>,
     
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     pub player: Signer<'info>,
 }
@@ -0,0 +1,93 @@
+// Phase 1 squad sharing - squadmates pooling balance for their allocations
+
+use anchor_lang::prelude::*;
+use crate::state::{Game, GameRegistry, PlayerGameState, SquadMember};
+use crate::events::SquadBalanceShared;
+use crate::errors::GameError;
+
+/// Hand part of your virtual balance to a squadmate to allocate
+/// Only unallocated balance can move; the receiver resubmits their
+/// allocations to put it to work
+pub fn share_squad_balance(ctx: Context<ShareSquadBalance>, amount: u64) -> Result<()> {
+    let game = &ctx.accounts.game.load()?;
+    let from_state = &mut ctx.accounts.from_state;
+    let to_state = &mut ctx.accounts.to_state;
+    
+    game.require_not_paused(&ctx.accounts.game_registry)?;
+    require!(game.is_squad_game(), GameError::NotSquadGame);
+    require!(game.current_phase == 1, GameError::InvalidPhase);
+    require!(
+        ctx.accounts.from_member.squad == ctx.accounts.to_member.squad,
+        GameError::NotSquadMember
+    );
+    require!(!from_state.has_active_allocation, GameError::AllocationActive);
+    require!(
+        amount > 0 && from_state.virtual_balance >= amount,
+        GameError::InsufficientBalance
+    );
+    
+    from_state.virtual_balance -= amount;
+    to_state.virtual_balance += amount;
+    
+    emit!(SquadBalanceShared {
+        game_id: game.game_id,
+        squad: ctx.accounts.from_member.squad,
+        from: from_state.player,
+        to: to_state.player,
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ShareSquadBalance<'info> {
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"player_state",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub from_state: Account<'info, PlayerGameState>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"player_state",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            to_member.player.as_ref()
+        ],
+        bump
+    )]
+    pub to_state: Account<'info, PlayerGameState>,
+    
+    #[account(
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = from_member.bump
+    )]
+    pub from_member: Account<'info, SquadMember>,
+    
+    #[account(
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            to_member.player.as_ref()
+        ],
+        bump = to_member.bump
+    )]
+    pub to_member: Account<'info, SquadMember>,
+    
+    pub player: Signer<'info>,
+}
@@ -1,17 +1,17 @@
 // Phase advancement - moving from one phase to the next
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, GameStatus};
+use crate::state::{Game, GameRegistry};
 use crate::events::PhaseAdvanced;
 use crate::errors::GameError;
-use crate::constants::PHASE_ADVANCE_BUFFER;
 
 /// Advance to the next phase (Phase 1 → 2, or Phase 2 → 3)
 pub fn advance_phase(ctx: Context<AdvancePhase>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &mut ctx.accounts.game.load_mut()?;
     let clock = Clock::get()?;
     
-    require!(game.game_started, GameError::GameNotStarted);
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require!(game.game_started != 0, GameError::GameNotStarted);
     require!(
         clock.unix_timestamp >= game.phase_end_time,
         GameError::PhaseNotEnded
@@ -37,7 +37,7 @@ pub fn advance_phase(ctx: Context<AdvancePhase>) -> Result<()> {
     };
     
     game.phase_end_time = clock.unix_timestamp + phase_duration;
-    game.phase_advance_deadline = game.phase_end_time + PHASE_ADVANCE_BUFFER;
+    game.phase_advance_deadline = game.phase_end_time + game.config.phase_advance_buffer;
     
     emit!(PhaseAdvanced {
         game_id: game.game_id,
@@ -51,7 +51,10 @@ pub fn advance_phase(ctx: Context<AdvancePhase>) -> Result<()> {
 #[derive(Accounts)]
 pub struct AdvancePhase<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     pub caller: Signer<'info>,
 }
@@ -1,10 +1,9 @@
 // Phase 2 challenge system - creating and responding to PvP challenges
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, PlayerGameState, Challenge, ChallengeStatus, MiniGameType};
+use crate::state::{Game, GameRegistry, PlayerEntry, PlayerGameState, Challenge, ChallengeStatus, MiniGameType, SquadMember};
 use crate::events::ChallengeCreated;
 use crate::errors::GameError;
-use crate::constants::{CHALLENGE_SIZE, MAX_OPPONENT_DECLINES};
 
 /// Create a challenge to another player
 pub fn create_challenge(
@@ -15,38 +14,57 @@ pub fn create_challenge(
     game_type: MiniGameType,
 ) -> Result<()> {
     let challenge = &mut ctx.accounts.challenge;
-    let game = &ctx.accounts.game;
+    let game = &ctx.accounts.game.load()?;
     let player_state = &ctx.accounts.player_state;
     
     // Validate phase and game state
+    game.require_not_paused(&ctx.accounts.game_registry)?;
     require!(game.current_phase == 2, GameError::InvalidPhase);
     require!(
         player_state.virtual_balance >= bet_amount,
         GameError::InsufficientBalance
     );
     require!(
-        game.players.contains(&opponent),
+        bet_amount <= game.max_bet(player_state.virtual_balance),
+        GameError::BetTooLarge
+    );
+    require!(game.allows_mini_game(&game_type), GameError::MiniGameNotAllowed);
+    require!(
+        ctx.accounts.opponent_entry.player == opponent,
         GameError::OpponentNotInGame
     );
     require!(
         opponent != ctx.accounts.challenger.key(),
         GameError::CannotChallengeSelf
     );
+    // Squad games are squad vs squad
+    let mut squads = (None, None);
+    if game.is_squad_game() {
+        let (Some(challenger_member), Some(opponent_member)) =
+            (&ctx.accounts.challenger_squad_member, &ctx.accounts.opponent_squad_member)
+        else {
+            return err!(GameError::SquadGame);
+        };
+        require!(challenger_member.squad != opponent_member.squad, GameError::SameSquad);
+        squads = (Some(challenger_member.squad), Some(opponent_member.squad));
+    }
     
-    // Check opponent limit
+    // Check opponent limit - squad games count games against the other squad
     require!(
         player_state.can_challenge_opponent(
-            &opponent,
+            &squads.1.unwrap_or(opponent),
             game.phase2_max_games_per_opponent
         ),
         GameError::MaxGamesPerOpponentReached
     );
     
     // Initialize challenge
+    challenge.version = Challenge::VERSION;
     challenge.challenge_id = timestamp as u64;
     challenge.game_id = game.game_id;
     challenge.challenger = ctx.accounts.challenger.key();
     challenge.opponent = opponent;
+    (challenge.challenger_squad, challenge.opponent_squad) = squads;
     challenge.bet_amount = bet_amount;
     challenge.game_type = game_type;
     challenge.status = ChallengeStatus::Pending;
@@ -61,6 +79,7 @@ pub fn create_challenge(
         game_id: game.game_id,
         challenger: challenge.challenger,
         opponent: challenge.opponent,
+        opponent_squad: challenge.opponent_squad,
         bet_amount: challenge.bet_amount,
     });
     
@@ -68,19 +87,29 @@ pub fn create_challenge(
 }
 
 /// Respond to a challenge (accept or decline)
+/// A squad challenge is answered by any member of the challenged squad, and
+/// whoever accepts plays it.
 pub fn respond_challenge(ctx: Context<RespondChallenge>, accept: bool) -> Result<()> {
     let challenge = &mut ctx.accounts.challenge;
     let opponent_state = &ctx.accounts.opponent_state;
+    let max_declines = ctx.accounts.game.load()?.config.max_opponent_declines;
     let clock = Clock::get()?;
     
+    ctx.accounts.game.load()?.require_not_paused(&ctx.accounts.game_registry)?;
     require!(
         challenge.status == ChallengeStatus::Pending,
         GameError::InvalidChallengeStatus
     );
-    require!(
-        challenge.opponent == ctx.accounts.opponent.key(),
-        GameError::NotChallengeOpponent
-    );
+    match challenge.opponent_squad {
+        Some(squad) => require!(
+            ctx.accounts.opponent_squad_member.as_ref().is_some_and(|member| member.squad == squad),
+            GameError::NotChallengeOpponent
+        ),
+        None => require!(
+            challenge.opponent == ctx.accounts.opponent.key(),
+            GameError::NotChallengeOpponent
+        ),
+    }
     
     if accept {
         // Check opponent has enough balance
@@ -89,13 +118,14 @@ pub fn respond_challenge(ctx: Context<RespondChallenge>, accept: bool) -> Result
             GameError::InsufficientBalance
         );
         
+        challenge.opponent = ctx.accounts.opponent.key();
         challenge.status = ChallengeStatus::Accepted;
         challenge.accepted_at = Some(clock.unix_timestamp);
     } else {
         // Track declines - after 5, challenge is forced
         challenge.opponent_decline_count += 1;
         
-        if challenge.opponent_decline_count >= MAX_OPPONENT_DECLINES {
+        if challenge.opponent_decline_count >= max_declines {
             challenge.status = ChallengeStatus::ForcedAccept;
         }
     }
@@ -109,10 +139,10 @@ pub struct CreateChallenge<'info> {
     #[account(
         init,
         payer = challenger,
-        space = CHALLENGE_SIZE,
+        space = Challenge::SIZE,
         seeds = [
             b"challenge",
-            game.game_id.to_le_bytes().as_ref(),
+            game.load()?.game_id.to_le_bytes().as_ref(),
             challenger.key().as_ref(),
             opponent_key.as_ref(),
             timestamp.to_le_bytes().as_ref()
@@ -121,16 +151,52 @@ pub struct CreateChallenge<'info> {
     )]
     pub challenge: Account<'info, Challenge>,
     
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     pub player_state: Account<'info, PlayerGameState>,
     
+    /// Proves the opponent joined this game
+    #[account(
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            opponent_key.as_ref()
+        ],
+        bump = opponent_entry.bump
+    )]
+    pub opponent_entry: Account<'info, PlayerEntry>,
+    
     #[account(mut)]
     pub challenger: Signer<'info>,
     
     /// CHECK: Validated in instruction
     pub opponent: UncheckedAccount<'info>,
     
+    /// Challenger's squad membership (squad games only)
+    #[account(
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            challenger.key().as_ref()
+        ],
+        bump = challenger_squad_member.bump
+    )]
+    pub challenger_squad_member: Option<Account<'info, SquadMember>>,
+    
+    /// Opponent's squad membership (squad games only)
+    #[account(
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            opponent_key.as_ref()
+        ],
+        bump = opponent_squad_member.bump
+    )]
+    pub opponent_squad_member: Option<Account<'info, SquadMember>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -139,9 +205,25 @@ pub struct RespondChallenge<'info> {
     #[account(mut)]
     pub challenge: Account<'info, Challenge>,
     
-    pub game: Account<'info, Game>,
+    #[account(seeds = [b"game", challenge.game_id.to_le_bytes().as_ref()], bump)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     pub opponent_state: Account<'info, PlayerGameState>,
     
+    /// Challenged player, or any member of the challenged squad
     pub opponent: Signer<'info>,
+    
+    /// Responder's squad membership (squad challenges only)
+    #[account(
+        seeds = [
+            b"squad_member",
+            challenge.game_id.to_le_bytes().as_ref(),
+            opponent.key().as_ref()
+        ],
+        bump = opponent_squad_member.bump
+    )]
+    pub opponent_squad_member: Option<Account<'info, SquadMember>>,
 }
@@ -1,7 +1,7 @@
 // Phase 2 mini-game execution - playing and resolving challenges
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, PlayerGameState, Challenge, ChallengeStatus};
+use crate::state::{Game, GameRegistry, PlayerGameState, Challenge, ChallengeStatus};
 use crate::events::MiniGameCompleted;
 use crate::errors::GameError;
 
@@ -49,6 +49,8 @@ pub fn claim_mini_game_win(ctx: Context<ClaimMiniGameWin>, winner: Pubkey) -> Re
     let winner_state = &mut ctx.accounts.winner_state;
     let loser_state = &mut ctx.accounts.loser_state;
     
+    let game = ctx.accounts.game.load()?;
+    game.require_not_paused(&ctx.accounts.game_registry)?;
     require!(
         challenge.status == ChallengeStatus::InProgress,
         GameError::InvalidChallengeStatus
@@ -76,9 +78,16 @@ pub fn claim_mini_game_win(ctx: Context<ClaimMiniGameWin>, winner: Pubkey) -> Re
     loser_state.virtual_balance -= bet_amount;
     winner_state.virtual_balance += bet_amount;
     
-    // Update game statistics
-    winner_state.record_game_played(&loser, true)?;
-    loser_state.record_game_played(&winner, false)?;
+    // Update game statistics - squad games track the opposing squad
+    let (winner_rival, loser_rival) = match (challenge.challenger_squad, challenge.opponent_squad) {
+        (Some(challenger_squad), Some(opponent_squad)) if winner == challenge.challenger => {
+            (opponent_squad, challenger_squad)
+        }
+        (Some(challenger_squad), Some(opponent_squad)) => (challenger_squad, opponent_squad),
+        _ => (loser, winner),
+    };
+    winner_state.record_game_played(&winner_rival, true, game.config.max_tracked_opponents)?;
+    loser_state.record_game_played(&loser_rival, false, game.config.max_tracked_opponents)?;
     
     // Mark challenge complete
     challenge.status = ChallengeStatus::Completed;
@@ -115,7 +124,10 @@ pub struct ClaimMiniGameWin<'info> {
     #[account(mut)]
     pub challenge: Account<'info, Challenge>,
     
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     #[account(mut)]
     pub winner_state: Account<'info, PlayerGameState>,
@@ -1,46 +1,54 @@
 // Phase 3 prize claiming - winner collects the prize pool
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, GameStatus};
-use crate::events::Phase3PrizeClaimed;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{require_role, Game, GameFeeRecord, GameRegistry, GameStatus, Role, RoleRegistry, Treasury};
+use crate::events::{CreatorFeeClaimed, Phase3PrizeClaimed, PlatformFeeCollected};
 use crate::errors::GameError;
+use crate::utils::{check_vault_balance, game_vault, pay_from_game, payout_recipient};
 
 /// Winner claims the prize pool
 pub fn claim_phase3_prize(ctx: Context<ClaimPhase3Prize>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
     let winner = &ctx.accounts.winner;
     
     // Validate game state
+    game.require_not_paused(&ctx.accounts.game_registry)?;
     require!(game.current_phase == 3, GameError::InvalidPhase);
     require!(
-        game.status == GameStatus::Completed,
+        game.has_status(GameStatus::Completed),
         GameError::GameNotCompleted
     );
-    require!(game.phase3_winner.is_some(), GameError::NoWinnerDeclared);
-    require!(!game.phase3_prize_claimed, GameError::AlreadyClaimed);
+    require!(game.winner().is_some(), GameError::NoWinnerDeclared);
+    require!(!game.is_squad_game(), GameError::SquadGame);
+    require!(game.phase3_prize_claimed == 0, GameError::AlreadyClaimed);
     
     // Verify this is the winner
-    let winner_pubkey = game.phase3_winner.unwrap();
+    let winner_pubkey = game.phase3_winner;
     require!(winner_pubkey == winner.key(), GameError::NotWinner);
     
-    // Calculate prize after platform fee
-    require!(
-        game.prize_pool > game.platform_fee_collected,
-        GameError::NoPrizeToCollect
-    );
-    
-    let prize_amount = game.prize_pool
-        .checked_sub(game.platform_fee_collected)
-        .ok_or(GameError::NoPrizeToCollect)?;
-    
+    // Platform fee was moved out of the prize pool when the winner was declared
+    let prize_amount = game.prize_pool;
     require!(prize_amount > 0, GameError::NoPrizeToCollect);
     
     // Transfer prize to winner
-    **game.to_account_info().try_borrow_mut_lamports()? -= prize_amount;
-    **winner.to_account_info().try_borrow_mut_lamports()? += prize_amount;
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(&game, &vault, &winner.to_account_info(), &ctx.accounts.winner_token_account)?;
+    pay_from_game(&game, &vault, &recipient, prize_amount)?;
     
-    game.phase3_prize_claimed = true;
+    game.phase3_prize_claimed = 1;
     game.prize_pool = 0;
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
     
     emit!(Phase3PrizeClaimed {
         game_id: game.game_id,
@@ -51,24 +59,108 @@ pub fn claim_phase3_prize(ctx: Context<ClaimPhase3Prize>) -> Result<()> {
     Ok(())
 }
 
-/// Admin/creator collects the platform fee
+/// Treasurer sweeps a game's platform fee into the program treasury
 pub fn claim_platform_fee(ctx: Context<ClaimPlatformFee>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
     
-    // Only creator can collect (in production, use game_registry.admin)
-    require!(
-        ctx.accounts.admin.key() == game.creator,
-        GameError::Unauthorized
-    );
+    game.require_not_paused(&ctx.accounts.game_registry)?;
+    // Only a treasurer can collect
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.treasurer.key(),
+        Role::Treasurer,
+    )?;
     
     let fee_amount = game.platform_fee_collected;
     require!(fee_amount > 0, GameError::NoFeeToCollect);
     
-    // Transfer fee to admin
-    **game.to_account_info().try_borrow_mut_lamports()? -= fee_amount;
-    **ctx.accounts.admin.to_account_info().try_borrow_mut_lamports()? += fee_amount;
+    // Transfer fee to the treasury (its token account for token games)
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(
+        &game,
+        &vault,
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.treasury_token_account,
+    )?;
+    pay_from_game(&game, &vault, &recipient, fee_amount)?;
     
     game.platform_fee_collected = 0;
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    let treasury = &mut ctx.accounts.treasury;
+    if treasury.version == 0 {
+        treasury.version = Treasury::VERSION;
+        treasury.bump = ctx.bumps.treasury;
+    }
+    if !game.is_token_game() {
+        treasury.total_sol_collected += fee_amount;
+    }
+    
+    let fee_record = &mut ctx.accounts.fee_record;
+    if fee_record.version == 0 {
+        fee_record.version = GameFeeRecord::VERSION;
+        fee_record.game_id = game.game_id;
+        fee_record.mint = game.mint;
+        fee_record.bump = ctx.bumps.fee_record;
+    }
+    fee_record.total_collected += fee_amount;
+    fee_record.last_collected_at = Clock::get()?.unix_timestamp;
+    
+    emit!(PlatformFeeCollected {
+        game_id: game.game_id,
+        mint: game.stake_mint(),
+        amount: fee_amount,
+        game_total: fee_record.total_collected,
+    });
+    
+    Ok(())
+}
+
+/// Creator claims their share of the platform fee
+pub fn claim_creator_fee(ctx: Context<ClaimCreatorFee>) -> Result<()> {
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
+    let creator = &ctx.accounts.creator;
+    
+    game.require_not_paused(&ctx.accounts.game_registry)?;
+    require!(game.creator == creator.key(), GameError::NotCreator);
+    
+    let fee_amount = game.creator_fee_collected;
+    require!(fee_amount > 0, GameError::NoFeeToCollect);
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(&game, &vault, &creator.to_account_info(), &ctx.accounts.creator_token_account)?;
+    pay_from_game(&game, &vault, &recipient, fee_amount)?;
+    
+    game.creator_fee_collected = 0;
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    emit!(CreatorFeeClaimed {
+        game_id: game.game_id,
+        creator: creator.key(),
+        mint: game.stake_mint(),
+        amount: fee_amount,
+    });
     
     Ok(())
 }
@@ -76,17 +168,114 @@ pub fn claim_platform_fee(ctx: Context<ClaimPlatformFee>) -> Result<()> {
 #[derive(Accounts)]
 pub struct ClaimPhase3Prize<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     #[account(mut)]
     pub winner: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Winner's token account for the game mint (token games only)
+    #[account(mut)]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct ClaimPlatformFee<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    #[account(
+        init_if_needed,
+        payer = treasurer,
+        space = Treasury::SIZE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = treasurer,
+        space = GameFeeRecord::SIZE,
+        seeds = [b"fee_record", game.load()?.game_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_record: Account<'info, GameFeeRecord>,
     
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub treasurer: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Treasury's token account for the game mint (token games only)
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFee<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Creator's token account for the game mint (token games only)
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
 }
@@ -3,7 +3,9 @@
 pub mod ready;
 pub mod start;
 pub mod claim;
+pub mod squad;
 
 pub use ready::*;
 pub use start::*;
 pub use claim::*;
+pub use squad::*;
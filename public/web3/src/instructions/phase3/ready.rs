@@ -1,18 +1,19 @@
 // Phase 3 ready system - players must opt-in to the final purge
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, Phase3ReadyState};
+use crate::state::{Game, GameRegistry, PlayerEntry, Phase3ReadyState, Squad, SquadMember};
 use crate::errors::GameError;
 
 /// Mark yourself as ready for the final purge
 /// Players must actively participate - no automatic enrollment
 pub fn mark_ready_phase3(ctx: Context<MarkReadyPhase3>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &mut ctx.accounts.game.load_mut()?;
     let ready_state = &mut ctx.accounts.ready_state;
     let clock = Clock::get()?;
     
+    game.sync_pause(&ctx.accounts.game_registry)?;
     require!(game.current_phase == 3, GameError::InvalidPhase);
-    require!(!game.phase3_started, GameError::Phase3AlreadyStarted);
+    require!(game.phase3_started == 0, GameError::Phase3AlreadyStarted);
     
     // Check deadline
     let deadline = if game.phase3_extended_deadline > 0 {
@@ -25,20 +26,28 @@ pub fn mark_ready_phase3(ctx: Context<MarkReadyPhase3>) -> Result<()> {
         clock.unix_timestamp <= deadline,
         GameError::ReadyDeadlineExpired
     );
-    require!(
-        game.players.contains(&ctx.accounts.player.key()),
-        GameError::NotInGame
-    );
     
     // Mark as ready (only count once)
     if !ready_state.ready {
+        ready_state.version = Phase3ReadyState::VERSION;
         ready_state.game_id = game.game_id;
         ready_state.player = ctx.accounts.player.key();
         ready_state.ready = true;
         ready_state.marked_ready_at = clock.unix_timestamp;
         ready_state.bump = ctx.bumps.ready_state;
         
-        game.phase3_players_ready += 1;
+        // Squad games count squads - the first member to mark ready enters the squad
+        if game.is_squad_game() {
+            let squad = ctx.accounts.squad.as_mut().ok_or(GameError::SquadGame)?;
+            let member = ctx.accounts.squad_member.as_ref().ok_or(GameError::SquadGame)?;
+            require!(member.squad == squad.key(), GameError::NotSquadMember);
+            if !squad.phase3_ready {
+                squad.phase3_ready = true;
+                game.phase3_players_ready += 1;
+            }
+        } else {
+            game.phase3_players_ready += 1;
+        }
     }
     
     Ok(())
@@ -47,7 +56,10 @@ pub fn mark_ready_phase3(ctx: Context<MarkReadyPhase3>) -> Result<()> {
 #[derive(Accounts)]
 pub struct MarkReadyPhase3<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     #[account(
         init_if_needed,
@@ -55,13 +67,39 @@ pub struct MarkReadyPhase3<'info> {
         space = Phase3ReadyState::SIZE,
         seeds = [
             b"phase3_ready",
-            game.game_id.to_le_bytes().as_ref(),
+            game.load()?.game_id.to_le_bytes().as_ref(),
             player.key().as_ref()
         ],
         bump
     )]
     pub ready_state: Account<'info, Phase3ReadyState>,
     
+    /// Proves the player joined this game
+    #[account(
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_entry.bump
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    /// Player's squad membership (squad games only)
+    #[account(
+        seeds = [
+            b"squad_member",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = squad_member.bump
+    )]
+    pub squad_member: Option<Account<'info, SquadMember>>,
+    
+    /// Player's squad (squad games only)
+    #[account(mut)]
+    pub squad: Option<Account<'info, Squad>>,
+    
     #[account(mut)]
     pub player: Signer<'info>,
     
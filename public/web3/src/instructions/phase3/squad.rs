@@ -0,0 +1,169 @@
+// Phase 3 for squad games - declaring a winning squad and splitting its prize
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{require_role, Game, GameRegistry, GameStatus, Role, RoleRegistry, Squad};
+use crate::events::{Phase3WinnerDeclared, SquadPrizeClaimed};
+use crate::errors::GameError;
+use crate::utils::{check_vault_balance, game_vault, pay_from_game, payout_recipient};
+use super::start::settle_fees;
+
+/// Referee submits the squad that won the purge
+pub fn submit_phase3_squad_winner(ctx: Context<SubmitPhase3SquadWinner>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let squad = ctx.accounts.squad.key();
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.submitter.key(),
+        Role::Referee,
+    )?;
+    require!(game.is_squad_game(), GameError::NotSquadGame);
+    require!(game.current_phase == 3, GameError::InvalidPhase);
+    require!(game.phase3_started != 0, GameError::Phase3NotStarted);
+    require!(game.winner().is_none(), GameError::WinnerAlreadyDeclared);
+    
+    game.phase3_winner = squad;
+    game.finish(GameStatus::Completed, Clock::get()?.unix_timestamp);
+    
+    settle_fees(game);
+    
+    emit!(Phase3WinnerDeclared {
+        game_id: game.game_id,
+        winner: squad,
+        prize_amount: game.prize_pool,
+    });
+    
+    Ok(())
+}
+
+/// Member of the winning squad claims their cut of the prize
+/// The last member to claim also takes the rounding dust
+pub fn claim_squad_prize(ctx: Context<ClaimSquadPrize>) -> Result<()> {
+    // Work on a copy - the game PDA signs token payouts, so it must not stay borrowed
+    let mut game = *ctx.accounts.game.load()?;
+    let squad = &mut ctx.accounts.squad;
+    let member = &ctx.accounts.member;
+    
+    game.require_not_paused(&ctx.accounts.game_registry)?;
+    require!(game.current_phase == 3, GameError::InvalidPhase);
+    require!(
+        game.has_status(GameStatus::Completed),
+        GameError::GameNotCompleted
+    );
+    require!(game.winner() == Some(squad.key()), GameError::NotWinner);
+    
+    let index = squad.member_index(&member.key()).ok_or(GameError::NotSquadMember)?;
+    require!(squad.claimed_mask & (1 << index) == 0, GameError::AlreadyClaimed);
+    
+    // Platform fee was moved out of the prize pool when the winner was declared
+    if squad.prize_total == 0 {
+        squad.prize_total = game.prize_pool;
+    }
+    require!(squad.prize_total > 0, GameError::NoPrizeToCollect);
+    
+    squad.claimed_mask |= 1 << index;
+    let amount = if squad.all_claimed() {
+        game.prize_pool
+    } else {
+        squad.member_prize(index, squad.prize_total)
+    };
+    
+    let vault = game_vault(
+        &ctx.accounts.game,
+        &game,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_program,
+    )?;
+    let recipient = payout_recipient(&game, &vault, &member.to_account_info(), &ctx.accounts.member_token_account)?;
+    pay_from_game(&game, &vault, &recipient, amount)?;
+    
+    game.prize_pool -= amount;
+    if squad.all_claimed() {
+        game.phase3_prize_claimed = 1;
+    }
+    check_vault_balance(&game, &vault)?;
+    *ctx.accounts.game.load_mut()? = game;
+    
+    emit!(SquadPrizeClaimed {
+        game_id: game.game_id,
+        squad: squad.key(),
+        member: member.key(),
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitPhase3SquadWinner<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    /// The winning squad, which must belong to this game
+    #[account(
+        seeds = [
+            b"squad",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            squad.leader.as_ref()
+        ],
+        bump = squad.bump
+    )]
+    pub squad: Account<'info, Squad>,
+    
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSquadPrize<'info> {
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"squad",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            squad.leader.as_ref()
+        ],
+        bump = squad.bump
+    )]
+    pub squad: Account<'info, Squad>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    /// Vault PDA holding SOL stakes
+    #[account(mut, seeds = [b"vault", game.load()?.game_id.to_le_bytes().as_ref()], bump = game.load()?.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// Game stake mint (token games only)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Game token vault (token games only)
+    #[account(mut)]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Member's token account for the game mint (token games only)
+    #[account(mut)]
+    pub member_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
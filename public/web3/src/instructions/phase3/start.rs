@@ -1,19 +1,19 @@
 // Phase 3 purge start - initiating the final game and declaring winner
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, GameStatus, PlayerGameState, Phase3ReadyState};
-use crate::events::Phase3WinnerDeclared;
+use crate::state::{require_role, Game, GameRegistry, GameStatus, PlayerEntry, Role, RoleRegistry};
+use crate::events::{CreatorFeeAccrued, Phase3WinnerDeclared};
 use crate::errors::GameError;
-use crate::constants::{PHASE3_READY_WINDOW, PHASE3_EXTENDED_WINDOW, PLATFORM_FEE_PERCENTAGE};
-use crate::utils::{count_eligible_players_for_phase3, find_ready_player};
+use crate::utils::{count_eligible_players_for_phase3, find_ready_player, find_ready_squad};
 
 /// Advance from Phase 2 to Phase 3
 pub fn advance_to_phase3(ctx: Context<AdvanceToPhase3>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &mut ctx.accounts.game.load_mut()?;
     let clock = Clock::get()?;
     
+    game.sync_pause(&ctx.accounts.game_registry)?;
     require!(game.current_phase == 2, GameError::InvalidPhase);
-    require!(game.game_started, GameError::GameNotStarted);
+    require!(game.game_started != 0, GameError::GameNotStarted);
     
     // Check time requirements
     let time_since_phase_start = clock.unix_timestamp - game.phase_start_time;
@@ -33,11 +33,11 @@ pub fn advance_to_phase3(ctx: Context<AdvanceToPhase3>) -> Result<()> {
     game.current_phase = 3;
     game.phase_start_time = clock.unix_timestamp;
     game.phase_end_time = clock.unix_timestamp + game.phases.phase3_duration as i64;
-    game.phase3_ready_deadline = clock.unix_timestamp + PHASE3_READY_WINDOW;
+    game.phase3_ready_deadline = clock.unix_timestamp + game.config.phase3_ready_window;
     game.phase3_extended_deadline = 0;
     game.phase3_players_ready = 0;
-    game.phase3_started = false;
-    game.phase3_winner = None;
+    game.phase3_started = 0;
+    game.phase3_winner = Pubkey::default();
     
     emit!(crate::events::PhaseAdvanced {
         game_id: game.game_id,
@@ -52,11 +52,12 @@ pub fn advance_to_phase3(ctx: Context<AdvanceToPhase3>) -> Result<()> {
 pub fn start_phase3_game<'info>(
     ctx: Context<'_, '_, 'info, 'info, StartPhase3Game<'info>>
 ) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &mut ctx.accounts.game.load_mut()?;
     let clock = Clock::get()?;
     
     require!(game.current_phase == 3, GameError::InvalidPhase);
-    require!(!game.phase3_started, GameError::Phase3AlreadyStarted);
+    require!(game.phase3_started == 0, GameError::Phase3AlreadyStarted);
+    game.sync_pause(&ctx.accounts.game_registry)?;
     
     // Count eligible players who met Phase 2 requirements
     let eligible_count = count_eligible_players_for_phase3(
@@ -64,8 +65,12 @@ pub fn start_phase3_game<'info>(
         game
     )?;
     
-    let all_eligible_ready = eligible_count > 0 
-        && game.phase3_players_ready == eligible_count;
+    // Squad games count ready squads, so everyone is ready once every squad is
+    let all_eligible_ready = if game.is_squad_game() {
+        game.phase3_players_ready == game.squad_count
+    } else {
+        eligible_count > 0 && game.phase3_players_ready == eligible_count
+    };
     
     // Check if we can start
     let deadline = if game.phase3_extended_deadline > 0 {
@@ -84,62 +89,88 @@ pub fn start_phase3_game<'info>(
     // No players ready - extend deadline or end game
     if ready_count == 0 {
         if game.phase3_extended_deadline == 0 {
-            game.phase3_extended_deadline = clock.unix_timestamp + PHASE3_EXTENDED_WINDOW;
+            game.phase3_extended_deadline = clock.unix_timestamp + game.config.phase3_extended_window;
             return Ok(());
         } else {
             // Game ends with no winner
-            game.phase3_winner = None;
-            game.status = GameStatus::Completed;
+            game.phase3_winner = Pubkey::default();
+            game.finish(GameStatus::Completed, clock.unix_timestamp);
             return Ok(());
         }
     }
     
-    // One player ready - they win by default!
+    // One player (or squad) ready - they win by default!
     if ready_count == 1 {
-        let ready_player = find_ready_player(ctx.remaining_accounts, game)?;
-        game.phase3_winner = Some(ready_player);
-        game.status = GameStatus::Completed;
+        let ready_player = if game.is_squad_game() {
+            find_ready_squad(ctx.remaining_accounts, game)?
+        } else {
+            find_ready_player(ctx.remaining_accounts, game)?
+        };
+        game.phase3_winner = ready_player;
+        game.finish(GameStatus::Completed, clock.unix_timestamp);
         
-        let platform_fee = game.prize_pool / 100;
-        game.platform_fee_collected += platform_fee;
+        settle_fees(game);
         
         return Ok(());
     }
     
     // Multiple players ready - start the purge!
-    game.phase3_started = true;
+    game.phase3_started = 1;
     
     Ok(())
 }
 
-/// Submit the winner of the purge
+/// Referee submits the winner of the purge
 pub fn submit_phase3_winner(ctx: Context<SubmitPhase3Winner>, winner: Pubkey) -> Result<()> {
-    let game = &mut ctx.accounts.game;
-    
+    let game = &mut ctx.accounts.game.load_mut()?;
+    
+    game.sync_pause(&ctx.accounts.game_registry)?;
+    require_role(
+        &ctx.accounts.game_registry,
+        ctx.accounts.roles.as_deref(),
+        &ctx.accounts.submitter.key(),
+        Role::Referee,
+    )?;
     require!(game.current_phase == 3, GameError::InvalidPhase);
-    require!(game.phase3_started, GameError::Phase3NotStarted);
-    require!(game.phase3_winner.is_none(), GameError::WinnerAlreadyDeclared);
-    require!(game.players.contains(&winner), GameError::NotInGame);
+    require!(game.phase3_started != 0, GameError::Phase3NotStarted);
+    require!(game.winner().is_none(), GameError::WinnerAlreadyDeclared);
+    require!(!game.is_squad_game(), GameError::SquadGame);
+    require!(ctx.accounts.winner_entry.player == winner, GameError::NotInGame);
     
-    game.phase3_winner = Some(winner);
-    game.status = GameStatus::Completed;
+    game.phase3_winner = winner;
+    game.finish(GameStatus::Completed, Clock::get()?.unix_timestamp);
     
-    let platform_fee = game.prize_pool / 100;
-    game.platform_fee_collected += platform_fee;
+    settle_fees(game);
     
     emit!(Phase3WinnerDeclared {
         game_id: game.game_id,
         winner,
-        prize_amount: game.prize_pool - platform_fee,
+        prize_amount: game.prize_pool,
     });
     
     Ok(())
 }
 
+/// Settle the platform fee once a winner is known, reporting the creator's share
+pub(crate) fn settle_fees(game: &mut Game) {
+    let (platform_fee, creator_fee) = game.settle_platform_fee();
+    if creator_fee > 0 {
+        emit!(CreatorFeeAccrued {
+            game_id: game.game_id,
+            creator: game.creator,
+            platform_fee,
+            creator_fee,
+        });
+    }
+}
+
 #[derive(Accounts)]
 pub struct AdvanceToPhase3<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     pub caller: Signer<'info>,
 }
@@ -147,15 +178,37 @@ pub struct AdvanceToPhase3<'info> {
 #[derive(Accounts)]
 pub struct StartPhase3Game<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
     
     pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
+#[instruction(winner: Pubkey)]
 pub struct SubmitPhase3Winner<'info> {
     #[account(mut)]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
+    
+    #[account(seeds = [b"game_registry"], bump)]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// Role assignments - omitted until a role has been granted
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, RoleRegistry>>,
+    
+    /// Proves the winner joined this game
+    #[account(
+        seeds = [
+            b"player_entry",
+            game.load()?.game_id.to_le_bytes().as_ref(),
+            winner.as_ref()
+        ],
+        bump = winner_entry.bump
+    )]
+    pub winner_entry: Account<'info, PlayerEntry>,
     
     pub submitter: Signer<'info>,
 }
@@ -22,19 +22,18 @@ pub mod utils;
 
 // Import everything we need
 use crate::instructions::*;
-use crate::errors::GameError;
 
 #[program]
 pub mod solana_survivor {
     use super::*;
-
+    
     // ==================== INITIALIZATION ====================
     
     /// Initialize the game registry with an admin address
     pub fn initialize(ctx: Context<Initialize>, admin: Pubkey) -> Result<()> {
         instructions::initialize::initialize(ctx, admin)
     }
-
+    
     // ==================== GAME LIFECYCLE ====================
     
     /// Create a new game
@@ -42,9 +41,10 @@ pub mod solana_survivor {
         ctx: Context<CreateGame>,
         name: String,
         entry_fee: u64,
-        max_players: u8,
+        max_players: u16,
         start_time: i64,
-        game_duration_hours: u8,
+        rules: state::GameRules,
+        access: state::GameAccess,
     ) -> Result<()> {
         instructions::game_lifecycle::create::create_game(
             ctx,
@@ -52,47 +52,172 @@ pub mod solana_survivor {
             entry_fee,
             max_players,
             start_time,
-            game_duration_hours,
+            rules,
+            access,
         )
     }
-
+    
     /// Join an existing game
-    pub fn enter_game(ctx: Context<EnterGame>) -> Result<()> {
-        instructions::game_lifecycle::enter::enter_game(ctx)
+    /// Private games take a Merkle proof (allowlist) or the invite code
+    pub fn enter_game(ctx: Context<EnterGame>, proof: Vec<[u8; 32]>, invite_code: String) -> Result<()> {
+        instructions::game_lifecycle::enter::enter_game(ctx, proof, invite_code)
     }
-
+    
+    /// Leave a game before it starts and get the entry refunded
+    pub fn leave_game(ctx: Context<LeaveGame>) -> Result<()> {
+        instructions::game_lifecycle::leave::leave_game(ctx)
+    }
+    
+    /// Queue for a full game, depositing the entry fee up front
+    pub fn join_waitlist(ctx: Context<JoinWaitlist>, proof: Vec<[u8; 32]>, invite_code: String) -> Result<()> {
+        instructions::game_lifecycle::waitlist::join_waitlist(ctx, proof, invite_code)
+    }
+    
+    /// Move the head of the waitlist into a free seat (permissionless)
+    pub fn promote_from_waitlist(ctx: Context<PromoteFromWaitlist>) -> Result<()> {
+        instructions::game_lifecycle::waitlist::promote_from_waitlist(ctx)
+    }
+    
+    /// Refund a waitlist deposit once the game started or closed without the player
+    pub fn refund_waitlist(ctx: Context<RefundWaitlist>) -> Result<()> {
+        instructions::game_lifecycle::waitlist::refund_waitlist(ctx)
+    }
+    
+    /// Operator creates a recurring game template
+    pub fn create_template(ctx: Context<CreateTemplate>, template_id: u64, params: state::TemplateParams) -> Result<()> {
+        instructions::game_lifecycle::template::create_template(ctx, template_id, params)
+    }
+    
+    /// Operator updates or pauses a template
+    pub fn update_template(ctx: Context<UpdateTemplate>, params: state::TemplateParams, active: bool) -> Result<()> {
+        instructions::game_lifecycle::template::update_template(ctx, params, active)
+    }
+    
+    /// Top up a template's spawn budget
+    pub fn fund_template(ctx: Context<FundTemplate>, amount: u64) -> Result<()> {
+        instructions::game_lifecycle::template::fund_template(ctx, amount)
+    }
+    
+    /// Operator closes a template and takes back its budget
+    pub fn close_template(ctx: Context<CloseTemplate>) -> Result<()> {
+        instructions::game_lifecycle::template::close_template(ctx)
+    }
+    
+    /// Create the next game from a template once it is due (permissionless)
+    pub fn spawn_from_template(ctx: Context<SpawnFromTemplate>) -> Result<()> {
+        instructions::game_lifecycle::template::spawn_from_template(ctx)
+    }
+    
+    /// Add funds to a game's prize pool as a named sponsor
+    pub fn sponsor_game(ctx: Context<SponsorGame>, amount: u64, display_name: String, fee_eligible: bool) -> Result<()> {
+        instructions::game_lifecycle::sponsor::sponsor_game(ctx, amount, display_name, fee_eligible)
+    }
+    
+    /// Return sponsor funds from a cancelled or expired game (permissionless)
+    pub fn refund_sponsor(ctx: Context<RefundSponsor>) -> Result<()> {
+        instructions::game_lifecycle::sponsor::refund_sponsor(ctx)
+    }
+    
+    /// Found a squad in a squad game, choosing the prize share of each slot
+    pub fn create_squad(ctx: Context<CreateSquad>, name: String, shares: Vec<u16>) -> Result<()> {
+        instructions::game_lifecycle::squad::create_squad(ctx, name, shares)
+    }
+    
+    /// Take the next free slot in a squad
+    pub fn join_squad(ctx: Context<JoinSquad>) -> Result<()> {
+        instructions::game_lifecycle::squad::join_squad(ctx)
+    }
+    
+    /// Leave a squad before the game starts
+    pub fn leave_squad(ctx: Context<LeaveSquad>) -> Result<()> {
+        instructions::game_lifecycle::squad::leave_squad(ctx)
+    }
+    
+    /// Reclaim a squad's rent after the game
+    pub fn close_squad(ctx: Context<CloseSquad>) -> Result<()> {
+        instructions::game_lifecycle::cleanup::close_squad(ctx)
+    }
+    
+    /// Reclaim a squad membership's rent after the game
+    pub fn close_squad_member(ctx: Context<CloseSquadMember>) -> Result<()> {
+        instructions::game_lifecycle::cleanup::close_squad_member(ctx)
+    }
+    
     /// Start the game (creator only)
     pub fn start_game(ctx: Context<StartGame>) -> Result<()> {
         instructions::game_lifecycle::start::start_game(ctx)
     }
-
+    
     /// Creator cancels the game
     pub fn creator_cancel_game(ctx: Context<CreatorCancelGame>) -> Result<()> {
         instructions::game_lifecycle::refund::creator_cancel_game(ctx)
     }
-
+    
     /// Claim refund from cancelled/expired game
     pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
         instructions::game_lifecycle::refund::claim_refund(ctx)
     }
-
+    
     /// Force refund if creator didn't fulfill obligations
     pub fn force_refund_expired_game(ctx: Context<ForceRefund>) -> Result<()> {
         instructions::game_lifecycle::refund::force_refund_expired_game(ctx)
     }
-
+    
+    /// Anyone pushes refunds to the players of a cancelled or expired game
+    pub fn crank_refunds<'info>(ctx: Context<'_, '_, 'info, 'info, CrankRefunds<'info>>) -> Result<()> {
+        instructions::game_lifecycle::refund::crank_refunds(ctx)
+    }
+    
+    /// Close a settled game and reclaim its rent
+    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+        instructions::game_lifecycle::close::close_game(ctx)
+    }
+    
+    /// Close your game entry once your stake is settled
+    pub fn close_player_entry(ctx: Context<ClosePlayerEntry>) -> Result<()> {
+        instructions::game_lifecycle::cleanup::close_player_entry(ctx)
+    }
+    
+    /// Close your player state after the game ends
+    pub fn close_player_state(ctx: Context<ClosePlayerState>) -> Result<()> {
+        instructions::game_lifecycle::cleanup::close_player_state(ctx)
+    }
+    
+    /// Close the pool state after the game ends
+    pub fn close_pool_state(ctx: Context<ClosePoolState>) -> Result<()> {
+        instructions::game_lifecycle::cleanup::close_pool_state(ctx)
+    }
+    
+    /// Close a challenge after the game ends
+    pub fn close_challenge(ctx: Context<CloseChallenge>) -> Result<()> {
+        instructions::game_lifecycle::cleanup::close_challenge(ctx)
+    }
+    
+    /// Close your Phase 3 ready state after the game ends
+    pub fn close_phase3_ready_state(ctx: Context<ClosePhase3ReadyState>) -> Result<()> {
+        instructions::game_lifecycle::cleanup::close_phase3_ready_state(ctx)
+    }
+    
+    /// Creator/admin sweeps abandoned accounts of a finished game
+    pub fn sweep_game_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepGameAccounts<'info>>,
+        game_id: u64,
+    ) -> Result<()> {
+        instructions::game_lifecycle::cleanup::sweep_game_accounts(ctx, game_id)
+    }
+    
     // ==================== PHASE 1: RESOURCE MANAGEMENT ====================
     
     /// Initialize player state for Phase 1
     pub fn initialize_player_state(ctx: Context<InitializePlayerState>) -> Result<()> {
         instructions::phase1::initialize::initialize_player_state(ctx)
     }
-
+    
     /// Initialize global pool state
     pub fn initialize_pool_state(ctx: Context<InitializePoolState>) -> Result<()> {
         instructions::phase1::initialize::initialize_pool_state(ctx)
     }
-
+    
     /// Submit resource allocations
     pub fn submit_allocations(
         ctx: Context<SubmitAllocations>,
@@ -111,22 +236,27 @@ pub mod solana_survivor {
             social,
         )
     }
-
+    
     /// Claim accumulated rewards
     pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         instructions::phase1::rewards::claim_rewards(ctx)
     }
-
+    
     /// Claim phase end rewards and apply penalties
     pub fn claim_phase_end_rewards(ctx: Context<ClaimPhaseEndRewards>) -> Result<()> {
         instructions::phase1::rewards::claim_phase_end_rewards(ctx)
     }
-
+    
+    /// Give unallocated balance to a squadmate
+    pub fn share_squad_balance(ctx: Context<ShareSquadBalance>, amount: u64) -> Result<()> {
+        instructions::phase1::share::share_squad_balance(ctx, amount)
+    }
+    
     /// Advance to next phase
     pub fn advance_phase(ctx: Context<AdvancePhase>) -> Result<()> {
         instructions::phase2::advance::advance_phase(ctx)
     }
-
+    
     // ==================== PHASE 2: PVP CHALLENGES ====================
     
     /// Create a challenge to another player
@@ -145,77 +275,238 @@ pub mod solana_survivor {
             game_type,
         )
     }
-
+    
     /// Respond to a challenge
     pub fn respond_challenge(ctx: Context<RespondChallenge>, accept: bool) -> Result<()> {
         instructions::phase2::challenge::respond_challenge(ctx, accept)
     }
-
+    
     /// Mark ready for mini-game
     pub fn ready_for_game(ctx: Context<ReadyForGame>) -> Result<()> {
         instructions::phase2::minigame::ready_for_game(ctx)
     }
-
+    
     /// Start the mini-game
     pub fn start_mini_game(ctx: Context<StartMiniGame>) -> Result<()> {
         instructions::phase2::minigame::start_mini_game(ctx)
     }
-
+    
     /// Claim mini-game victory
     pub fn claim_mini_game_win(ctx: Context<ClaimMiniGameWin>, winner: Pubkey) -> Result<()> {
         instructions::phase2::minigame::claim_mini_game_win(ctx, winner)
     }
-
+    
     // ==================== PHASE 3: THE PURGE ====================
     
     /// Advance to Phase 3
     pub fn advance_to_phase3(ctx: Context<AdvanceToPhase3>) -> Result<()> {
         instructions::phase3::start::advance_to_phase3(ctx)
     }
-
+    
     /// Mark yourself ready for the purge
     pub fn mark_ready_phase3(ctx: Context<MarkReadyPhase3>) -> Result<()> {
         instructions::phase3::ready::mark_ready_phase3(ctx)
     }
-
+    
     /// Start the purge
     pub fn start_phase3_game<'info>(
         ctx: Context<'_, '_, 'info, 'info, StartPhase3Game<'info>>
     ) -> Result<()> {
         instructions::phase3::start::start_phase3_game(ctx)
     }
-
-    /// Submit the purge winner
+    
+    /// Referee submits the purge winner
     pub fn submit_phase3_winner(ctx: Context<SubmitPhase3Winner>, winner: Pubkey) -> Result<()> {
         instructions::phase3::start::submit_phase3_winner(ctx, winner)
     }
-
+    
     /// Winner claims the prize
     pub fn claim_phase3_prize(ctx: Context<ClaimPhase3Prize>) -> Result<()> {
         instructions::phase3::claim::claim_phase3_prize(ctx)
     }
-
-    /// Admin/creator collects platform fee
+    
+    /// Referee submits the winning squad
+    pub fn submit_phase3_squad_winner(ctx: Context<SubmitPhase3SquadWinner>) -> Result<()> {
+        instructions::phase3::squad::submit_phase3_squad_winner(ctx)
+    }
+    
+    /// Winning squad member claims their share of the prize
+    pub fn claim_squad_prize(ctx: Context<ClaimSquadPrize>) -> Result<()> {
+        instructions::phase3::squad::claim_squad_prize(ctx)
+    }
+    
+    /// Treasurer collects platform fee
     pub fn claim_platform_fee(ctx: Context<ClaimPlatformFee>) -> Result<()> {
         instructions::phase3::claim::claim_platform_fee(ctx)
     }
-
+    
+    /// Creator claims their share of the platform fee
+    pub fn claim_creator_fee(ctx: Context<ClaimCreatorFee>) -> Result<()> {
+        instructions::phase3::claim::claim_creator_fee(ctx)
+    }
+    
     // ==================== ADMIN FUNCTIONS ====================
     
-    /// Admin emergency game start
+    /// Operator game start (consumes a queued StartGame action)
     pub fn admin_start_game(ctx: Context<AdminStartGame>) -> Result<()> {
         instructions::admin::game_control::admin_start_game(ctx)
     }
-
-    /// Admin force phase advancement
+    
+    /// Operator phase advancement (consumes a queued AdvancePhase action)
     pub fn admin_advance_phase(ctx: Context<AdminAdvancePhase>) -> Result<()> {
         instructions::admin::game_control::admin_advance_phase(ctx)
     }
-
-    /// Admin closes purge with no ready players
+    
+    /// Operator closes purge with no ready players (consumes a queued action)
     pub fn admin_close_purge_no_ready<'info>(
         ctx: Context<'_, '_, 'info, 'info, AdminClosePurgeNoReady<'info>>
     ) -> Result<()> {
         instructions::admin::game_control::admin_close_purge_no_ready(ctx)
     }
+    
+    /// Role admin grants or revokes roles for a key
+    pub fn set_roles(ctx: Context<SetRoles>, member: Pubkey, roles: u8) -> Result<()> {
+        instructions::admin::roles::set_roles(ctx, member, roles)
+    }
+    
+    /// Treasurer withdraws collected platform fees from the treasury
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::admin::treasury::withdraw_treasury(ctx, amount)
+    }
+    
+    /// Admin pauses or resumes the whole program (emergency, no timelock)
+    pub fn set_program_paused(ctx: Context<SetProgramPaused>, paused: bool) -> Result<()> {
+        instructions::admin::pause::set_program_paused(ctx, paused)
+    }
+    
+    /// Admin pauses or resumes a single game (emergency, no timelock)
+    pub fn set_game_paused(ctx: Context<SetGamePaused>, paused: bool) -> Result<()> {
+        instructions::admin::pause::set_game_paused(ctx, paused)
+    }
+    
+    /// Admin nominates a new admin (two-step transfer)
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey, expires_at: Option<i64>) -> Result<()> {
+        instructions::admin::transfer::propose_admin(ctx, new_admin, expires_at)
+    }
+    
+    /// Proposed admin accepts the transfer
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::admin::transfer::accept_admin(ctx)
+    }
+    
+    /// Admin creates the global config with the default tuning parameters
+    pub fn initialize_global_config(ctx: Context<InitializeGlobalConfig>) -> Result<()> {
+        instructions::admin::config::initialize_global_config(ctx)
+    }
+    
+    /// Admin updates the tuning parameters used by newly created games
+    pub fn update_global_config(ctx: Context<UpdateGlobalConfig>, params: state::ConfigParams) -> Result<()> {
+        instructions::admin::config::update_global_config(ctx, params)
+    }
+    
+    /// Admin updates the bounds on the rules creators can pick
+    pub fn update_rule_bounds(ctx: Context<UpdateGlobalConfig>, rule_bounds: state::RuleBounds) -> Result<()> {
+        instructions::admin::config::update_rule_bounds(ctx, rule_bounds)
+    }
+    
+    /// Operator announces a game intervention (runs after the notice period)
+    pub fn queue_admin_action(
+        ctx: Context<QueueAdminAction>,
+        game_id: u64,
+        action: state::AdminAction,
+    ) -> Result<()> {
+        instructions::admin::timelock::queue_admin_action(ctx, game_id, action)
+    }
+    
+    /// Operator withdraws a queued intervention
+    pub fn cancel_admin_action(ctx: Context<CancelAdminAction>) -> Result<()> {
+        instructions::admin::timelock::cancel_admin_action(ctx)
+    }
+    
+    /// Admin sets the council members and approval threshold
+    pub fn set_admin_council<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetAdminCouncil<'info>>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::admin::council::set_admin_council(ctx, members, threshold)
+    }
+    
+    /// Council member proposes an emergency action on a game
+    pub fn create_admin_proposal(
+        ctx: Context<CreateAdminProposal>,
+        game_id: u64,
+        action: state::AdminAction,
+    ) -> Result<()> {
+        instructions::admin::council::create_admin_proposal(ctx, game_id, action)
+    }
+    
+    /// Council member approves a proposal
+    pub fn approve_admin_proposal(ctx: Context<ApproveAdminProposal>) -> Result<()> {
+        instructions::admin::council::approve_admin_proposal(ctx)
+    }
+    
+    /// Proposer withdraws an unexecuted proposal
+    pub fn cancel_admin_proposal(ctx: Context<CancelAdminProposal>) -> Result<()> {
+        instructions::admin::council::cancel_admin_proposal(ctx)
+    }
+    
+    /// Anyone executes an approved StartGame proposal
+    pub fn execute_start_game(ctx: Context<ExecuteGameProposal>) -> Result<()> {
+        instructions::admin::council::execute_start_game(ctx)
+    }
+    
+    /// Anyone executes an approved AdvancePhase proposal
+    pub fn execute_advance_phase(ctx: Context<ExecuteGameProposal>) -> Result<()> {
+        instructions::admin::council::execute_advance_phase(ctx)
+    }
+    
+    /// Anyone executes an approved ClosePurgeNoReady proposal
+    pub fn execute_close_purge_no_ready<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteClosePurgeNoReady<'info>>
+    ) -> Result<()> {
+        instructions::admin::council::execute_close_purge_no_ready(ctx)
+    }
+    
+    /// Read-only check of a game's fund accounting
+    pub fn audit_game(ctx: Context<AuditGame>) -> Result<()> {
+        instructions::admin::audit::audit_game(ctx)
+    }
+    
+    /// Admin upgrades a game to the current zero-copy layout
+    pub fn migrate_game(ctx: Context<MigrateGame>, game_id: u64) -> Result<()> {
+        instructions::admin::migrate::migrate_game(ctx, game_id)
+    }
+    
+    /// Admin creates the player entries of a migrated game from its roster
+    pub fn migrate_player_entries<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigratePlayerEntries<'info>>
+    ) -> Result<()> {
+        instructions::admin::migrate::migrate_player_entries(ctx)
+    }
+    
+    /// Admin adds the version byte to the game registry (run first)
+    pub fn migrate_registry(ctx: Context<MigrateRegistry>) -> Result<()> {
+        instructions::admin::migrate::migrate_registry(ctx)
+    }
+    
+    /// Admin upgrades a player state to the current layout
+    pub fn migrate_player_state(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::admin::migrate::migrate_player_state(ctx)
+    }
+    
+    /// Admin upgrades a pool state to the current layout
+    pub fn migrate_pool_state(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::admin::migrate::migrate_pool_state(ctx)
+    }
+    
+    /// Admin upgrades a challenge to the current layout
+    pub fn migrate_challenge(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::admin::migrate::migrate_challenge(ctx)
+    }
+    
+    /// Admin upgrades a Phase 3 ready state to the current layout
+    pub fn migrate_phase3_ready_state(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::admin::migrate::migrate_phase3_ready_state(ctx)
+    }
 }
@@ -0,0 +1,29 @@
+// Creator bond - lamports a creator locks up until their game starts or is cancelled
+
+use anchor_lang::prelude::*;
+
+/// Bond posted by the creator in `create_game`, seeded by game id
+/// Returned on a proper start or cancel; if players have to force a refund
+/// it is paid out to them instead, and whatever they can't take goes to the
+/// treasury when the game closes
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorBond {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    pub creator: Pubkey,
+    
+    // Bonded lamports not yet returned or slashed (on top of rent)
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl CreatorBond {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
@@ -4,7 +4,11 @@ use anchor_lang::prelude::*;
 
 /// Challenge account representing a PvP match between two players
 #[account]
+#[derive(InitSpace)]
 pub struct Challenge {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
     pub challenge_id: u64,
     pub game_id: u64,
     
@@ -12,6 +16,10 @@ pub struct Challenge {
     pub challenger: Pubkey,
     pub opponent: Pubkey,
     
+    // Squads facing off in squad games; any member of `opponent_squad` may answer
+    pub challenger_squad: Option<Pubkey>,
+    pub opponent_squad: Option<Pubkey>,
+    
     // Game details
     pub bet_amount: u64,
     pub game_type: MiniGameType,
@@ -29,8 +37,16 @@ pub struct Challenge {
     pub opponent_decline_count: u8,  // Force accept after 5 declines
 }
 
+impl Challenge {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
+
 /// Types of mini-games players can challenge each other to
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
 pub enum MiniGameType {
     CryptoTrivia,        // Knowledge-based questions
     RockPaperScissors,   // Classic game
@@ -38,8 +54,18 @@ pub enum MiniGameType {
     MemeBattle,          // Creative competition
 }
 
+impl MiniGameType {
+    /// Bitmask with every mini-game allowed
+    pub const ALL: u8 = 0b1111;
+    
+    /// This mini-game's bit in `Game::allowed_mini_games`
+    pub fn mask(&self) -> u8 {
+        1 << self.clone() as u8
+    }
+}
+
 /// Challenge lifecycle states
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
 pub enum ChallengeStatus {
     Pending,        // Waiting for opponent response
     Accepted,       // Opponent accepted, waiting for both ready
@@ -0,0 +1,196 @@
+// Global configuration - tuning parameters editable by the admin
+
+use anchor_lang::prelude::*;
+use bytemuck::{Pod, Zeroable};
+use crate::constants::*;
+use crate::errors::GameError;
+
+/// Admin-editable tuning parameters, stored next to the game registry
+/// Each game snapshots `params` when it is created, so updates only
+/// affect games created afterwards
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalConfig {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub params: ConfigParams,
+    pub bump: u8,
+    
+    // Limits on the custom rules creators pass to `create_game`
+    pub rule_bounds: RuleBounds,
+}
+
+impl GlobalConfig {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
+
+/// Game tuning parameters
+/// Plain-old-data so it can be embedded in the zero-copy `Game` snapshot;
+/// fields are ordered so the `repr(C)` layout has no implicit padding
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct ConfigParams {
+    // Timing (seconds)
+    pub game_start_grace_period: i64,
+    pub phase_advance_buffer: i64,
+    pub phase3_ready_window: i64,
+    pub phase3_extended_window: i64,
+    pub account_sweep_grace_period: i64,
+    
+    // Notice period before a queued or proposed admin action can run
+    pub admin_action_delay: i64,
+    
+    // Creator bond locked in `create_game` (lamports)
+    pub creator_bond: u64,
+    
+    // Paid to the refund crank per refunded player (lamports)
+    pub crank_refund_tip: u64,
+    
+    // Platform fees (percent of the prize pool)
+    pub platform_fee_percentage: u64,
+    pub admin_share_no_ready: u64,
+    
+    // Virtual balance = entry_fee * multiplier
+    pub initial_balance_multiplier: u64,
+    
+    // Phase 1 resource rates and caps
+    pub mining_base_rate: f64,
+    pub farming_base_rate: f64,
+    pub social_base_rate: f64,
+    pub max_mining_difficulty: f64,
+    pub max_social_bonus: f64,
+    
+    // Player requirements
+    pub min_players_to_start: u16,
+    pub max_players_allowed: u16,
+    
+    // Phase 2 challenge limits
+    pub min_phase2_games: u8,
+    pub max_phase2_games: u8,
+    pub max_opponent_declines: u8,
+    
+    // Opponent records kept per player state; sizes the account
+    pub max_tracked_opponents: u8,
+}
+
+/// Admin bounds for per-game `GameRules`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy)]
+pub struct RuleBounds {
+    // Allowed length of each phase (seconds)
+    pub min_phase_duration: u64,
+    pub max_phase_duration: u64,
+    
+    // Largest share of the platform fee a creator may keep (basis points)
+    pub max_creator_fee_bps: u16,
+}
+
+impl Default for RuleBounds {
+    fn default() -> Self {
+        Self {
+            min_phase_duration: MIN_PHASE_DURATION,
+            max_phase_duration: MAX_PHASE_DURATION,
+            max_creator_fee_bps: MAX_CREATOR_FEE_BPS,
+        }
+    }
+}
+
+impl RuleBounds {
+    /// Check the bounds against their hard limits
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.min_phase_duration >= MIN_PHASE_DURATION_LIMIT
+                && self.min_phase_duration <= self.max_phase_duration
+                && self.max_phase_duration <= MAX_PHASE_DURATION_LIMIT
+                && self.max_creator_fee_bps as u64 <= BPS_DENOMINATOR,
+            GameError::InvalidConfig
+        );
+        
+        Ok(())
+    }
+}
+
+impl Default for ConfigParams {
+    fn default() -> Self {
+        Self {
+            game_start_grace_period: GAME_START_GRACE_PERIOD,
+            phase_advance_buffer: PHASE_ADVANCE_BUFFER,
+            phase3_ready_window: PHASE3_READY_WINDOW,
+            phase3_extended_window: PHASE3_EXTENDED_WINDOW,
+            account_sweep_grace_period: ACCOUNT_SWEEP_GRACE_PERIOD,
+            admin_action_delay: ADMIN_ACTION_DELAY,
+            creator_bond: CREATOR_BOND,
+            crank_refund_tip: CRANK_REFUND_TIP,
+            platform_fee_percentage: PLATFORM_FEE_PERCENTAGE,
+            admin_share_no_ready: ADMIN_SHARE_NO_READY,
+            initial_balance_multiplier: INITIAL_BALANCE_MULTIPLIER,
+            mining_base_rate: MINING_BASE_RATE,
+            farming_base_rate: FARMING_BASE_RATE,
+            social_base_rate: SOCIAL_BASE_RATE,
+            max_mining_difficulty: MAX_MINING_DIFFICULTY,
+            max_social_bonus: MAX_SOCIAL_BONUS,
+            min_players_to_start: MIN_PLAYERS_TO_START,
+            max_players_allowed: MAX_PLAYERS_ALLOWED,
+            min_phase2_games: MIN_PHASE2_GAMES,
+            max_phase2_games: MAX_PHASE2_GAMES,
+            max_opponent_declines: MAX_OPPONENT_DECLINES,
+            max_tracked_opponents: MAX_TRACKED_OPPONENTS,
+        }
+    }
+}
+
+impl ConfigParams {
+    /// Check every parameter against its hard bounds
+    pub fn validate(&self) -> Result<()> {
+        let window = MIN_CONFIG_WINDOW..=MAX_CONFIG_WINDOW;
+        let rate = |value: f64| value > 0.0 && value <= MAX_BASE_RATE;
+        
+        require!(
+            window.contains(&self.game_start_grace_period)
+                && window.contains(&self.phase_advance_buffer)
+                && window.contains(&self.phase3_ready_window)
+                && window.contains(&self.phase3_extended_window)
+                && (MIN_SWEEP_GRACE_PERIOD..=MAX_SWEEP_GRACE_PERIOD).contains(&self.account_sweep_grace_period)
+                && (MIN_ADMIN_ACTION_DELAY..=MAX_ADMIN_ACTION_DELAY).contains(&self.admin_action_delay),
+            GameError::InvalidConfig
+        );
+        require!(
+            self.creator_bond <= MAX_CREATOR_BOND && self.crank_refund_tip <= MAX_CRANK_REFUND_TIP,
+            GameError::InvalidConfig
+        );
+        require!(
+            self.platform_fee_percentage <= MAX_PLATFORM_FEE_PERCENTAGE
+                && self.admin_share_no_ready <= MAX_ADMIN_SHARE_NO_READY
+                && (1..=MAX_INITIAL_BALANCE_MULTIPLIER).contains(&self.initial_balance_multiplier),
+            GameError::InvalidConfig
+        );
+        require!(
+            rate(self.mining_base_rate)
+                && rate(self.farming_base_rate)
+                && rate(self.social_base_rate)
+                && (0.0..1.0).contains(&self.max_mining_difficulty)
+                && (0.0..=MAX_SOCIAL_BONUS_LIMIT).contains(&self.max_social_bonus),
+            GameError::InvalidConfig
+        );
+        require!(
+            self.min_players_to_start >= 2
+                && self.min_players_to_start <= self.max_players_allowed
+                && self.max_players_allowed <= MAX_PLAYERS_LIMIT,
+            GameError::InvalidConfig
+        );
+        require!(
+            self.min_phase2_games >= 1
+                && self.min_phase2_games <= self.max_phase2_games
+                && self.max_phase2_games <= MAX_PHASE2_GAMES_LIMIT
+                && (1..=MAX_OPPONENT_DECLINES_LIMIT).contains(&self.max_opponent_declines)
+                && (1..=MAX_TRACKED_OPPONENTS_LIMIT).contains(&self.max_tracked_opponents),
+            GameError::InvalidConfig
+        );
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+// Admin council and timelock - M-of-N approval and public notice for admin
+// game controls
+
+use anchor_lang::prelude::*;
+use crate::constants::{ADMIN_ACTION_GRACE, MAX_COUNCIL_MEMBERS};
+use crate::errors::GameError;
+
+/// Council of admin keys; any emergency action needs `threshold` approvals
+#[account]
+#[derive(InitSpace)]
+pub struct AdminCouncil {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    #[max_len(MAX_COUNCIL_MEMBERS)]
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    
+    // Used to derive proposal PDAs
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+impl AdminCouncil {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.members.contains(key)
+    }
+    
+    /// Approvals that still count - members removed since approving are ignored
+    pub fn count_approvals(&self, approvals: &[Pubkey]) -> usize {
+        approvals.iter().filter(|key| self.is_member(key)).count()
+    }
+    
+    /// Fail once a council exists - its M-of-N approval then replaces the
+    /// single-key timelocked controls
+    pub fn require_absent(council: &AccountInfo) -> Result<()> {
+        require!(council.data_is_empty(), GameError::CouncilRequired);
+        Ok(())
+    }
+}
+
+/// Proposed emergency action on one game, executable by anyone once approved
+#[account]
+#[derive(InitSpace)]
+pub struct AdminProposal {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub proposal_id: u64,
+    pub game_id: u64,
+    pub action: AdminAction,
+    pub proposer: Pubkey,
+    
+    #[max_len(MAX_COUNCIL_MEMBERS)]
+    pub approvals: Vec<Pubkey>,
+    
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl AdminProposal {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
+
+/// Admin action announced for one game, seeded by (game_id, action)
+/// Consumed by the matching `admin_*` instruction once the delay has passed
+#[account]
+#[derive(InitSpace)]
+pub struct QueuedAdminAction {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    pub action: AdminAction,
+    pub queued_by: Pubkey,
+    pub queued_at: i64,
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+impl QueuedAdminAction {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// The notice period is over and the action has not lapsed
+    pub fn check_ready(&self, action: AdminAction, now: i64) -> Result<()> {
+        require!(self.action == action, GameError::ProposalMismatch);
+        require!(now >= self.executable_at, GameError::TimelockNotElapsed);
+        require!(now <= self.executable_at + ADMIN_ACTION_GRACE, GameError::QueuedActionExpired);
+        
+        Ok(())
+    }
+}
+
+/// Admin game controls - proposed by the council or queued by the admin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+pub enum AdminAction {
+    StartGame,
+    AdvancePhase,
+    ClosePurgeNoReady,
+}
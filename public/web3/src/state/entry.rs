@@ -0,0 +1,30 @@
+// Player entry - per-player membership record for a game
+
+use anchor_lang::prelude::*;
+
+/// Proof that a player joined a game, seeded by (game_id, player)
+/// Replaces the inline player lists on `Game` so membership and refund
+/// checks are a single account lookup regardless of game size
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerEntry {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub entered_at: i64,
+    
+    // Stake the vault actually received for this entry (net of transfer fees)
+    pub amount: u64,
+    pub refunded: bool,
+    pub bump: u8,
+}
+
+impl PlayerEntry {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
@@ -1,55 +1,327 @@
 // Game state and lifecycle management
 
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+use crate::constants::{
+    BPS_DENOMINATOR, MAX_ALLOWLIST_PROOF_LEN, MAX_BET_PERCENTAGE, MAX_GAME_NAME_LEN, MAX_INVITE_CODE_LEN,
+    MAX_LEAVE_LOCK_WINDOW, MAX_SQUAD_SIZE, MIN_SQUAD_SIZE,
+};
+use crate::errors::GameError;
+use crate::state::{ConfigParams, GameRegistry, MiniGameType, RuleBounds};
 
 /// Main game account that tracks the entire game lifecycle
-#[account]
+/// Zero-copy: fields are fixed-size and ordered so the `repr(C)` layout has
+/// no padding. Flags are stored as u8 (0/1), optional keys as
+/// `Pubkey::default()` when unset, and the status as a `GameStatus` u8.
+#[account(zero_copy)]
 pub struct Game {
     // Basic game info
     pub game_id: u64,
-    pub name: String,
-    pub creator: Pubkey,
     pub entry_fee: u64,
     
-    // Player tracking
-    pub max_players: u8,
-    pub current_players: u8,
-    pub players: Vec<Pubkey>,
-    
     // Timing
     pub start_time: i64,
     pub expire_time: i64,
     
     // Game state
-    pub status: GameStatus,
-    pub game_started: bool,
     pub prize_pool: u64,
-    pub refunded_players: Vec<Pubkey>,
     
     // Phase management
-    pub current_phase: u8,
     pub phase_start_time: i64,
     pub phase_end_time: i64,
     pub phase_advance_deadline: i64,
     pub phases: PhaseDurations,
     
-    // Phase 2 requirements
-    pub phase2_required_games: u8,
-    pub phase2_max_games_per_opponent: u8,
-    
     // Phase 3 purge state
     pub phase3_ready_deadline: i64,
     pub phase3_extended_deadline: i64,
-    pub phase3_players_ready: u8,
-    pub phase3_started: bool,
-    pub phase3_winner: Option<Pubkey>,
-    pub phase3_prize_claimed: bool,
     
     // Platform fees
     pub platform_fee_collected: u64,
+    
+    // When the game reached a final status (0 while running)
+    pub finished_at: i64,
+    
+    pub creator: Pubkey,
+    
+    // Declared Phase 3 winner (default while undecided)
+    pub phase3_winner: Pubkey,
+    
+    // Stake currency - default for native SOL, the mint for SPL token games
+    pub mint: Pubkey,
+    
+    // UTF-8 name, zero padded
+    pub name: [u8; MAX_GAME_NAME_LEN],
+    
+    // Player tracking - membership lives in PlayerEntry PDAs
+    pub max_players: u16,
+    pub current_players: u16,
+    pub refunded_count: u16,
+    pub phase3_players_ready: u16,
+    
+    pub status: u8,
+    pub game_started: u8,
+    pub creator_joined: u8,
+    pub current_phase: u8,
+    
+    // Phase 2 requirements
+    pub phase2_required_games: u8,
+    pub phase2_max_games_per_opponent: u8,
+    
+    pub phase3_started: u8,
+    pub phase3_prize_claimed: u8,
+    pub bump: u8,
+    
+    // Bump of the SOL vault PDA holding stakes (`[b"vault", game_id]`)
+    pub vault_bump: u8,
+    
+    /// Account layout version, bumped by `migrate_game`
+    pub version: u8,
+    
+    // Creator rules for Phase 2 challenges
+    pub max_bet_percentage: u8,
+    pub allowed_mini_games: u8,
+    
+    // Per-game emergency pause, set by the admin
+    pub paused: u8,
+    
+    // Creator's share of the platform fee (basis points)
+    pub creator_fee_bps: u16,
+    
+    // GlobalConfig snapshot taken at creation
+    pub config: ConfigParams,
+    
+    // When the current per-game pause began
+    pub paused_at: i64,
+    
+    // Part of the registry's `total_paused` already added to the deadlines
+    pub global_pause_applied: i64,
+    
+    // Creator fee share settled but not yet claimed
+    pub creator_fee_collected: u64,
+    
+    // Players may leave until this many seconds before `start_time`
+    pub leave_lock_window: i64,
+    
+    // Waitlist deposits held in the vault, not yet promoted or refunded
+    pub waitlist_escrow: u64,
+    
+    // Private games: Merkle root of allowed wallets or hash of the invite code
+    pub access_root: [u8; 32],
+    
+    // Who may enter, as an `AccessMode` u8
+    pub access_mode: u8,
+    
+    // Whether the creator's `CreatorBond` PDA is still open (never for migrated games)
+    pub creator_bonded: u8,
+    pub _access_padding: [u8; 6],
+    
+    // Sponsor funds in the prize pool, and the part excluded from the platform fee
+    pub sponsored_amount: u64,
+    pub sponsor_fee_exempt: u64,
+    
+    // Squad games: largest squad (0 for solo games), squads formed, players
+    // in a squad and squads still below `MIN_SQUAD_SIZE`
+    pub squad_size: u8,
+    pub _squad_padding: u8,
+    pub squad_count: u16,
+    pub squad_players: u16,
+    pub short_squads: u16,
 }
 
 impl Game {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + std::mem::size_of::<Game>();
+    
+    /// Game name as a string
+    pub fn name(&self) -> String {
+        let len = self.name.iter().position(|b| *b == 0).unwrap_or(MAX_GAME_NAME_LEN);
+        String::from_utf8_lossy(&self.name[..len]).into_owned()
+    }
+    
+    /// Store a name that was checked to fit `MAX_GAME_NAME_LEN`
+    pub fn set_name(&mut self, name: &str) {
+        self.name = [0; MAX_GAME_NAME_LEN];
+        self.name[..name.len()].copy_from_slice(name.as_bytes());
+    }
+    
+    /// Stake mint - None for native SOL games
+    pub fn stake_mint(&self) -> Option<Pubkey> {
+        (self.mint != Pubkey::default()).then_some(self.mint)
+    }
+    
+    /// Whether stakes are held in an SPL token vault instead of lamports
+    pub fn is_token_game(&self) -> bool {
+        self.stake_mint().is_some()
+    }
+    
+    /// Declared Phase 3 winner, if any
+    pub fn winner(&self) -> Option<Pubkey> {
+        (self.phase3_winner != Pubkey::default()).then_some(self.phase3_winner)
+    }
+    
+    /// Whether players enter and win as squads (the winner is a squad PDA)
+    pub fn is_squad_game(&self) -> bool {
+        self.squad_size != 0
+    }
+    
+    /// Whether a squad game can start: everyone is in a squad of at least
+    /// `MIN_SQUAD_SIZE` and there is someone to play against
+    pub fn squads_complete(&self) -> bool {
+        self.squad_count >= 2 && self.short_squads == 0 && self.squad_players == self.current_players
+    }
+    
+    /// Check that `player` may enter this game
+    /// Allowlist games take a Merkle proof of the player's wallet, invite-only
+    /// games the invite code; public games ignore both
+    pub fn check_access(&self, player: &Pubkey, proof: &[[u8; 32]], invite_code: &str) -> Result<()> {
+        if self.access_mode == AccessMode::Allowlist as u8 {
+            require!(proof.len() <= MAX_ALLOWLIST_PROOF_LEN, GameError::NotOnAllowlist);
+            require!(
+                allowlist_root(player, proof) == self.access_root,
+                GameError::NotOnAllowlist
+            );
+        } else if self.access_mode == AccessMode::InviteCode as u8 {
+            require!(
+                invite_code.len() <= MAX_INVITE_CODE_LEN && invite_code_hash(invite_code) == self.access_root,
+                GameError::InvalidInviteCode
+            );
+        }
+        Ok(())
+    }
+    
+    /// Whether the game is currently in `status`
+    pub fn has_status(&self, status: GameStatus) -> bool {
+        self.status == status as u8
+    }
+    
+    /// Whether the game reached a final status
+    pub fn has_final_status(&self) -> bool {
+        self.has_status(GameStatus::Completed)
+            || self.has_status(GameStatus::Cancelled)
+            || self.has_status(GameStatus::ExpiredWithPenalty)
+    }
+    
+    /// Fail while the program or this game is paused
+    pub fn require_not_paused(&self, registry: &GameRegistry) -> Result<()> {
+        require!(!registry.paused, GameError::ProgramPaused);
+        require!(self.paused == 0, GameError::GamePaused);
+        Ok(())
+    }
+    
+    /// Global pause time not yet added to this game's deadlines
+    pub fn pending_global_pause(&self, registry: &GameRegistry) -> i64 {
+        registry.total_paused - self.global_pause_applied
+    }
+    
+    /// Fail while paused, otherwise catch the deadlines up with any global
+    /// pause that ended since this game was last touched
+    pub fn sync_pause(&mut self, registry: &GameRegistry) -> Result<()> {
+        self.require_not_paused(registry)?;
+        self.extend_deadlines(self.pending_global_pause(registry));
+        self.global_pause_applied = registry.total_paused;
+        Ok(())
+    }
+    
+    /// Push every pending deadline back by `delta` seconds so paused time
+    /// doesn't count against players
+    pub fn extend_deadlines(&mut self, delta: i64) {
+        if delta <= 0 || self.has_final_status() {
+            return;
+        }
+        
+        if self.game_started == 0 {
+            self.start_time += delta;
+            self.expire_time += delta;
+            return;
+        }
+        self.phase_end_time += delta;
+        self.phase_advance_deadline += delta;
+        if self.phase3_ready_deadline > 0 {
+            self.phase3_ready_deadline += delta;
+        }
+        if self.phase3_extended_deadline > 0 {
+            self.phase3_extended_deadline += delta;
+        }
+    }
+    
+    /// Whether challenges may use `game_type` in this game
+    pub fn allows_mini_game(&self, game_type: &MiniGameType) -> bool {
+        self.allowed_mini_games & game_type.mask() != 0
+    }
+    
+    /// Largest challenge bet allowed for a player holding `balance`
+    pub fn max_bet(&self, balance: u64) -> u64 {
+        balance * self.max_bet_percentage as u64 / 100
+    }
+    
+    /// Move the platform fee out of the prize pool once a winner is known
+    /// so the vault always holds exactly the prize pool plus unclaimed fees.
+    /// Returns the fee and the creator's share of it.
+    pub fn settle_platform_fee(&mut self) -> (u64, u64) {
+        let platform_fee = self.fee_base() * self.config.platform_fee_percentage / 100;
+        self.prize_pool -= platform_fee;
+        (platform_fee, self.accrue_platform_fee(platform_fee))
+    }
+    
+    /// Part of the prize pool the platform takes its fee from - sponsor
+    /// funds count only if the sponsor opted in
+    pub fn fee_base(&self) -> u64 {
+        self.prize_pool.saturating_sub(self.sponsor_fee_exempt)
+    }
+    
+    /// Book `fee` as platform fee, setting the creator's share aside for
+    /// `claim_creator_fee`. Returns the creator's share.
+    pub fn accrue_platform_fee(&mut self, fee: u64) -> u64 {
+        let creator_fee = fee * self.creator_fee_bps as u64 / BPS_DENOMINATOR;
+        self.platform_fee_collected += fee - creator_fee;
+        self.creator_fee_collected += creator_fee;
+        creator_fee
+    }
+    
+    /// Creator missed their obligations: once every other player has been
+    /// refunded, the creator's forfeited stake becomes a platform fee
+    pub fn forfeit_creator_stake(&mut self) {
+        // The creator can't claim a refund once the game expired with penalty
+        if !self.has_status(GameStatus::ExpiredWithPenalty) || self.creator_joined == 0 {
+            return;
+        }
+        
+        // Sponsor funds stay in the pool for `refund_sponsor`
+        if self.refunded_count + 1 == self.current_players {
+            self.platform_fee_collected += self.prize_pool - self.sponsored_amount;
+            self.prize_pool = self.sponsored_amount;
+        }
+    }
+    
+    /// Move the game into a final status and remember when it happened
+    pub fn finish(&mut self, status: GameStatus, now: i64) {
+        self.status = status as u8;
+        self.finished_at = now;
+    }
+    
+    /// Whether per-player accounts (player states, challenges, ready states)
+    /// are no longer needed. A game that completed without a winner still
+    /// needs player states until the admin purge pays out the pool.
+    pub fn is_finished(&self) -> bool {
+        let purge_pending = self.has_status(GameStatus::Completed)
+            && self.winner().is_none()
+            && self.prize_pool > 0;
+        self.has_final_status() && !purge_pending
+    }
+    
+    /// Whether every stake has been paid out and the game can be closed
+    pub fn is_settled(&self) -> bool {
+        self.has_final_status()
+            && self.prize_pool == 0
+            && self.platform_fee_collected == 0
+            && self.creator_fee_collected == 0
+            && self.waitlist_escrow == 0
+    }
+    
     /// Calculate Phase 2 game requirements based on player count and duration
     /// More players = more games needed, longer phase = more games allowed
     pub fn calculate_phase2_requirements(&self) -> (u8, u8) {
@@ -69,7 +341,7 @@ impl Game {
         };
         
         let adjusted_requirement = (base_requirement as f64 * time_multiplier).floor() as u8;
-        let required_games = adjusted_requirement.max(3).min(10);
+        let required_games = adjusted_requirement.clamp(self.config.min_phase2_games, self.config.max_phase2_games);
         
         // Max games per opponent prevents farming same player
         let max_per_opponent = if total_players <= 5.0 {
@@ -86,22 +358,150 @@ impl Game {
     }
 }
 
+/// Custom rules a creator picks when creating a game
+/// Checked against the GlobalConfig snapshot and the admin's rule bounds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct GameRules {
+    pub phase1_duration: u64,
+    pub phase2_duration: u64,
+    pub phase3_duration: u64,
+    pub min_players: u16,
+    
+    // Phase 2 games each player must play (0 = derived from players and phase length)
+    pub phase2_required_games: u8,
+    
+    // Largest challenge bet as a percent of the challenger's balance
+    pub max_bet_percentage: u8,
+    
+    // Bitmask of allowed `MiniGameType`s
+    pub allowed_mini_games: u8,
+    
+    // Creator's share of the platform fee (basis points)
+    pub creator_fee_bps: u16,
+    
+    // Seconds before the start when players can no longer leave
+    pub leave_lock_window: i64,
+    
+    // Largest squad for team games, 0 for solo games
+    pub squad_size: u8,
+}
+
+impl GameRules {
+    /// Check the rules against the admin bounds for a game of `max_players`
+    pub fn validate(&self, config: &ConfigParams, bounds: &RuleBounds, max_players: u16) -> Result<()> {
+        let durations = bounds.min_phase_duration..=bounds.max_phase_duration;
+        
+        require!(
+            durations.contains(&self.phase1_duration)
+                && durations.contains(&self.phase2_duration)
+                && durations.contains(&self.phase3_duration),
+            GameError::InvalidGameRules
+        );
+        require!(
+            (config.min_players_to_start..=max_players).contains(&self.min_players),
+            GameError::InvalidGameRules
+        );
+        require!(
+            self.phase2_required_games == 0
+                || (config.min_phase2_games..=config.max_phase2_games).contains(&self.phase2_required_games),
+            GameError::InvalidGameRules
+        );
+        require!(
+            (1..=MAX_BET_PERCENTAGE).contains(&self.max_bet_percentage),
+            GameError::InvalidGameRules
+        );
+        require!(
+            self.allowed_mini_games != 0 && self.allowed_mini_games & !MiniGameType::ALL == 0,
+            GameError::InvalidGameRules
+        );
+        require!(
+            self.creator_fee_bps <= bounds.max_creator_fee_bps,
+            GameError::InvalidGameRules
+        );
+        require!(
+            (0..=MAX_LEAVE_LOCK_WINDOW).contains(&self.leave_lock_window),
+            GameError::InvalidGameRules
+        );
+        // Squad games need room for two squads of the minimum size
+        require!(
+            self.squad_size == 0
+                || ((MIN_SQUAD_SIZE..=MAX_SQUAD_SIZE as u8).contains(&self.squad_size)
+                    && self.min_players >= 2 * MIN_SQUAD_SIZE as u16),
+            GameError::InvalidGameRules
+        );
+        
+        Ok(())
+    }
+}
+
 /// Phase duration configuration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[zero_copy]
+#[derive(AnchorDeserialize)]
 pub struct PhaseDurations {
     pub phase1_duration: u64,  // Resource management phase
     pub phase2_duration: u64,  // PvP challenge phase
     pub phase3_duration: u64,  // Final purge phase
 }
 
-/// Game lifecycle status
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+/// Who may enter a game, stored on `Game` as its u8 discriminant
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+#[repr(u8)]
+pub enum AccessMode {
+    Public,                 // Anyone can enter
+    Allowlist,              // Wallets in the creator's Merkle allowlist
+    InviteCode,             // Anyone holding the invite code
+}
+
+/// Access settings a creator picks when creating a game
+/// `root` is the allowlist Merkle root or the invite code hash, zero for public games
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct GameAccess {
+    pub mode: AccessMode,
+    pub root: [u8; 32],
+}
+
+impl GameAccess {
+    /// Private games need a root; public games must not carry one
+    pub fn validate(&self) -> Result<()> {
+        let has_root = self.root != [0; 32];
+        require!(
+            has_root == (self.mode != AccessMode::Public),
+            GameError::InvalidGameAccess
+        );
+        Ok(())
+    }
+}
+
+/// Allowlist leaf for a wallet - leaves and nodes are domain-separated so a
+/// node can't be passed off as a leaf
+fn allowlist_leaf(player: &Pubkey) -> [u8; 32] {
+    hashv(&[&[0], player.as_ref()]).to_bytes()
+}
+
+/// Root of the allowlist tree implied by `player` and its Merkle `proof`
+/// Sibling pairs are hashed in sorted order, so proofs carry no directions
+pub fn allowlist_root(player: &Pubkey, proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(allowlist_leaf(player), |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[&[1], &left, &right]).to_bytes()
+    })
+}
+
+/// Hash stored for invite-only games
+/// The code itself is revealed on-chain by the first entry that uses it
+pub fn invite_code_hash(code: &str) -> [u8; 32] {
+    hashv(&[code.as_bytes()]).to_bytes()
+}
+
+/// Game lifecycle status, stored on `Game` as its u8 discriminant
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+#[repr(u8)]
 pub enum GameStatus {
     WaitingForPlayers,      // Accepting new players
     ReadyToStart,           // Full but not started yet
     InProgress,             // Game is active
     Completed,              // Game finished, prizes claimed
     Cancelled,              // Creator cancelled
-    Expired,                // Registration period expired
+    Expired,                // Never set; reserved so later discriminants keep their values
     ExpiredWithPenalty,     // Expired with creator penalty
 }
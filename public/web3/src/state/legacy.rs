@@ -0,0 +1,202 @@
+// Legacy account layouts - kept only to migrate live accounts
+// Each Legacy* type is the layout of the deployed program
+
+use anchor_lang::prelude::*;
+use super::{
+    Challenge, ChallengeStatus, GamePoolState, GameRegistry, GameStatus, MiniGameType, OpponentRecord,
+    Phase3ReadyState, PhaseDurations, PlayerGameState, ResourceAllocations,
+};
+
+/// Borsh-serialized `Game` layout of the deployed program, before the
+/// account became zero-copy. Membership was stored inline and stakes were
+/// held on the game account itself. Read by `migrate_game`; never written
+#[derive(AnchorDeserialize)]
+pub struct LegacyGame {
+    pub game_id: u64,
+    pub name: String,
+    pub creator: Pubkey,
+    pub entry_fee: u64,
+    pub max_players: u8,
+    pub current_players: u8,
+    pub players: Vec<Pubkey>,
+    pub start_time: i64,
+    pub expire_time: i64,
+    pub status: GameStatus,
+    pub game_started: bool,
+    pub prize_pool: u64,
+    pub refunded_players: Vec<Pubkey>,
+    pub current_phase: u8,
+    pub phase_start_time: i64,
+    pub phase_end_time: i64,
+    pub phase_advance_deadline: i64,
+    pub phases: PhaseDurations,
+    pub phase2_required_games: u8,
+    pub phase2_max_games_per_opponent: u8,
+    pub phase3_ready_deadline: i64,
+    pub phase3_extended_deadline: i64,
+    pub phase3_players_ready: u8,
+    pub phase3_started: bool,
+    pub phase3_winner: Option<Pubkey>,
+    pub phase3_prize_claimed: bool,
+    pub platform_fee_collected: u64,
+}
+
+/// Deployed `GameRegistry`, before the version byte
+#[derive(AnchorDeserialize)]
+pub struct LegacyGameRegistry {
+    pub game_count: u64,
+    pub total_games_created: u64,
+    pub admin: Pubkey,
+}
+
+impl From<LegacyGameRegistry> for GameRegistry {
+    fn from(legacy: LegacyGameRegistry) -> Self {
+        Self {
+            version: Self::VERSION,
+            game_count: legacy.game_count,
+            total_games_created: legacy.total_games_created,
+            admin: legacy.admin,
+            pending_admin: None,
+            pending_admin_expires_at: None,
+            paused: false,
+            paused_at: 0,
+            total_paused: 0,
+        }
+    }
+}
+
+/// `PlayerGameState` before the version byte
+#[derive(AnchorDeserialize)]
+pub struct LegacyPlayerGameState {
+    pub player: Pubkey,
+    pub game_id: u64,
+    pub virtual_balance: u64,
+    pub total_earned: u64,
+    pub last_claim_time: i64,
+    pub has_active_allocation: bool,
+    pub allocations: ResourceAllocations,
+    pub phase2_games_played: u8,
+    pub phase2_games_won: u8,
+    pub phase2_opponents_played: Vec<OpponentRecord>,
+    pub phase2_requirement_met: bool,
+    pub phase2_penalty_applied: bool,
+    pub phase3_prize_claimed: bool,
+}
+
+impl From<LegacyPlayerGameState> for PlayerGameState {
+    fn from(legacy: LegacyPlayerGameState) -> Self {
+        Self {
+            version: Self::VERSION,
+            player: legacy.player,
+            game_id: legacy.game_id,
+            virtual_balance: legacy.virtual_balance,
+            total_earned: legacy.total_earned,
+            last_claim_time: legacy.last_claim_time,
+            has_active_allocation: legacy.has_active_allocation,
+            allocations: legacy.allocations,
+            phase2_games_played: legacy.phase2_games_played,
+            phase2_games_won: legacy.phase2_games_won,
+            phase2_opponents_played: legacy.phase2_opponents_played,
+            phase2_requirement_met: legacy.phase2_requirement_met,
+            phase2_penalty_applied: legacy.phase2_penalty_applied,
+            phase3_prize_claimed: legacy.phase3_prize_claimed,
+        }
+    }
+}
+
+/// `GamePoolState` before the version byte
+#[derive(AnchorDeserialize)]
+pub struct LegacyGamePoolState {
+    pub game_id: u64,
+    pub mining_pool_total: u64,
+    pub farming_pool_total: u64,
+    pub trading_pool_total: u64,
+    pub research_pool_total: u64,
+    pub social_pool_total: u64,
+    pub social_pool_participants: u32,
+    pub farming_season: u8,
+    pub trading_market_state: u8,
+    pub last_event_time: i64,
+}
+
+impl LegacyGamePoolState {
+    /// Current layout, with `payer` receiving the rent on close
+    pub fn upgrade(self, payer: Pubkey) -> GamePoolState {
+        GamePoolState {
+            version: GamePoolState::VERSION,
+            game_id: self.game_id,
+            mining_pool_total: self.mining_pool_total,
+            farming_pool_total: self.farming_pool_total,
+            trading_pool_total: self.trading_pool_total,
+            research_pool_total: self.research_pool_total,
+            social_pool_total: self.social_pool_total,
+            social_pool_participants: self.social_pool_participants,
+            farming_season: self.farming_season,
+            trading_market_state: self.trading_market_state,
+            last_event_time: self.last_event_time,
+            payer,
+        }
+    }
+}
+
+/// `Challenge` before the version byte
+#[derive(AnchorDeserialize)]
+pub struct LegacyChallenge {
+    pub challenge_id: u64,
+    pub game_id: u64,
+    pub challenger: Pubkey,
+    pub opponent: Pubkey,
+    pub bet_amount: u64,
+    pub game_type: MiniGameType,
+    pub status: ChallengeStatus,
+    pub created_at: i64,
+    pub accepted_at: Option<i64>,
+    pub game_started_at: Option<i64>,
+    pub winner: Option<Pubkey>,
+    pub opponent_decline_count: u8,
+}
+
+impl From<LegacyChallenge> for Challenge {
+    fn from(legacy: LegacyChallenge) -> Self {
+        Self {
+            version: Self::VERSION,
+            challenge_id: legacy.challenge_id,
+            game_id: legacy.game_id,
+            challenger: legacy.challenger,
+            opponent: legacy.opponent,
+            challenger_squad: None,
+            opponent_squad: None,
+            bet_amount: legacy.bet_amount,
+            game_type: legacy.game_type,
+            status: legacy.status,
+            created_at: legacy.created_at,
+            accepted_at: legacy.accepted_at,
+            game_started_at: legacy.game_started_at,
+            winner: legacy.winner,
+            opponent_decline_count: legacy.opponent_decline_count,
+        }
+    }
+}
+
+/// `Phase3ReadyState` before the version byte
+#[derive(AnchorDeserialize)]
+pub struct LegacyPhase3ReadyState {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub ready: bool,
+    pub marked_ready_at: i64,
+    pub bump: u8,
+}
+
+impl From<LegacyPhase3ReadyState> for Phase3ReadyState {
+    fn from(legacy: LegacyPhase3ReadyState) -> Self {
+        Self {
+            version: Self::VERSION,
+            game_id: legacy.game_id,
+            player: legacy.player,
+            ready: legacy.ready,
+            marked_ready_at: legacy.marked_ready_at,
+            bump: legacy.bump,
+        }
+    }
+}
@@ -6,6 +6,18 @@ pub mod player;
 pub mod pool;
 pub mod challenge;
 pub mod phase3;
+pub mod entry;
+pub mod legacy;
+pub mod config;
+pub mod council;
+pub mod roles;
+pub mod treasury;
+pub mod bond;
+pub mod waitlist;
+pub mod template;
+pub mod sponsor;
+pub mod squad;
+pub mod roster;
 
 // Re-export commonly used types
 pub use registry::*;
@@ -14,3 +26,15 @@ pub use player::*;
 pub use pool::*;
 pub use challenge::*;
 pub use phase3::*;
+pub use entry::*;
+pub use legacy::*;
+pub use config::*;
+pub use council::*;
+pub use roles::*;
+pub use treasury::*;
+pub use bond::*;
+pub use waitlist::*;
+pub use template::*;
+pub use sponsor::*;
+pub use squad::*;
+pub use roster::*;
@@ -5,7 +5,11 @@ use anchor_lang::prelude::*;
 /// Tracks which players have marked themselves ready for the purge
 /// Players must actively opt-in to participate in the final game
 #[account]
+#[derive(InitSpace)]
 pub struct Phase3ReadyState {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
     pub game_id: u64,
     pub player: Pubkey,
     pub ready: bool,
@@ -14,5 +18,9 @@ pub struct Phase3ReadyState {
 }
 
 impl Phase3ReadyState {
-    pub const SIZE: usize = 8 + 8 + 32 + 1 + 8 + 1;
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
 }
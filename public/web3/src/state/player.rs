@@ -1,11 +1,14 @@
 // Player state and resource management
 
 use anchor_lang::prelude::*;
-use crate::errors::GameError;
 
 /// Per-player game state tracking virtual balance and activities
 #[account]
+#[derive(InitSpace)]
 pub struct PlayerGameState {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
     pub player: Pubkey,
     pub game_id: u64,
     
@@ -21,6 +24,8 @@ pub struct PlayerGameState {
     // Phase 2: PvP tracking
     pub phase2_games_played: u8,
     pub phase2_games_won: u8,
+    // Room for the game's `max_tracked_opponents`, see `PlayerGameState::size`
+    #[max_len(0)]
     pub phase2_opponents_played: Vec<OpponentRecord>,
     pub phase2_requirement_met: bool,
     pub phase2_penalty_applied: bool,
@@ -30,6 +35,14 @@ pub struct PlayerGameState {
 }
 
 impl PlayerGameState {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator with room for `max_tracked` opponents
+    pub fn size(max_tracked: u8) -> usize {
+        8 + Self::INIT_SPACE + max_tracked as usize * OpponentRecord::INIT_SPACE
+    }
+    
     /// Check if player can challenge a specific opponent
     /// Respects the max games per opponent limit
     pub fn can_challenge_opponent(&self, opponent: &Pubkey, max_games: u8) -> bool {
@@ -43,7 +56,7 @@ impl PlayerGameState {
     }
     
     /// Record a completed mini-game
-    pub fn record_game_played(&mut self, opponent: &Pubkey, won: bool) -> Result<()> {
+    pub fn record_game_played(&mut self, opponent: &Pubkey, won: bool, max_tracked: u8) -> Result<()> {
         self.phase2_games_played += 1;
         
         if won {
@@ -57,8 +70,8 @@ impl PlayerGameState {
         {
             record.games_count += 1;
         } else {
-            // Only track up to 10 unique opponents to save space
-            if self.phase2_opponents_played.len() < 10 {
+            // Only track as many unique opponents as the account has room for
+            if self.phase2_opponents_played.len() < max_tracked as usize {
                 self.phase2_opponents_played.push(OpponentRecord {
                     opponent: *opponent,
                     games_count: 1,
@@ -77,7 +90,7 @@ impl PlayerGameState {
 }
 
 /// Resource allocation across different activities in Phase 1
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
 pub struct ResourceAllocations {
     pub mining: u64,      // Steady but slows with competition
     pub farming: u64,     // Seasonal bonuses
@@ -86,8 +99,8 @@ pub struct ResourceAllocations {
     pub social: u64,      // Collaboration bonus
 }
 
-/// Track games played against each opponent
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+/// Track games played against each opponent (the opposing squad in squad games)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct OpponentRecord {
     pub opponent: Pubkey,
     pub games_count: u8,
@@ -5,7 +5,11 @@ use anchor_lang::prelude::*;
 /// Global state tracking total allocations across all players
 /// Used to calculate competition-based rewards in Phase 1
 #[account]
+#[derive(InitSpace)]
 pub struct GamePoolState {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
     pub game_id: u64,
     
     // Total tokens allocated to each resource type
@@ -22,17 +26,23 @@ pub struct GamePoolState {
     pub farming_season: u8,        // 0-3: affects farming rewards
     pub trading_market_state: u8,  // 0=crash, 1=normal, 2=boom
     pub last_event_time: i64,      // Last time events were updated
+    
+    pub payer: Pubkey,             // Paid the rent, receives it back on close
 }
 
 impl GamePoolState {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+    
     /// Calculate mining difficulty based on total pool competition
     /// More tokens in mining = higher difficulty = lower rewards
-    pub fn get_mining_difficulty_factor(&self) -> f64 {
-        use crate::constants::MAX_MINING_DIFFICULTY;
-        
+    pub fn get_mining_difficulty_factor(&self, max_difficulty: f64) -> f64 {
         // Difficulty increases as more players mine
         let difficulty = (self.mining_pool_total as f64 / 10_000_000_000_000.0)
-            .min(MAX_MINING_DIFFICULTY);
+            .min(max_difficulty);
         
         1.0 - difficulty
     }
@@ -61,11 +71,9 @@ impl GamePoolState {
     
     /// Calculate social collaboration bonus
     /// More participants = better rewards for everyone
-    pub fn get_social_multiplier(&self) -> f64 {
-        use crate::constants::MAX_SOCIAL_BONUS;
-        
+    pub fn get_social_multiplier(&self, max_bonus: f64) -> f64 {
         let base_multiplier = 1.0;
-        let bonus = (self.social_pool_participants as f64 * 0.1).min(MAX_SOCIAL_BONUS);
+        let bonus = (self.social_pool_participants as f64 * 0.1).min(max_bonus);
         
         base_multiplier + bonus
     }
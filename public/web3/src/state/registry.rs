@@ -5,7 +5,11 @@ use anchor_lang::prelude::*;
 /// Central registry that keeps track of all games created
 /// and maintains the admin address for privileged operations
 #[account]
+#[derive(InitSpace)]
 pub struct GameRegistry {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
     /// Current count of active games (used for game ID generation)
     pub game_count: u64,
     
@@ -14,9 +18,31 @@ pub struct GameRegistry {
     
     /// Admin wallet address with emergency controls
     pub admin: Pubkey,
+    
+    /// Proposed new admin, set by `propose_admin` until it is accepted
+    pub pending_admin: Option<Pubkey>,
+    
+    /// When the pending proposal lapses (None = never)
+    pub pending_admin_expires_at: Option<i64>,
+    
+    /// Global emergency pause - halts play in every game
+    pub paused: bool,
+    
+    /// When the current global pause began
+    pub paused_at: i64,
+    
+    /// Total seconds the program has spent globally paused; games push their
+    /// deadlines back by whatever part of this they haven't applied yet
+    pub total_paused: i64,
 }
 
 impl GameRegistry {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+    
     /// Get the next game ID and increment the counter
     pub fn next_game_id(&mut self) -> u64 {
         let id = self.game_count;
@@ -24,4 +50,17 @@ impl GameRegistry {
         self.total_games_created += 1;
         id
     }
+    
+    /// Toggle the global pause; returns how long the pause lasted on resume
+    pub fn set_paused(&mut self, paused: bool, now: i64) -> i64 {
+        let paused_for = if paused { 0 } else { now - self.paused_at };
+        if paused {
+            self.paused_at = now;
+        } else {
+            self.total_paused += paused_for;
+            self.paused_at = 0;
+        }
+        self.paused = paused;
+        paused_for
+    }
 }
@@ -0,0 +1,85 @@
+// Role registry - maps keys to the privileged roles they hold
+
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ROLE_MEMBERS;
+use crate::errors::GameError;
+use crate::state::GameRegistry;
+
+/// Privileged roles, stored as bits of `RoleMember::roles`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// Manages role assignments and the emergency pause
+    Admin,
+    /// Starts and advances games
+    Operator,
+    /// Submits game results
+    Referee,
+    /// Collects platform fees
+    Treasurer,
+}
+
+impl Role {
+    /// Mask with every role set
+    pub const ALL: u8 = 0b1111;
+    
+    /// Bit of this role in a roles mask
+    pub fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// One key and the roles it holds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RoleMember {
+    pub key: Pubkey,
+    pub roles: u8,
+}
+
+/// Keys holding privileged roles; the registry admin implicitly holds all of them
+#[account]
+#[derive(InitSpace)]
+pub struct RoleRegistry {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    #[max_len(MAX_ROLE_MEMBERS)]
+    pub members: Vec<RoleMember>,
+    pub bump: u8,
+}
+
+impl RoleRegistry {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Roles mask held by `key`
+    pub fn roles_of(&self, key: &Pubkey) -> u8 {
+        self.members.iter().find(|m| m.key == *key).map_or(0, |m| m.roles)
+    }
+    
+    /// Replace the roles of `key`; an empty mask removes it
+    pub fn set_roles(&mut self, key: Pubkey, roles: u8) -> Result<()> {
+        self.members.retain(|m| m.key != key);
+        if roles != 0 {
+            require!(self.members.len() < MAX_ROLE_MEMBERS, GameError::RoleRegistryFull);
+            self.members.push(RoleMember { key, roles });
+        }
+        Ok(())
+    }
+}
+
+/// Fail unless `key` holds `role`. The registry admin holds every role, so
+/// privileged instructions keep working before any role is assigned.
+pub fn require_role(
+    game_registry: &GameRegistry,
+    roles: Option<&RoleRegistry>,
+    key: &Pubkey,
+    role: Role,
+) -> Result<()> {
+    let granted = *key == game_registry.admin
+        || roles.is_some_and(|roles| roles.roles_of(key) & role.mask() != 0);
+    require!(granted, GameError::MissingRole);
+    Ok(())
+}
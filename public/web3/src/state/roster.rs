@@ -0,0 +1,36 @@
+// Legacy roster - players of a migrated game still waiting for their entry
+
+use anchor_lang::prelude::*;
+
+/// Inline player list of a deployed game, seeded by game id
+/// `migrate_game` copies it here because the zero-copy `Game` has no room
+/// for it; `migrate_player_entries` turns it into `PlayerEntry` PDAs in
+/// batches and closes the roster once it is empty
+#[account]
+pub struct LegacyRoster {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    
+    // Players without a `PlayerEntry` yet, migrated from the back
+    pub players: Vec<RosterPlayer>,
+    pub bump: u8,
+}
+
+impl LegacyRoster {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator for `players` entries
+    pub fn size(players: usize) -> usize {
+        8 + 1 + 8 + 4 + players * RosterPlayer::INIT_SPACE + 1
+    }
+}
+
+/// A deployed game's player and whether their stake was already refunded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RosterPlayer {
+    pub player: Pubkey,
+    pub refunded: bool,
+}
@@ -0,0 +1,35 @@
+// Sponsor record - a partner's contribution to a game's prize pool
+
+use anchor_lang::prelude::*;
+use crate::constants::MAX_SPONSOR_NAME_LEN;
+
+/// Funds a sponsor added to a game, seeded by (game_id, sponsor)
+/// Repeat sponsorships by the same wallet add to one record
+#[account]
+#[derive(InitSpace)]
+pub struct SponsorRecord {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    pub sponsor: Pubkey,
+    
+    #[max_len(MAX_SPONSOR_NAME_LEN)]
+    pub display_name: String,
+    
+    // Amount the vault actually received (net of transfer fees)
+    pub amount: u64,
+    
+    // Whether the sponsor agreed to the platform fee being taken from these funds
+    pub fee_eligible: bool,
+    pub sponsored_at: i64,
+    pub bump: u8,
+}
+
+impl SponsorRecord {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
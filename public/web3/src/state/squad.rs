@@ -0,0 +1,84 @@
+// Squads - teams of players entering and winning a game together
+
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_SQUAD_NAME_LEN, MAX_SQUAD_SIZE};
+
+/// A team in a squad game, seeded by (game_id, leader)
+/// Members fill the slots in join order; slot `i` earns `shares[i]` of the
+/// prize if the squad wins. A leaver's share moves to the free last slot, so
+/// the remaining members keep theirs.
+#[account]
+#[derive(InitSpace)]
+pub struct Squad {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    pub leader: Pubkey,
+    
+    #[max_len(MAX_SQUAD_NAME_LEN)]
+    pub name: String,
+    
+    // Prize ratio per slot in basis points, chosen at creation
+    #[max_len(MAX_SQUAD_SIZE)]
+    pub shares: Vec<u16>,
+    
+    // Members in join order, the leader first
+    #[max_len(MAX_SQUAD_SIZE)]
+    pub members: Vec<Pubkey>,
+    
+    // Phase 3: marked ready by its first member
+    pub phase3_ready: bool,
+    
+    // Prize pool when the first member claimed, and a bit per member who did
+    pub prize_total: u64,
+    pub claimed_mask: u8,
+    
+    pub bump: u8,
+}
+
+impl Squad {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Slot of `player` in the squad
+    pub fn member_index(&self, player: &Pubkey) -> Option<usize> {
+        self.members.iter().position(|m| m == player)
+    }
+    
+    /// Member's cut of `prize`, by their slot's share of the filled slots
+    pub fn member_prize(&self, index: usize, prize: u64) -> u64 {
+        let filled: u64 = self.shares[..self.members.len()].iter().map(|s| *s as u64).sum();
+        (prize as u128 * self.shares[index] as u128 / filled as u128) as u64
+    }
+    
+    /// Whether every member has claimed their cut
+    pub fn all_claimed(&self) -> bool {
+        self.claimed_mask.count_ones() as usize == self.members.len()
+    }
+}
+
+/// Links a player to their squad, seeded by (game_id, player)
+/// One per player, so nobody can be in two squads of the same game
+#[account]
+#[derive(InitSpace)]
+pub struct SquadMember {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub squad: Pubkey,
+    pub bump: u8,
+}
+
+impl SquadMember {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
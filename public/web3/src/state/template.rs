@@ -0,0 +1,59 @@
+// Game template - settings and schedule for a recurring game
+
+use anchor_lang::prelude::*;
+use crate::constants::MAX_GAME_NAME_LEN;
+use crate::state::{GameAccess, GameRules};
+
+/// Recurring game owned by an operator, seeded by (operator, template_id)
+/// `spawn_from_template` creates the next game once it is within `lead_time`
+/// of `next_start_time`. Lamports above rent are the spawn budget that pays
+/// back whoever spawns a game for its rent and posts each game's creator bond.
+#[account]
+#[derive(InitSpace)]
+pub struct GameTemplate {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub template_id: u64,
+    pub operator: Pubkey,
+    
+    // Settings copied into every spawned game
+    #[max_len(MAX_GAME_NAME_LEN)]
+    pub name: String,
+    pub entry_fee: u64,
+    pub max_players: u16,
+    pub mint: Option<Pubkey>,
+    pub rules: GameRules,
+    pub access: GameAccess,
+    
+    // Schedule: seconds between starts, and how early a game opens for entries
+    pub cadence: i64,
+    pub lead_time: i64,
+    pub next_start_time: i64,
+    
+    pub games_spawned: u64,
+    pub last_game_id: Option<u64>,
+    pub active: bool,
+    pub bump: u8,
+}
+
+impl GameTemplate {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
+
+/// Template settings an operator passes to `create_template` / `update_template`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TemplateParams {
+    pub name: String,
+    pub entry_fee: u64,
+    pub max_players: u16,
+    pub rules: GameRules,
+    pub access: GameAccess,
+    pub cadence: i64,
+    pub lead_time: i64,
+    pub next_start_time: i64,
+}
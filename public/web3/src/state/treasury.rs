@@ -0,0 +1,55 @@
+// Program treasury - collects platform fees from every game
+
+use anchor_lang::prelude::*;
+
+/// Program-wide treasury PDA (`[b"treasury"]`)
+/// SOL fees are held as lamports on this account; token fees in its
+/// associated token account for each mint
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    /// Lamports collected from SOL games
+    pub total_sol_collected: u64,
+    
+    /// Lamports withdrawn by treasurers
+    pub total_sol_withdrawn: u64,
+    
+    pub bump: u8,
+}
+
+impl Treasury {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
+
+/// Cumulative fees one game paid into the treasury, for reporting
+/// Amounts are in the game's stake currency
+#[account]
+#[derive(InitSpace)]
+pub struct GameFeeRecord {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    
+    /// Stake mint (default for native SOL)
+    pub mint: Pubkey,
+    
+    pub total_collected: u64,
+    pub last_collected_at: i64,
+    pub bump: u8,
+}
+
+impl GameFeeRecord {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
@@ -0,0 +1,64 @@
+// Waitlist - queue of players holding a deposit for a full game
+
+use anchor_lang::prelude::*;
+
+/// Per-game waitlist queue, seeded by game id
+/// Entries are numbered in join order; `head` is the next one to promote.
+/// Entries can't leave the queue early, so every position in `head..tail`
+/// stays open until the game starts or is cancelled
+#[account]
+#[derive(InitSpace)]
+pub struct Waitlist {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    
+    // Next position to promote into a free seat
+    pub head: u32,
+    
+    // Position handed to the next player who joins
+    pub tail: u32,
+    
+    // Entries still holding a deposit (waiting or awaiting refund)
+    pub waiting: u32,
+    
+    // Paid the rent, receives it back once the queue is swept
+    pub payer: Pubkey,
+    pub bump: u8,
+}
+
+impl Waitlist {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
+
+/// A player's place in a game's waitlist, seeded by (game_id, player)
+/// Must stay at least as large as `PlayerEntry` - on promotion its rent
+/// pays back whoever funds the new entry
+#[account]
+#[derive(InitSpace)]
+pub struct WaitlistEntry {
+    /// Account layout version, bumped by the matching `migrate_*` instruction
+    pub version: u8,
+    
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub position: u32,
+    
+    // Deposit the vault actually received (net of transfer fees)
+    pub amount: u64,
+    pub joined_at: i64,
+    pub bump: u8,
+}
+
+impl WaitlistEntry {
+    /// Current account layout version
+    pub const VERSION: u8 = 1;
+    
+    /// Account size including the discriminator
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+}
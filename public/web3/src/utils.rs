@@ -1,7 +1,12 @@
 // Helper functions and utilities used across the program
 
 use anchor_lang::prelude::*;
-use crate::state::{Game, PlayerGameState, Phase3ReadyState};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Game, PlayerGameState, Phase3ReadyState, Squad};
 use crate::errors::GameError;
 
 /// Count how many players are eligible for Phase 3
@@ -9,8 +14,8 @@ use crate::errors::GameError;
 pub fn count_eligible_players_for_phase3<'info>(
     remaining_accounts: &'info [AccountInfo<'info>],
     game: &Game,
-) -> Result<u8> {
-    let mut count = 0u8;
+) -> Result<u16> {
+    let mut count = 0u16;
     
     for account_info in remaining_accounts {
         if let Ok(player_state) = Account::<PlayerGameState>::try_from(account_info) {
@@ -39,6 +44,22 @@ pub fn find_ready_player<'info>(
     Err(GameError::ReadyPlayerNotFound.into())
 }
 
+/// Find the first ready squad in the remaining accounts (squad games)
+pub fn find_ready_squad<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    game: &Game,
+) -> Result<Pubkey> {
+    for account_info in remaining_accounts {
+        if let Ok(squad) = Account::<Squad>::try_from(account_info) {
+            if squad.phase3_ready && squad.game_id == game.game_id {
+                return Ok(squad.key());
+            }
+        }
+    }
+    
+    Err(GameError::ReadyPlayerNotFound.into())
+}
+
 /// Calculate the current effective time for reward claims
 /// Returns the earlier of: current time or phase end time
 pub fn get_effective_claim_time(current_time: i64, phase_end: i64) -> i64 {
@@ -58,3 +79,241 @@ pub fn seconds_to_hours(seconds: i64) -> f64 {
 pub fn has_time_passed(current_time: i64, reference_time: i64, required_duration: i64) -> bool {
     current_time >= reference_time + required_duration
 }
+
+/// Token-2022 mint extensions that break escrow: the vault could be frozen,
+/// drained by a third party, or made unable to pay out
+const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 5] = [
+    ExtensionType::NonTransferable,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::TransferHook,
+    ExtensionType::Pausable,
+    ExtensionType::DefaultAccountState,
+];
+
+/// Reject mints whose extensions would let someone other than the game
+/// control the vault. Classic SPL mints carry no extensions.
+pub fn validate_stake_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    
+    for extension in state.get_extension_types()? {
+        require!(
+            !UNSUPPORTED_MINT_EXTENSIONS.contains(&extension),
+            GameError::UnsupportedMintExtension
+        );
+    }
+    
+    Ok(())
+}
+
+/// Mint, vault and token program used to move stakes of a token game
+pub struct GameTokenAccounts<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Accounts holding a game's stakes
+/// SOL games keep stakes in the `vault` PDA; token games in the token vault
+pub struct GameVault<'a, 'info> {
+    pub game: &'a AccountLoader<'info, Game>,
+    pub vault: &'a SystemAccount<'info>,
+    pub system_program: &'a Program<'info, System>,
+    pub token: Option<GameTokenAccounts<'a, 'info>>,
+}
+
+/// Validate the vault accounts passed to an instruction
+/// The SOL vault PDA is checked by account constraints; token accounts are
+/// required for token games and ignored for SOL games
+pub fn game_vault<'a, 'info>(
+    game_account: &'a AccountLoader<'info, Game>,
+    game: &Game,
+    vault: &'a SystemAccount<'info>,
+    system_program: &'a Program<'info, System>,
+    mint: &'a Option<InterfaceAccount<'info, Mint>>,
+    token_vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+) -> Result<GameVault<'a, 'info>> {
+    let token = match game.stake_mint() {
+        None => None,
+        Some(game_mint) => {
+            let mint = mint.as_ref().ok_or(GameError::MissingTokenAccounts)?;
+            let token_vault = token_vault.as_ref().ok_or(GameError::MissingTokenAccounts)?;
+            let token_program = token_program.as_ref().ok_or(GameError::MissingTokenAccounts)?;
+            
+            require_keys_eq!(mint.key(), game_mint, GameError::InvalidTokenAccount);
+            require_keys_eq!(
+                token_vault.key(),
+                get_associated_token_address_with_program_id(&game_account.key(), &game_mint, &token_program.key()),
+                GameError::InvalidTokenAccount
+            );
+            
+            Some(GameTokenAccounts { mint, vault: token_vault, token_program })
+        }
+    };
+    
+    Ok(GameVault { game: game_account, vault, system_program, token })
+}
+
+/// Check that a user token account holds the game's mint and belongs to `owner`
+pub fn user_token_account<'a, 'info>(
+    game: &Game,
+    token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    owner: &Pubkey,
+) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+    let token_account = token_account.as_ref().ok_or(GameError::MissingTokenAccounts)?;
+    
+    require!(
+        Some(token_account.mint) == game.stake_mint() && token_account.owner == *owner,
+        GameError::InvalidTokenAccount
+    );
+    
+    Ok(token_account)
+}
+
+/// Current token balance of a token account, read after a CPI changed it
+fn token_balance(token_account: &InterfaceAccount<TokenAccount>) -> Result<u64> {
+    let info = token_account.to_account_info();
+    let data = info.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// Stake balance held by the vault, excluding the SOL vault's rent reserve
+pub fn vault_balance(vault: &GameVault) -> Result<u64> {
+    match &vault.token {
+        Some(accounts) => token_balance(accounts.vault),
+        None => {
+            let rent_reserve = Rent::get()?.minimum_balance(0);
+            Ok(vault.vault.lamports().saturating_sub(rent_reserve))
+        }
+    }
+}
+
+/// Vault invariant: the vault must hold at least what the game owes
+/// (`prize_pool` plus unclaimed platform and creator fees). Anything above
+/// that was sent to the vault directly and is not tracked by the game.
+pub fn check_vault_balance(game: &Game, vault: &GameVault) -> Result<()> {
+    let owed = game.prize_pool
+        .checked_add(game.platform_fee_collected)
+        .and_then(|owed| owed.checked_add(game.creator_fee_collected))
+        .and_then(|owed| owed.checked_add(game.waitlist_escrow))
+        .ok_or(GameError::MathOverflow)?;
+    
+    require!(vault_balance(vault)? >= owed, GameError::VaultBalanceMismatch);
+    
+    Ok(())
+}
+
+/// Move `amount` of stake currency from a player into the game vault
+/// `source` is the player's token account for token games.
+/// Returns the amount the vault actually received - for Token-2022 mints with
+/// a transfer fee this is less than `amount`.
+pub fn deposit_to_game<'info>(
+    vault: &GameVault<'_, 'info>,
+    depositor: &Signer<'info>,
+    source: Option<&InterfaceAccount<'info, TokenAccount>>,
+    amount: u64,
+) -> Result<u64> {
+    match (&vault.token, source) {
+        (None, _) => {
+            system_program::transfer(
+                CpiContext::new(
+                    vault.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: depositor.to_account_info(),
+                        to: vault.vault.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            Ok(amount)
+        }
+        (Some(accounts), Some(source)) => {
+            let balance_before = accounts.vault.amount;
+            
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: source.to_account_info(),
+                        mint: accounts.mint.to_account_info(),
+                        to: accounts.vault.to_account_info(),
+                        authority: depositor.to_account_info(),
+                    },
+                ),
+                amount,
+                accounts.mint.decimals,
+            )?;
+            
+            token_balance(accounts.vault)?
+                .checked_sub(balance_before)
+                .ok_or(GameError::MathOverflow.into())
+        }
+        (Some(_), None) => Err(GameError::MissingTokenAccounts.into()),
+    }
+}
+
+/// Pay `amount` of stake currency out of the game vault
+/// SOL games sign with the vault PDA seeds; token games sign the vault
+/// transfer with the game PDA seeds. `recipient` is the destination wallet
+/// for SOL games and the destination token account for token games.
+/// The game account must not be borrowed while paying out tokens.
+pub fn pay_from_game<'info>(
+    game: &Game,
+    vault: &GameVault<'_, 'info>,
+    recipient: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let game_id = game.game_id.to_le_bytes();
+    
+    match &vault.token {
+        None => {
+            let seeds: &[&[u8]] = &[b"vault", game_id.as_ref(), &[game.vault_bump]];
+            
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    vault.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: vault.vault.to_account_info(),
+                        to: recipient.clone(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            )
+        }
+        Some(accounts) => {
+            let seeds: &[&[u8]] = &[b"game", game_id.as_ref(), &[game.bump]];
+            
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.vault.to_account_info(),
+                        mint: accounts.mint.to_account_info(),
+                        to: recipient.clone(),
+                        authority: vault.game.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+                accounts.mint.decimals,
+            )
+        }
+    }
+}
+
+/// Recipient account for a payout: the owner's token account for token
+/// games, the owner's wallet for SOL games
+pub fn payout_recipient<'info>(
+    game: &Game,
+    vault: &GameVault<'_, 'info>,
+    wallet: &AccountInfo<'info>,
+    token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+) -> Result<AccountInfo<'info>> {
+    match vault.token {
+        Some(_) => Ok(user_token_account(game, token_account, wallet.key)?.to_account_info()),
+        None => Ok(wallet.clone()),
+    }
+}
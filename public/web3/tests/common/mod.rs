@@ -0,0 +1,539 @@
+// Shared fixtures for the instruction tests
+// Runs the built program (`cargo test-sbf`) in LiteSVM with the registry and
+// global config initialized, and builds the instructions the tests send.
+
+#![allow(dead_code, clippy::result_large_err)]
+
+use std::path::{Path, PathBuf};
+
+use anchor_lang::{
+    prelude::{Clock, Pubkey},
+    solana_program::instruction::{error::InstructionError, AccountMeta, Instruction},
+    system_program, AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_survivor::{
+    accounts, instruction,
+    errors::GameError,
+    state::{AccessMode, Game, GameAccess, GameRegistry, GameRules, MiniGameType},
+};
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+pub const ENTRY_FEE: u64 = LAMPORTS_PER_SOL;
+pub const GENESIS_TIME: i64 = 1_700_000_000;
+pub const START_DELAY: i64 = 3_600;
+
+/// Rules accepted by the default global config, for a solo game
+pub fn rules(min_players: u16) -> GameRules {
+    GameRules {
+        phase1_duration: 600,
+        phase2_duration: 600,
+        phase3_duration: 600,
+        min_players,
+        phase2_required_games: 0,
+        max_bet_percentage: 50,
+        allowed_mini_games: MiniGameType::ALL,
+        creator_fee_bps: 0,
+        leave_lock_window: 600,
+        squad_size: 0,
+    }
+}
+
+// ==================== PDAS ====================
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &solana_survivor::ID).0
+}
+
+pub fn registry_pda() -> Pubkey {
+    pda(&[b"game_registry"])
+}
+
+pub fn config_pda() -> Pubkey {
+    pda(&[b"global_config"])
+}
+
+pub fn game_pda(game_id: u64) -> Pubkey {
+    pda(&[b"game", &game_id.to_le_bytes()])
+}
+
+pub fn vault_pda(game_id: u64) -> Pubkey {
+    pda(&[b"vault", &game_id.to_le_bytes()])
+}
+
+pub fn bond_pda(game_id: u64) -> Pubkey {
+    pda(&[b"creator_bond", &game_id.to_le_bytes()])
+}
+
+pub fn entry_pda(game_id: u64, player: &Pubkey) -> Pubkey {
+    pda(&[b"player_entry", &game_id.to_le_bytes(), player.as_ref()])
+}
+
+pub fn waitlist_pda(game_id: u64) -> Pubkey {
+    pda(&[b"waitlist", &game_id.to_le_bytes()])
+}
+
+pub fn waitlist_entry_pda(game_id: u64, player: &Pubkey) -> Pubkey {
+    pda(&[b"waitlist_entry", &game_id.to_le_bytes(), player.as_ref()])
+}
+
+pub fn squad_pda(game_id: u64, leader: &Pubkey) -> Pubkey {
+    pda(&[b"squad", &game_id.to_le_bytes(), leader.as_ref()])
+}
+
+pub fn squad_member_pda(game_id: u64, player: &Pubkey) -> Pubkey {
+    pda(&[b"squad_member", &game_id.to_le_bytes(), player.as_ref()])
+}
+
+// ==================== INSTRUCTIONS ====================
+
+fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: solana_survivor::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn enter_ix(game_id: u64, player: &Pubkey) -> Instruction {
+    ix(
+        accounts::EnterGame {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            player_entry: entry_pda(game_id, player),
+            player: *player,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            player_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::EnterGame { proof: vec![], invite_code: String::new() },
+    )
+}
+
+/// `promote` is the waitlist player whose seat the leave frees, if any
+pub fn leave_ix(game_id: u64, player: &Pubkey, promote: Option<&Pubkey>) -> Instruction {
+    ix(
+        accounts::LeaveGame {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            player_entry: entry_pda(game_id, player),
+            player: *player,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            player_token_account: None,
+            token_program: None,
+            waitlist: promote.map(|_| waitlist_pda(game_id)),
+            waitlist_entry: promote.map(|next| waitlist_entry_pda(game_id, next)),
+            promoted_entry: promote.map(|next| entry_pda(game_id, next)),
+            squad_member: None,
+            system_program: system_program::ID,
+        },
+        instruction::LeaveGame {},
+    )
+}
+
+pub fn join_waitlist_ix(game_id: u64, player: &Pubkey) -> Instruction {
+    ix(
+        accounts::JoinWaitlist {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            waitlist: waitlist_pda(game_id),
+            waitlist_entry: waitlist_entry_pda(game_id, player),
+            player_entry: entry_pda(game_id, player),
+            player: *player,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            player_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::JoinWaitlist { proof: vec![], invite_code: String::new() },
+    )
+}
+
+pub fn refund_waitlist_ix(game_id: u64, player: &Pubkey) -> Instruction {
+    ix(
+        accounts::RefundWaitlist {
+            game: game_pda(game_id),
+            waitlist: waitlist_pda(game_id),
+            waitlist_entry: waitlist_entry_pda(game_id, player),
+            player: *player,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            player_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::RefundWaitlist {},
+    )
+}
+
+pub fn start_ix(game_id: u64, creator: &Pubkey) -> Instruction {
+    ix(
+        accounts::StartGame {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            creator_bond: Some(bond_pda(game_id)),
+            creator: *creator,
+        },
+        instruction::StartGame {},
+    )
+}
+
+pub fn cancel_ix(game_id: u64, creator: &Pubkey) -> Instruction {
+    ix(
+        accounts::CreatorCancelGame {
+            game: game_pda(game_id),
+            creator_bond: Some(bond_pda(game_id)),
+            creator: *creator,
+        },
+        instruction::CreatorCancelGame {},
+    )
+}
+
+pub fn claim_refund_ix(game_id: u64, player: &Pubkey, with_bond: bool) -> Instruction {
+    ix(
+        accounts::ClaimRefund {
+            game: game_pda(game_id),
+            player_entry: entry_pda(game_id, player),
+            creator_bond: with_bond.then(|| bond_pda(game_id)),
+            player: *player,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            player_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimRefund {},
+    )
+}
+
+pub fn force_refund_ix(game_id: u64, player: &Pubkey, with_bond: bool) -> Instruction {
+    ix(
+        accounts::ForceRefund {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            player_entry: entry_pda(game_id, player),
+            creator_bond: with_bond.then(|| bond_pda(game_id)),
+            player: *player,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            player_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::ForceRefundExpiredGame {},
+    )
+}
+
+pub fn crank_refunds_ix(game_id: u64, cranker: &Pubkey, players: &[Pubkey], with_bond: bool) -> Instruction {
+    let mut crank = ix(
+        accounts::CrankRefunds {
+            game: game_pda(game_id),
+            creator_bond: with_bond.then(|| bond_pda(game_id)),
+            cranker: *cranker,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::CrankRefunds {},
+    );
+    for player in players {
+        crank.accounts.push(AccountMeta::new(entry_pda(game_id, player), false));
+        crank.accounts.push(AccountMeta::new(*player, false));
+    }
+    crank
+}
+
+pub fn close_game_ix(game_id: u64, creator: &Pubkey, with_bond: bool) -> Instruction {
+    ix(
+        accounts::CloseGame {
+            game: game_pda(game_id),
+            creator: *creator,
+            creator_bond: with_bond.then(|| bond_pda(game_id)),
+            treasury: None,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            creator_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::CloseGame {},
+    )
+}
+
+pub fn claim_prize_ix(game_id: u64, winner: &Pubkey) -> Instruction {
+    ix(
+        accounts::ClaimPhase3Prize {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            winner: *winner,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            winner_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimPhase3Prize {},
+    )
+}
+
+pub fn create_squad_ix(game_id: u64, leader: &Pubkey, shares: Vec<u16>) -> Instruction {
+    ix(
+        accounts::CreateSquad {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            squad: squad_pda(game_id, leader),
+            squad_member: squad_member_pda(game_id, leader),
+            player_entry: entry_pda(game_id, leader),
+            leader: *leader,
+            system_program: system_program::ID,
+        },
+        instruction::CreateSquad { name: "Squad".to_string(), shares },
+    )
+}
+
+pub fn join_squad_ix(game_id: u64, leader: &Pubkey, player: &Pubkey) -> Instruction {
+    ix(
+        accounts::JoinSquad {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            squad: squad_pda(game_id, leader),
+            squad_member: squad_member_pda(game_id, player),
+            player_entry: entry_pda(game_id, player),
+            player: *player,
+            system_program: system_program::ID,
+        },
+        instruction::JoinSquad {},
+    )
+}
+
+pub fn leave_squad_ix(game_id: u64, leader: &Pubkey, player: &Pubkey) -> Instruction {
+    ix(
+        accounts::LeaveSquad {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            squad: squad_pda(game_id, leader),
+            squad_member: squad_member_pda(game_id, player),
+            player: *player,
+        },
+        instruction::LeaveSquad {},
+    )
+}
+
+pub fn claim_squad_prize_ix(game_id: u64, leader: &Pubkey, member: &Pubkey) -> Instruction {
+    ix(
+        accounts::ClaimSquadPrize {
+            game: game_pda(game_id),
+            game_registry: registry_pda(),
+            squad: squad_pda(game_id, leader),
+            member: *member,
+            vault: vault_pda(game_id),
+            mint: None,
+            token_vault: None,
+            member_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimSquadPrize {},
+    )
+}
+
+// ==================== CONTEXT ====================
+
+/// The built program, loaded from `SBF_OUT_DIR` (set by `cargo test-sbf`)
+fn program_path() -> PathBuf {
+    std::env::var("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| Path::new(env!("CARGO_MANIFEST_DIR")).join("target/deploy"))
+        .join("solana_survivor.so")
+}
+
+/// Assert that a transaction failed with `error` from its first instruction
+pub fn assert_game_error(result: TransactionResult, error: GameError) {
+    let code = u32::from(error);
+    match result {
+        Ok(_) => panic!("expected error {code}, the transaction succeeded"),
+        Err(failed) => assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(0, InstructionError::Custom(code)),
+            "{:#?}",
+            failed.meta.logs
+        ),
+    }
+}
+
+/// LiteSVM with the program deployed and the registry and global config
+/// initialized. The admin pays every transaction's fee, so the balances of
+/// the other signers only move by what the program pays or charges.
+pub struct TestContext {
+    pub svm: LiteSVM,
+    pub admin: Keypair,
+}
+
+impl TestContext {
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(solana_survivor::ID, program_path())
+            .expect("build the program with `cargo build-sbf` first");
+
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), 100 * LAMPORTS_PER_SOL).unwrap();
+        let mut ctx = Self { svm, admin };
+        ctx.warp_to(GENESIS_TIME);
+
+        let admin = ctx.admin.pubkey();
+        ctx.send(
+            ix(
+                accounts::Initialize {
+                    game_registry: registry_pda(),
+                    authority: admin,
+                    system_program: system_program::ID,
+                },
+                instruction::Initialize { admin },
+            ),
+            &[],
+        )
+        .unwrap();
+        ctx.send(
+            ix(
+                accounts::InitializeGlobalConfig {
+                    global_config: config_pda(),
+                    game_registry: registry_pda(),
+                    admin,
+                    system_program: system_program::ID,
+                },
+                instruction::InitializeGlobalConfig {},
+            ),
+            &[],
+        )
+        .unwrap();
+
+        ctx
+    }
+
+    /// A new wallet holding 10 SOL
+    pub fn funded_keypair(&mut self) -> Keypair {
+        let keypair = Keypair::new();
+        self.svm.airdrop(&keypair.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        keypair
+    }
+
+    /// Send `instruction` signed by the admin (fee payer) and `signers`
+    pub fn send(&mut self, instruction: Instruction, signers: &[&Keypair]) -> TransactionResult {
+        // A fresh blockhash so a repeated instruction isn't rejected as a duplicate
+        self.svm.expire_blockhash();
+        let mut all_signers = vec![&self.admin];
+        all_signers.extend(signers.iter().filter(|signer| signer.pubkey() != self.admin.pubkey()));
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.admin.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx)
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    pub fn balance(&self, key: &Pubkey) -> u64 {
+        self.svm.get_balance(key).unwrap_or(0)
+    }
+
+    pub fn rent(&self, space: usize) -> u64 {
+        self.svm.minimum_balance_for_rent_exemption(space)
+    }
+
+    /// Deserialize an Anchor account, `None` once it is closed
+    pub fn account<T: AccountDeserialize>(&self, key: &Pubkey) -> Option<T> {
+        let account = self.svm.get_account(key).filter(|account| account.lamports > 0)?;
+        Some(T::try_deserialize(&mut account.data.as_slice()).unwrap())
+    }
+
+    pub fn game(&self, game_id: u64) -> Game {
+        let account = self.svm.get_account(&game_pda(game_id)).expect("game account");
+        bytemuck::pod_read_unaligned(&account.data[8..Game::SIZE])
+    }
+
+    /// Overwrite the game's state, for paths that would otherwise need a full
+    /// three-phase game to reach
+    pub fn patch_game(&mut self, game_id: u64, patch: impl FnOnce(&mut Game)) {
+        let key = game_pda(game_id);
+        let mut game = self.game(game_id);
+        patch(&mut game);
+        let mut account = self.svm.get_account(&key).unwrap();
+        account.data[8..Game::SIZE].copy_from_slice(bytemuck::bytes_of(&game));
+        self.svm.set_account(key, account).unwrap();
+    }
+
+    /// Create a public SOL game starting in an hour, returning its id
+    pub fn create_game(&mut self, creator: &Keypair, max_players: u16, rules: GameRules) -> u64 {
+        let registry: GameRegistry = self.account(&registry_pda()).unwrap();
+        let game_id = registry.game_count;
+        let start_time = self.now() + START_DELAY;
+        self.send(
+            ix(
+                accounts::CreateGame {
+                    game: game_pda(game_id),
+                    game_registry: registry_pda(),
+                    global_config: config_pda(),
+                    creator_bond: bond_pda(game_id),
+                    creator: creator.pubkey(),
+                    vault: vault_pda(game_id),
+                    mint: None,
+                    token_vault: None,
+                    token_program: None,
+                    associated_token_program: None,
+                    system_program: system_program::ID,
+                },
+                instruction::CreateGame {
+                    name: "Test game".to_string(),
+                    entry_fee: ENTRY_FEE,
+                    max_players,
+                    start_time,
+                    rules,
+                    access: GameAccess { mode: AccessMode::Public, root: [0; 32] },
+                },
+            ),
+            &[creator],
+        )
+        .unwrap();
+
+        game_id
+    }
+
+    /// Fund `count` new wallets and enter each into the game
+    pub fn enter_players(&mut self, game_id: u64, count: usize) -> Vec<Keypair> {
+        (0..count)
+            .map(|_| {
+                let player = self.funded_keypair();
+                self.send(enter_ix(game_id, &player.pubkey()), &[&player]).unwrap();
+                player
+            })
+            .collect()
+    }
+}
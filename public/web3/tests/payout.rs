@@ -0,0 +1,108 @@
+// Phase 3 prize payouts to solo and squad winners
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use solana_signer::Signer;
+use solana_survivor::{
+    errors::GameError,
+    state::{CreatorBond, GameRules, GameStatus, Squad},
+};
+
+/// Skip the three phases: the game is completed with `winner` declared and
+/// the platform fee taken out of the prize pool
+fn complete_game(ctx: &mut TestContext, game_id: u64, winner: Pubkey) -> u64 {
+    ctx.patch_game(game_id, |game| {
+        let fee = game.prize_pool * game.config.platform_fee_percentage / 100;
+        game.game_started = 1;
+        game.current_phase = 3;
+        game.status = GameStatus::Completed as u8;
+        game.phase3_winner = winner;
+        game.platform_fee_collected = fee;
+        game.prize_pool -= fee;
+    });
+    ctx.game(game_id).prize_pool
+}
+
+#[test]
+fn phase3_winner_claims_the_prize_pool() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.funded_keypair();
+    let game_id = ctx.create_game(&creator, 3, rules(3));
+    let players = ctx.enter_players(game_id, 3);
+
+    // Starting on time hands the bond back
+    let bond_lamports = ctx.balance(&bond_pda(game_id));
+    let start_time = ctx.game(game_id).start_time;
+    ctx.warp_to(start_time);
+    let before = ctx.balance(&creator.pubkey());
+    ctx.send(start_ix(game_id, &creator.pubkey()), &[&creator]).unwrap();
+    assert_eq!(ctx.balance(&creator.pubkey()) - before, bond_lamports);
+    assert!(ctx.account::<CreatorBond>(&bond_pda(game_id)).is_none());
+
+    let (winner, loser) = (&players[0], &players[1]);
+    let prize = complete_game(&mut ctx, game_id, winner.pubkey());
+    assert_game_error(
+        ctx.send(claim_prize_ix(game_id, &loser.pubkey()), &[loser]),
+        GameError::NotWinner,
+    );
+
+    let before = ctx.balance(&winner.pubkey());
+    ctx.send(claim_prize_ix(game_id, &winner.pubkey()), &[winner]).unwrap();
+    assert_eq!(ctx.balance(&winner.pubkey()) - before, prize);
+    assert_game_error(
+        ctx.send(claim_prize_ix(game_id, &winner.pubkey()), &[winner]),
+        GameError::AlreadyClaimed,
+    );
+
+    // Only the platform fee is left in the vault
+    let game = ctx.game(game_id);
+    assert_eq!(game.prize_pool, 0);
+    let reserve = ctx.rent(0);
+    assert_eq!(ctx.balance(&vault_pda(game_id)), reserve + game.platform_fee_collected);
+}
+
+#[test]
+fn squad_prize_follows_member_shares_after_a_member_leaves() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.funded_keypair();
+    let game_id = ctx.create_game(&creator, 5, GameRules { squad_size: 3, ..rules(4) });
+    let players = ctx.enter_players(game_id, 5);
+    let [leader, leaver, member] = [0, 1, 2].map(|index| players[index].pubkey());
+
+    ctx.send(create_squad_ix(game_id, &leader, vec![5_000, 3_000, 2_000]), &[&players[0]]).unwrap();
+    ctx.send(join_squad_ix(game_id, &leader, &leaver), &[&players[1]]).unwrap();
+    ctx.send(join_squad_ix(game_id, &leader, &member), &[&players[2]]).unwrap();
+
+    // The member who stays keeps the share they joined for
+    ctx.send(leave_squad_ix(game_id, &leader, &leaver), &[&players[1]]).unwrap();
+    let squad = ctx.account::<Squad>(&squad_pda(game_id, &leader)).unwrap();
+    assert_eq!(squad.members, vec![leader, member]);
+    assert_eq!(squad.shares, vec![5_000, 2_000, 3_000]);
+
+    let prize = complete_game(&mut ctx, game_id, squad_pda(game_id, &leader));
+    assert_game_error(
+        ctx.send(claim_squad_prize_ix(game_id, &leader, &leaver), &[&players[1]]),
+        GameError::NotSquadMember,
+    );
+
+    let leader_prize = (prize as u128 * 5_000 / 7_000) as u64;
+    let before = ctx.balance(&leader);
+    ctx.send(claim_squad_prize_ix(game_id, &leader, &leader), &[&players[0]]).unwrap();
+    assert_eq!(ctx.balance(&leader) - before, leader_prize);
+
+    // The last claim takes the rest, rounding dust included
+    let before = ctx.balance(&member);
+    ctx.send(claim_squad_prize_ix(game_id, &leader, &member), &[&players[2]]).unwrap();
+    assert_eq!(ctx.balance(&member) - before, prize - leader_prize);
+    assert_game_error(
+        ctx.send(claim_squad_prize_ix(game_id, &leader, &member), &[&players[2]]),
+        GameError::AlreadyClaimed,
+    );
+
+    let game = ctx.game(game_id);
+    assert_eq!(game.prize_pool, 0);
+    assert_eq!(game.phase3_prize_claimed, 1);
+}
@@ -0,0 +1,133 @@
+// Refunds of cancelled and expired games, and the creator bond
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use solana_signer::Signer;
+use solana_survivor::{
+    errors::GameError,
+    state::{CreatorBond, GameStatus, PlayerEntry},
+};
+
+#[test]
+fn cancelled_game_refunds_entries_and_returns_bond() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.funded_keypair();
+    let game_id = ctx.create_game(&creator, 5, rules(3));
+    let players = ctx.enter_players(game_id, 2);
+
+    // Cancelling in time closes the bond back to the creator
+    let bond_lamports = ctx.balance(&bond_pda(game_id));
+    let creator_before = ctx.balance(&creator.pubkey());
+    ctx.send(cancel_ix(game_id, &creator.pubkey()), &[&creator]).unwrap();
+    assert!(ctx.account::<CreatorBond>(&bond_pda(game_id)).is_none());
+    assert_eq!(ctx.balance(&creator.pubkey()) - creator_before, bond_lamports);
+    let game = ctx.game(game_id);
+    assert!(game.has_status(GameStatus::Cancelled));
+    assert_eq!(game.creator_bonded, 0);
+
+    for player in &players {
+        let before = ctx.balance(&player.pubkey());
+        ctx.send(claim_refund_ix(game_id, &player.pubkey(), false), &[player]).unwrap();
+        assert_eq!(ctx.balance(&player.pubkey()) - before, ENTRY_FEE);
+    }
+    assert_game_error(
+        ctx.send(claim_refund_ix(game_id, &players[0].pubkey(), false), &[&players[0]]),
+        GameError::AlreadyRefunded,
+    );
+
+    let game = ctx.game(game_id);
+    assert_eq!(game.refunded_count, 2);
+    assert!(game.is_settled());
+}
+
+#[test]
+fn expired_game_slashes_bond_to_refunded_players() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.funded_keypair();
+    let game_id = ctx.create_game(&creator, 5, rules(3));
+    let players = ctx.enter_players(game_id, 3);
+    let bond = ctx.account::<CreatorBond>(&bond_pda(game_id)).unwrap().amount;
+
+    // The creator never started the game
+    let game = ctx.game(game_id);
+    ctx.warp_to(game.start_time + game.config.game_start_grace_period);
+
+    // Forced refund: the first player takes a third of the bond
+    assert_game_error(
+        ctx.send(force_refund_ix(game_id, &players[0].pubkey(), false), &[&players[0]]),
+        GameError::CreatorBondRequired,
+    );
+    let before = ctx.balance(&players[0].pubkey());
+    ctx.send(force_refund_ix(game_id, &players[0].pubkey(), true), &[&players[0]]).unwrap();
+    let first_share = bond / 3;
+    assert_eq!(ctx.balance(&players[0].pubkey()) - before, ENTRY_FEE + first_share);
+    assert!(ctx.game(game_id).has_status(GameStatus::ExpiredWithPenalty));
+
+    // Claimed refund: half of what is left
+    assert_game_error(
+        ctx.send(claim_refund_ix(game_id, &players[1].pubkey(), false), &[&players[1]]),
+        GameError::CreatorBondRequired,
+    );
+    let before = ctx.balance(&players[1].pubkey());
+    ctx.send(claim_refund_ix(game_id, &players[1].pubkey(), true), &[&players[1]]).unwrap();
+    let second_share = (bond - first_share) / 2;
+    assert_eq!(ctx.balance(&players[1].pubkey()) - before, ENTRY_FEE + second_share);
+
+    // Cranked refund: the last player takes the rest, and the entry's rent minus the tip
+    let cranker = ctx.funded_keypair();
+    let last = players[2].pubkey();
+    assert_game_error(
+        ctx.send(crank_refunds_ix(game_id, &cranker.pubkey(), &[last], false), &[&cranker]),
+        GameError::CreatorBondRequired,
+    );
+    let entry_rent = ctx.balance(&entry_pda(game_id, &last));
+    let tip = ctx.game(game_id).config.crank_refund_tip;
+    let before = ctx.balance(&last);
+    let cranker_before = ctx.balance(&cranker.pubkey());
+    ctx.send(crank_refunds_ix(game_id, &cranker.pubkey(), &[last], true), &[&cranker]).unwrap();
+    let last_share = bond - first_share - second_share;
+    assert_eq!(ctx.balance(&last) - before, ENTRY_FEE + last_share + entry_rent - tip);
+    assert_eq!(ctx.balance(&cranker.pubkey()) - cranker_before, tip);
+    assert!(ctx.account::<PlayerEntry>(&entry_pda(game_id, &last)).is_none());
+    assert_eq!(ctx.account::<CreatorBond>(&bond_pda(game_id)).unwrap().amount, 0);
+
+    let game = ctx.game(game_id);
+    assert_eq!(game.refunded_count, 3);
+    assert!(game.is_settled());
+}
+
+#[test]
+fn closing_a_settled_game_returns_the_rent() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.funded_keypair();
+    let game_id = ctx.create_game(&creator, 5, rules(3));
+    let players = ctx.enter_players(game_id, 3);
+
+    let game = ctx.game(game_id);
+    ctx.warp_to(game.start_time + game.config.game_start_grace_period);
+    ctx.send(force_refund_ix(game_id, &players[0].pubkey(), true), &[&players[0]]).unwrap();
+
+    // Players are still owed their entries
+    assert_game_error(
+        ctx.send(close_game_ix(game_id, &creator.pubkey(), true), &[]),
+        GameError::GameNotSettled,
+    );
+    let rest: Vec<_> = players[1..].iter().map(|player| player.pubkey()).collect();
+    let cranker = ctx.funded_keypair();
+    ctx.send(crank_refunds_ix(game_id, &cranker.pubkey(), &rest, true), &[&cranker]).unwrap();
+
+    // The slashed bond account still has to be closed with the game
+    assert_game_error(
+        ctx.send(close_game_ix(game_id, &creator.pubkey(), false), &[]),
+        GameError::CreatorBondRequired,
+    );
+    let rent = ctx.balance(&game_pda(game_id)) + ctx.balance(&vault_pda(game_id)) + ctx.balance(&bond_pda(game_id));
+    let before = ctx.balance(&creator.pubkey());
+    ctx.send(close_game_ix(game_id, &creator.pubkey(), true), &[]).unwrap();
+    assert_eq!(ctx.balance(&creator.pubkey()) - before, rent);
+    assert_eq!(ctx.balance(&game_pda(game_id)), 0);
+    assert_eq!(ctx.balance(&vault_pda(game_id)), 0);
+    assert_eq!(ctx.balance(&bond_pda(game_id)), 0);
+}
@@ -0,0 +1,86 @@
+// Waitlist deposits: promotion into freed seats and refunds
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use solana_signer::Signer;
+use solana_survivor::{
+    errors::GameError,
+    state::{GameStatus, PlayerEntry, WaitlistEntry},
+};
+
+#[test]
+fn leaving_a_full_game_promotes_the_waitlist_head() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.funded_keypair();
+    let game_id = ctx.create_game(&creator, 3, rules(3));
+    let players = ctx.enter_players(game_id, 3);
+    assert!(ctx.game(game_id).has_status(GameStatus::ReadyToStart));
+
+    let queued: Vec<_> = (0..2).map(|_| ctx.funded_keypair()).collect();
+    for player in &queued {
+        ctx.send(join_waitlist_ix(game_id, &player.pubkey()), &[player]).unwrap();
+    }
+    assert_eq!(ctx.game(game_id).waitlist_escrow, 2 * ENTRY_FEE);
+
+    // The freed seat belongs to the head of the queue
+    let leaver = &players[0];
+    assert_game_error(
+        ctx.send(leave_ix(game_id, &leaver.pubkey(), None), &[leaver]),
+        GameError::WaitlistRequired,
+    );
+    let entry_rent = ctx.balance(&entry_pda(game_id, &leaver.pubkey()));
+    let before = ctx.balance(&leaver.pubkey());
+    let head = queued[0].pubkey();
+    ctx.send(leave_ix(game_id, &leaver.pubkey(), Some(&head)), &[leaver]).unwrap();
+    assert_eq!(ctx.balance(&leaver.pubkey()) - before, ENTRY_FEE + entry_rent);
+
+    let promoted = ctx.account::<PlayerEntry>(&entry_pda(game_id, &head)).unwrap();
+    assert_eq!(promoted.player, head);
+    assert_eq!(promoted.amount, ENTRY_FEE);
+    assert!(ctx.account::<WaitlistEntry>(&waitlist_entry_pda(game_id, &head)).is_none());
+
+    let game = ctx.game(game_id);
+    assert!(game.has_status(GameStatus::ReadyToStart));
+    assert_eq!(game.current_players, 3);
+    assert_eq!(game.prize_pool, 3 * ENTRY_FEE);
+    assert_eq!(game.waitlist_escrow, ENTRY_FEE);
+
+    // The rest of the queue waits until the game is decided
+    assert_game_error(
+        ctx.send(refund_waitlist_ix(game_id, &queued[1].pubkey()), &[]),
+        GameError::WaitlistStillOpen,
+    );
+}
+
+#[test]
+fn queued_deposits_are_refunded_once_the_game_is_cancelled() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.funded_keypair();
+    let game_id = ctx.create_game(&creator, 3, rules(3));
+    let mut players = ctx.enter_players(game_id, 2);
+
+    // Nobody queues while seats are free
+    let queued = ctx.funded_keypair();
+    assert_game_error(
+        ctx.send(join_waitlist_ix(game_id, &queued.pubkey()), &[&queued]),
+        GameError::SeatsAvailable,
+    );
+    players.extend(ctx.enter_players(game_id, 1));
+    ctx.send(join_waitlist_ix(game_id, &queued.pubkey()), &[&queued]).unwrap();
+
+    ctx.send(cancel_ix(game_id, &creator.pubkey()), &[&creator]).unwrap();
+
+    // The deposit and the entry's rent go back to the queued player
+    let entry_rent = ctx.balance(&waitlist_entry_pda(game_id, &queued.pubkey()));
+    let before = ctx.balance(&queued.pubkey());
+    ctx.send(refund_waitlist_ix(game_id, &queued.pubkey()), &[]).unwrap();
+    assert_eq!(ctx.balance(&queued.pubkey()) - before, ENTRY_FEE + entry_rent);
+    assert_eq!(ctx.game(game_id).waitlist_escrow, 0);
+
+    for player in &players {
+        ctx.send(claim_refund_ix(game_id, &player.pubkey(), false), &[player]).unwrap();
+    }
+    assert!(ctx.game(game_id).is_settled());
+}
@@ -0,0 +1,67 @@
+// PendingAdminActions - public notice of admin interventions queued for a game
+// and of an emergency pause
+import { useEffect, useState } from 'react';
+import { ShieldAlert } from 'lucide-react';
+import { Alert, AlertDescription, AlertTitle } from '@/components/ui/alert';
+import { useSolanaGame, type PendingAdminAction } from '@/hooks/useSolanaGame';
+
+interface PendingAdminActionsProps {
+    gameId: number | null | undefined;
+    paused?: boolean;
+}
+
+const ACTION_LABELS: Record<PendingAdminAction['action'], string> = {
+    StartGame: 'Force start the game',
+    AdvancePhase: 'Advance to the next phase',
+    ClosePurgeNoReady: 'Close the purge and redistribute the pot',
+};
+
+export default function PendingAdminActions({ gameId, paused }: PendingAdminActionsProps) {
+    const { fetchPendingAdminActions } = useSolanaGame();
+    const [pending, setPending] = useState<PendingAdminAction[]>([]);
+
+    useEffect(() => {
+        if (gameId === null || gameId === undefined) return;
+
+        const load = () => fetchPendingAdminActions(gameId)
+            .then(setPending)
+            .catch((error) => console.error('Error loading pending admin actions:', error));
+
+        load();
+        const interval = setInterval(load, 30000);
+        return () => clearInterval(interval);
+    }, [gameId, fetchPendingAdminActions]);
+
+    if (paused) {
+        return (
+            <Alert variant="destructive" className="mb-6">
+                <ShieldAlert className="h-4 w-4" />
+                <AlertTitle>Game paused</AlertTitle>
+                <AlertDescription>
+                    Play is halted by the admin. Refunds stay open and every deadline is extended by the paused time.
+                </AlertDescription>
+            </Alert>
+        );
+    }
+
+    if (pending.length === 0) {
+        return null;
+    }
+
+    return (
+        <Alert className="mb-6 border-yellow-500/50 bg-yellow-500/10">
+            <ShieldAlert className="h-4 w-4" />
+            <AlertTitle>Pending admin intervention</AlertTitle>
+            <AlertDescription>
+                <ul className="space-y-1">
+                    {pending.map((p) => (
+                        <li key={`${p.source}-${p.action}-${p.executableAt.getTime()}`}>
+                            {ACTION_LABELS[p.action]} ({p.source === 'council' ? 'council proposal' : 'admin'}) - earliest{' '}
+                            {p.executableAt.toLocaleString()}
+                        </li>
+                    ))}
+                </ul>
+            </AlertDescription>
+        </Alert>
+    );
+}
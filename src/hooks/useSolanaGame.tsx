@@ -1,6 +1,6 @@
 // src/hooks/useSolanaGame.tsx - VERSIUNE CU CONFIRMĂRI EXPLICITE ȘI SETLOADING CONSISTENT
 import { useEffect, useState, useRef, useCallback } from 'react';
-import { Connection, PublicKey, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { Connection, PublicKey, LAMPORTS_PER_SOL, SystemProgram, TransactionInstruction } from '@solana/web3.js';
 import { Program, AnchorProvider, web3, BN } from '@coral-xyz/anchor';
 import { useWallet } from '@solana/wallet-adapter-react';
 import solanaIdl from '../lib/solana_survivor.json';
@@ -54,6 +54,22 @@ export interface Game {
   phase3Winner: string | null;
   phase3PrizeClaimed: boolean;
   platformFeeCollected: number;
+  creatorFeeBps: number;
+  creatorFeeCollected: number;
+  leaveLockWindow: number; // Seconds before the start when leaving closes
+  waitlistEscrow: number;
+  accessMode: AccessMode;
+  sponsoredAmount: number;
+  sponsorFeeExempt: number; // Sponsor funds the platform fee is not taken from
+  squadSize: number; // Largest squad, 0 for solo games
+  squadCount: number;
+  squadPlayers: number;
+  shortSquads: number; // Squads still below two members
+  mint: string | null;
+  minPlayers: number;
+  maxBetPercentage: number;
+  allowedMiniGames: MiniGameType[];
+  paused: boolean;
 }
 
 export interface GameEvent {
@@ -66,12 +82,43 @@ export interface GameEvent {
   participant?: PublicKey;
 }
 
+// Optional creator rules - anything omitted falls back to the defaults
+export interface GameRulesInput {
+  phaseDurationsMinutes?: [number, number, number];
+  minPlayers?: number;
+  phase2RequiredGames?: number; // 0 = derived from players and phase length
+  maxBetPercentage?: number;
+  allowedMiniGames?: MiniGameType[];
+  creatorFeeBps?: number; // Creator's share of the platform fee, capped by the rule bounds
+  leaveLockMinutes?: number; // Players can't leave in the last N minutes before the start
+  squadSize?: number; // 2-5 for squad games, 0 for solo games
+}
+
+export type AccessMode = 'Public' | 'Allowlist' | 'InviteCode';
+
+// Private game settings - the allowlist or invite code never goes on-chain,
+// only its Merkle root / hash
+export interface GameAccessInput {
+  mode: AccessMode;
+  allowlist?: string[];
+  inviteCode?: string;
+}
+
+// What a player shows to enter a private game
+export interface AccessPassInput {
+  allowlist?: string[];
+  inviteCode?: string;
+}
+
 interface CreateGameParams {
   gameName: string;
   entryFee: number;
   maxPlayers: number;
   startTime: Date;
   gameDurationHours: number;
+  mint?: string;
+  rules?: GameRulesInput;
+  access?: GameAccessInput;
 }
 
 export interface PlayerGameState {
@@ -97,6 +144,100 @@ export interface PlayerPhase2Stats {
 }
 
 export type MiniGameType = 'CryptoTrivia' | 'RockPaperScissors' | 'SpeedTrading' | 'MemeBattle';
+// Order matches the on-chain MiniGameType enum (bit i of allowed_mini_games)
+export const MINI_GAME_TYPES: MiniGameType[] = ['CryptoTrivia', 'RockPaperScissors', 'SpeedTrading', 'MemeBattle'];
+export type AdminAction = 'StartGame' | 'AdvancePhase' | 'ClosePurgeNoReady';
+
+export interface AdminProposal {
+  publicKey: PublicKey;
+  proposalId: number;
+  gameId: number;
+  action: AdminAction;
+  proposer: string;
+  approvals: string[];
+  expiresAt: Date;
+}
+
+// Admin intervention announced through the timelock, or a council proposal
+export interface PendingAdminAction {
+  gameId: number;
+  action: AdminAction;
+  source: 'queue' | 'council';
+  executableAt: Date;
+  expiresAt: Date;
+}
+
+export type Role = 'Admin' | 'Operator' | 'Referee' | 'Treasurer';
+// Order matches the on-chain Role enum (bit i of a roles mask)
+export const ROLES: Role[] = ['Admin', 'Operator', 'Referee', 'Treasurer'];
+
+export interface RoleMember {
+  key: string;
+  roles: Role[];
+}
+
+export interface SponsorInfo {
+  sponsor: string;
+  displayName: string;
+  amount: number;
+  feeEligible: boolean;
+}
+
+export interface SquadInfo {
+  address: string;
+  leader: string;
+  name: string;
+  shares: number[]; // Prize share per slot, in basis points
+  members: string[];
+  phase3Ready: boolean;
+  claimed: boolean[];
+}
+
+export interface GameFeeRecord {
+  gameId: number;
+  mint: string | null;
+  totalCollected: number;
+  lastCollectedAt: Date;
+}
+
+export interface GameTemplateInfo {
+  publicKey: string;
+  templateId: number;
+  operator: string;
+  name: string;
+  entryFee: number; // Base units (lamports for SOL templates)
+  maxPlayers: number;
+  mint: string | null;
+  cadenceHours: number;
+  leadTimeMinutes: number;
+  nextStartTime: Date;
+  gamesSpawned: number;
+  lastGameId: number | null;
+  active: boolean;
+  budget: number; // SOL available to pay back spawners
+}
+
+export interface CreateTemplateParams {
+  gameName: string;
+  entryFee: number;
+  maxPlayers: number;
+  gameDurationHours: number;
+  mint?: string;
+  rules?: GameRulesInput;
+  access?: GameAccessInput;
+  cadenceHours: number;
+  leadTimeMinutes: number;
+  firstStartTime: Date;
+  budget: number; // SOL deposited for spawn rent
+}
+
+export interface TreasuryInfo {
+  balance: number;
+  totalSolCollected: number;
+  totalSolWithdrawn: number;
+  feeRecords: GameFeeRecord[];
+}
+
 export type ChallengeStatus = 'Pending' | 'Accepted' | 'BothReady' | 'InProgress' | 'Completed' | 'Expired' | 'ForcedAccept';
 
 export interface Challenge {
@@ -105,6 +246,7 @@ export interface Challenge {
   gameId: number;
   challenger: PublicKey;
   opponent: PublicKey;
+  opponentSquad: PublicKey | null;
   betAmount: number;
   gameType: MiniGameType;
   status: ChallengeStatus;
@@ -158,6 +300,52 @@ export interface Phase3GameState {
   platformFeeCollected: number;
 }
 
+export const TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
+export const TOKEN_2022_PROGRAM_ID = new PublicKey('TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb');
+export const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL');
+
+// Associated token account of `owner` for `mint` (owner may be a PDA, e.g. the game vault)
+export function getAssociatedTokenAddress(
+  mint: PublicKey,
+  owner: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  )[0];
+}
+
+// Creates `owner`'s associated token account for `mint` unless it already exists
+export function createAssociatedTokenAccountIdempotentInstruction(
+  payer: PublicKey,
+  owner: PublicKey,
+  mint: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: ASSOCIATED_TOKEN_PROGRAM_ID,
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: getAssociatedTokenAddress(mint, owner, tokenProgram), isSigner: false, isWritable: true },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      { pubkey: mint, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: tokenProgram, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from([1]),
+  });
+}
+
+// Token program that owns `mint` (classic SPL Token or Token-2022)
+export async function getMintTokenProgram(connection: Connection, mint: PublicKey): Promise<PublicKey> {
+  const info = await connection.getAccountInfo(mint);
+  if (!info) {
+    throw new Error(`Mint ${mint.toBase58()} not found`);
+  }
+  return info.owner;
+}
+
 // PDA Derivation Functions
 export function getGameRegistryPDA(programId: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
@@ -166,6 +354,131 @@ export function getGameRegistryPDA(programId: PublicKey): [PublicKey, number] {
   );
 }
 
+export function getGlobalConfigPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("global_config")],
+    programId
+  );
+}
+
+export function getAdminCouncilPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_council")],
+    programId
+  );
+}
+
+export function getAdminProposalPDA(programId: PublicKey, proposalId: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_proposal"), new BN(proposalId).toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+export function getRolesPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("roles")],
+    programId
+  );
+}
+
+export function getTreasuryPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    programId
+  );
+}
+
+export function getFeeRecordPDA(programId: PublicKey, gameId: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_record"), new BN(gameId).toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+// Role registry is optional on chain - pass it only once it exists
+export async function getRolesAccount(connection: Connection, programId: PublicKey): Promise<PublicKey | null> {
+  const [rolesPDA] = getRolesPDA(programId);
+  return (await connection.getAccountInfo(rolesPDA)) ? rolesPDA : null;
+}
+
+// Order matches the on-chain AdminAction enum (last seed byte)
+export const ADMIN_ACTIONS: AdminAction[] = ['StartGame', 'AdvancePhase', 'ClosePurgeNoReady'];
+
+export function getQueuedAdminActionPDA(programId: PublicKey, gameId: number, action: AdminAction): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_action"), new BN(gameId).toArrayLike(Buffer, "le", 8), Buffer.from([ADMIN_ACTIONS.indexOf(action)])],
+    programId
+  );
+}
+
+export function getCreatorBondPDA(programId: PublicKey, gameId: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("creator_bond"), new BN(gameId).toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+export function getTemplatePDA(programId: PublicKey, operator: PublicKey, templateId: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("game_template"), operator.toBuffer(), new BN(templateId).toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+export function getWaitlistPDA(programId: PublicKey, gameId: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("waitlist"), new BN(gameId).toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+export function getWaitlistEntryPDA(programId: PublicKey, gameId: number, player: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("waitlist_entry"), new BN(gameId).toArrayLike(Buffer, "le", 8), player.toBuffer()],
+    programId
+  );
+}
+
+export function getSponsorPDA(programId: PublicKey, gameId: number, sponsor: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("sponsor"), new BN(gameId).toArrayLike(Buffer, "le", 8), sponsor.toBuffer()],
+    programId
+  );
+}
+
+export function getSquadPDA(programId: PublicKey, gameId: number, leader: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("squad"), new BN(gameId).toArrayLike(Buffer, "le", 8), leader.toBuffer()],
+    programId
+  );
+}
+
+export function getSquadMemberPDA(programId: PublicKey, gameId: number, player: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("squad_member"), new BN(gameId).toArrayLike(Buffer, "le", 8), player.toBuffer()],
+    programId
+  );
+}
+
+// Players of a migrated game still waiting for their PlayerEntry
+export function getLegacyRosterPDA(programId: PublicKey, gameId: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("legacy_roster"), new BN(gameId).toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+// Games created before creator bonds have none - pass the bond only if it exists
+export async function getCreatorBondAccount(connection: Connection, programId: PublicKey, gameId: number): Promise<PublicKey | null> {
+  const [bondPDA] = getCreatorBondPDA(programId, gameId);
+  return (await connection.getAccountInfo(bondPDA)) ? bondPDA : null;
+}
+
+// Default bond locked by `create_game`, returned on start or cancel; the
+// current amount is `creatorBond` in the global config
+export const CREATOR_BOND_SOL = 0.1;
+
 export function getGamePDA(programId: PublicKey, gameId: number): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("game"), new BN(gameId).toArrayLike(Buffer, "le", 8)],
@@ -173,6 +486,241 @@ export function getGamePDA(programId: PublicKey, gameId: number): [PublicKey, nu
   );
 }
 
+// (player_state, payout) pairs for a no-ready purge - the program skips
+// players who missed Phase 2
+export async function getPurgeRemainingAccounts(connection: Connection, programId: PublicKey, game: Game) {
+  const mint = game.mint ? new PublicKey(game.mint) : null;
+  const tokenProgram = mint ? await getMintTokenProgram(connection, mint) : null;
+  return game.players.flatMap((player) => {
+    const playerKey = new PublicKey(player);
+    const [playerStatePDA] = getPlayerStatePDA(programId, game.gameId, playerKey);
+    return [
+      { pubkey: playerStatePDA, isWritable: false, isSigner: false },
+      {
+        pubkey: mint && tokenProgram ? getAssociatedTokenAddress(mint, playerKey, tokenProgram) : playerKey,
+        isWritable: true,
+        isSigner: false,
+      },
+    ];
+  });
+}
+
+// (player_entry, wallet[, token account]) groups for crank_refunds - players
+// already refunded, and a creator who forfeited, are left out
+export async function getRefundRemainingAccounts(connection: Connection, programId: PublicKey, game: Game) {
+  const mint = game.mint ? new PublicKey(game.mint) : null;
+  const tokenProgram = mint ? await getMintTokenProgram(connection, mint) : null;
+  return game.players
+    .filter((player) => !game.refundedPlayers.includes(player))
+    .filter((player) => !(game.status === 'ExpiredWithPenalty' && player === game.creator))
+    .map((player) => {
+      const playerKey = new PublicKey(player);
+      const group = [
+        { pubkey: getPlayerEntryPDA(programId, game.gameId, playerKey)[0], isWritable: true, isSigner: false },
+        { pubkey: playerKey, isWritable: true, isSigner: false },
+      ];
+      if (mint && tokenProgram) {
+        group.push({ pubkey: getAssociatedTokenAddress(mint, playerKey, tokenProgram), isWritable: true, isSigner: false });
+      }
+      return group;
+    });
+}
+
+// Players refunded per crank_refunds transaction (keeps it under the account limit)
+const CRANK_BATCH_SIZE = 8;
+
+// Player entries created per migrate_player_entries transaction
+const ROSTER_BATCH_SIZE = 10;
+
+// Create the PlayerEntry PDAs of a migrated game, back of the roster first;
+// the game resumes once the roster is empty
+async function migrateRoster(program: Program, admin: PublicKey, gameId: number): Promise<string[]> {
+  const [rosterPDA] = getLegacyRosterPDA(program.programId, gameId);
+  const roster = await (program.account as any).legacyRoster.fetchNullable(rosterPDA);
+  if (!roster) {
+    return [];
+  }
+
+  const players: PublicKey[] = roster.players.map((listed: any) => listed.player).reverse();
+  const signatures: string[] = [];
+  for (let i = 0; i < players.length; i += ROSTER_BATCH_SIZE) {
+    const tx = await program.methods
+      .migratePlayerEntries()
+      .accounts({
+        game: getGamePDA(program.programId, gameId)[0],
+        roster: rosterPDA,
+        gameRegistry: getGameRegistryPDA(program.programId)[0],
+        admin,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(players.slice(i, i + ROSTER_BATCH_SIZE).map((player) => ({
+        pubkey: getPlayerEntryPDA(program.programId, gameId, player)[0],
+        isWritable: true,
+        isSigner: false,
+      })))
+      .rpc({ skipPreflight: false, commitment: 'confirmed' });
+    signatures.push(tx);
+  }
+  return signatures;
+}
+
+// Borsh accounts start with the 8-byte discriminator and a version byte
+const ACCOUNT_HEADER = 8 + 1;
+
+// Mirrors ADMIN_ACTION_GRACE in the program; the notice period is each
+// game's `config.adminActionDelay`
+const ADMIN_ACTION_GRACE = 86400;
+
+// Sizes the deployed program allocated, before the version byte - accounts of
+// this size still need migrate_*
+const LEGACY_ACCOUNT_SIZES: Record<string, number> = {
+  PlayerGameState: 448,
+  GamePoolState: 70,
+  Challenge: 158,
+  Phase3ReadyState: 58,
+};
+const LEGACY_REGISTRY_SIZE = 56;
+// Deployed Borsh games were always allocated at 6658 bytes
+const LEGACY_GAME_SIZE = 6658;
+
+// Game is zero-copy on chain: status is a u8, flags are 0/1, unset keys are
+// the default pubkey and the name is a zero-padded byte array
+const GAME_STATUSES: Game['status'][] = [
+  'WaitingForPlayers', 'ReadyToStart', 'InProgress', 'Completed', 'Cancelled', 'Expired', 'ExpiredWithPenalty',
+];
+
+export function decodeGameStatus(status: number): Game['status'] {
+  return GAME_STATUSES[status];
+}
+
+export function decodeGameName(name: number[]): string {
+  const end = name.indexOf(0);
+  return new TextDecoder().decode(Uint8Array.from(end === -1 ? name : name.slice(0, end)));
+}
+
+export function optionalKey(key: PublicKey): PublicKey | null {
+  return key.equals(PublicKey.default) ? null : key;
+}
+
+async function sha256(...parts: Uint8Array[]): Promise<Uint8Array> {
+  const data = new Uint8Array(parts.reduce((len, part) => len + part.length, 0));
+  parts.reduce((offset, part) => (data.set(part, offset), offset + part.length), 0);
+  return new Uint8Array(await crypto.subtle.digest('SHA-256', data));
+}
+
+function compareBytes(a: Uint8Array, b: Uint8Array): number {
+  for (let i = 0; i < a.length; i++) {
+    if (a[i] !== b[i]) return a[i] - b[i];
+  }
+  return 0;
+}
+
+// Allowlist Merkle tree, matching `allowlist_root` on-chain: leaves are
+// sha256(0 || wallet), nodes sha256(1 || min || max); an odd node moves up as is
+async function allowlistLevels(wallets: string[]): Promise<Uint8Array[][]> {
+  const unique = [...new Set(wallets.map((w) => new PublicKey(w.trim()).toBase58()))];
+  let level = await Promise.all(unique.map((w) => sha256(Uint8Array.of(0), new PublicKey(w).toBytes())));
+  const levels = [level];
+  while (level.length > 1) {
+    const next: Uint8Array[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      if (i + 1 === level.length) {
+        next.push(level[i]);
+        continue;
+      }
+      const [left, right] = compareBytes(level[i], level[i + 1]) <= 0 ? [level[i], level[i + 1]] : [level[i + 1], level[i]];
+      next.push(await sha256(Uint8Array.of(1), left, right));
+    }
+    levels.push(next);
+    level = next;
+  }
+  return levels;
+}
+
+export async function getAllowlistRoot(wallets: string[]): Promise<number[]> {
+  const levels = await allowlistLevels(wallets);
+  return Array.from(levels[levels.length - 1][0]);
+}
+
+export async function getAllowlistProof(wallets: string[], player: PublicKey): Promise<number[][]> {
+  const levels = await allowlistLevels(wallets);
+  const leaf = await sha256(Uint8Array.of(0), player.toBytes());
+  let index = levels[0].findIndex((node) => compareBytes(node, leaf) === 0);
+  if (index === -1) {
+    throw new Error('Your wallet is not on this allowlist');
+  }
+
+  const proof: number[][] = [];
+  for (const level of levels.slice(0, -1)) {
+    const sibling = index ^ 1;
+    if (sibling < level.length) {
+      proof.push(Array.from(level[sibling]));
+    }
+    index >>= 1;
+  }
+  return proof;
+}
+
+export async function getInviteCodeHash(code: string): Promise<number[]> {
+  return Array.from(await sha256(new TextEncoder().encode(code)));
+}
+
+// Arguments for `enter_game` / `join_waitlist`; public games need neither
+async function accessArgs(pass: AccessPassInput | undefined, player: PublicKey): Promise<[number[][], string]> {
+  const proof = pass?.allowlist?.length ? await getAllowlistProof(pass.allowlist, player) : [];
+  return [proof, pass?.inviteCode ?? ''];
+}
+
+// `GameRules` argument - phases split the duration evenly unless set
+function rulesParam(rules: GameRulesInput = {}, gameDurationHours: number, defaultMinPlayers: number) {
+  const phaseSeconds = rules.phaseDurationsMinutes?.map((minutes) => minutes * 60)
+    ?? Array(3).fill(Math.floor(gameDurationHours * 3600 / 3));
+  const allowedMiniGames = rules.allowedMiniGames ?? MINI_GAME_TYPES;
+  return {
+    phase1Duration: new BN(phaseSeconds[0]),
+    phase2Duration: new BN(phaseSeconds[1]),
+    phase3Duration: new BN(phaseSeconds[2]),
+    minPlayers: rules.minPlayers ?? defaultMinPlayers,
+    phase2RequiredGames: rules.phase2RequiredGames ?? 0,
+    maxBetPercentage: rules.maxBetPercentage ?? 100,
+    allowedMiniGames: allowedMiniGames.reduce((mask, type) => mask | (1 << MINI_GAME_TYPES.indexOf(type)), 0),
+    creatorFeeBps: rules.creatorFeeBps ?? 0,
+    leaveLockWindow: new BN((rules.leaveLockMinutes ?? 10) * 60),
+    squadSize: rules.squadSize ?? 0,
+  };
+}
+
+async function accessParam(access: GameAccessInput | undefined) {
+  switch (access?.mode) {
+    case 'Allowlist':
+      return { mode: { allowlist: {} }, root: await getAllowlistRoot(access.allowlist ?? []) };
+    case 'InviteCode':
+      return { mode: { inviteCode: {} }, root: await getInviteCodeHash(access.inviteCode ?? '') };
+    default:
+      return { mode: { public: {} }, root: new Array(32).fill(0) };
+  }
+}
+
+// Vault PDA holding a game's SOL stakes
+export function getVaultPDA(programId: PublicKey, gameId: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), new BN(gameId).toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+// Per-player membership record for a game
+export function getPlayerEntryPDA(programId: PublicKey, gameId: number, player: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("player_entry"),
+      new BN(gameId).toArrayLike(Buffer, "le", 8),
+      player.toBuffer(),
+    ],
+    programId
+  );
+}
+
 export function getPlayerStatePDA(programId: PublicKey, gameId: number, player: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [
@@ -219,6 +767,7 @@ export function useSolanaGame() {
 
   const fetchInProgressRef = useRef(false);
   const lastFetchTimeRef = useRef(0);
+  const mintDecimalsRef = useRef<Record<string, number>>({});
   const FETCH_COOLDOWN_MS = 5000;
 
   console.log('🔄 useSolanaGame render:', {
@@ -290,28 +839,53 @@ export function useSolanaGame() {
     try {
       console.log('🎮 Fetching games from Solana...');
 
-      const accounts = await (prog.account as any).game.all();
+      // Games still on the pre zero-copy layout can't be decoded until migrate_game runs
+      const accounts = await (prog.account as any).game.all([
+        { dataSize: (prog.account as any).game.size }
+      ]);
       console.log('📊 Raw accounts fetched:', accounts.length);
 
+      // Membership lives in PlayerEntry PDAs - group them by game
+      const entries = await (prog.account as any).playerEntry.all();
+      const playersByGame: Record<number, string[]> = {};
+      const refundedByGame: Record<number, string[]> = {};
+      for (const { account: entry } of entries) {
+        const id = entry.gameId.toNumber();
+        (playersByGame[id] ??= []).push(entry.player.toBase58());
+        if (entry.refunded) {
+          (refundedByGame[id] ??= []).push(entry.player.toBase58());
+        }
+      }
+
+      // Token games store amounts in the mint's base units
+      for (const acc of accounts) {
+        const mint = optionalKey(acc.account.mint)?.toBase58();
+        if (mint && mintDecimalsRef.current[mint] === undefined) {
+          const supply = await prog.provider.connection.getTokenSupply(acc.account.mint);
+          mintDecimalsRef.current[mint] = supply.value.decimals;
+        }
+      }
+
       const formattedGames: Game[] = accounts.map((acc: any) => {
         const g = acc.account;
-        const rawStatus = Object.keys(g.status)[0];
-        const status = rawStatus.charAt(0).toUpperCase() + rawStatus.slice(1);
+        const mint = optionalKey(g.mint)?.toBase58() ?? null;
+        const unit = mint ? 10 ** mintDecimalsRef.current[mint] : LAMPORTS_PER_SOL;
+        const winner = optionalKey(g.phase3Winner);
 
         return {
           gameId: g.gameId.toNumber(),
-          name: g.name || `Game #${g.gameId.toNumber()}`,
+          name: decodeGameName(g.name) || `Game #${g.gameId.toNumber()}`,
           creator: g.creator.toBase58(),
-          entryFee: g.entryFee.toNumber() / LAMPORTS_PER_SOL,
+          entryFee: g.entryFee.toNumber() / unit,
           maxPlayers: g.maxPlayers,
           currentPlayers: g.currentPlayers,
           startTime: new Date(g.startTime.toNumber() * 1000),
           expireTime: new Date(g.expireTime.toNumber() * 1000),
-          status: status as Game['status'],
-          prizePool: g.prizePool.toNumber() / LAMPORTS_PER_SOL,
-          players: g.players.map((p: PublicKey) => p.toBase58()),
-          gameStarted: g.gameStarted || false,
-          refundedPlayers: g.refundedPlayers ? g.refundedPlayers.map((p: PublicKey) => p.toBase58()) : [],
+          status: decodeGameStatus(g.status),
+          prizePool: g.prizePool.toNumber() / unit,
+          players: playersByGame[g.gameId.toNumber()] ?? [],
+          gameStarted: g.gameStarted === 1,
+          refundedPlayers: refundedByGame[g.gameId.toNumber()] ?? [],
           currentPhase: g.currentPhase || 0,
           phaseEndTime: new Date(g.phaseEndTime?.toNumber() * 1000 || Date.now()),
           phaseAdvanceDeadline: new Date(g.phaseAdvanceDeadline?.toNumber() * 1000 || Date.now()),
@@ -330,10 +904,26 @@ export function useSolanaGame() {
             ? new Date(g.phase3ExtendedDeadline.toNumber() * 1000)
             : null,
           phase3PlayersReady: g.phase3PlayersReady || 0,
-          phase3Started: g.phase3Started || false,
-          phase3Winner: g.phase3Winner ? g.phase3Winner.toBase58() : null,
-          phase3PrizeClaimed: g.phase3PrizeClaimed || false,
+          phase3Started: g.phase3Started === 1,
+          phase3Winner: winner ? winner.toBase58() : null,
+          phase3PrizeClaimed: g.phase3PrizeClaimed === 1,
           platformFeeCollected: g.platformFeeCollected?.toNumber() || 0,
+          creatorFeeBps: g.creatorFeeBps,
+          creatorFeeCollected: g.creatorFeeCollected?.toNumber() || 0,
+          leaveLockWindow: g.leaveLockWindow?.toNumber() ?? 600,
+          waitlistEscrow: g.waitlistEscrow?.toNumber() || 0,
+          accessMode: (['Public', 'Allowlist', 'InviteCode'] as AccessMode[])[g.accessMode ?? 0],
+          sponsoredAmount: (g.sponsoredAmount?.toNumber() || 0) / unit,
+          sponsorFeeExempt: (g.sponsorFeeExempt?.toNumber() || 0) / unit,
+          squadSize: g.squadSize ?? 0,
+          squadCount: g.squadCount ?? 0,
+          squadPlayers: g.squadPlayers ?? 0,
+          shortSquads: g.shortSquads ?? 0,
+          minPlayers: g.config.minPlayersToStart,
+          maxBetPercentage: g.maxBetPercentage,
+          allowedMiniGames: MINI_GAME_TYPES.filter((_, i) => g.allowedMiniGames & (1 << i)),
+          paused: g.paused === 1,
+          mint,
         };
       });
 
@@ -414,7 +1004,7 @@ export function useSolanaGame() {
       const allChallenges = await (program.account as any).challenge.all([
         {
           memcmp: {
-            offset: 8 + 8,
+            offset: ACCOUNT_HEADER + 8,
             bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
           }
         }
@@ -468,6 +1058,91 @@ export function useSolanaGame() {
     }
   };
 
+  // Players who entered a game, from their PlayerEntry PDAs
+  const getGamePlayers = async (gameId: number): Promise<PublicKey[]> => {
+    const entries = await (program!.account as any).playerEntry.all([
+      {
+        memcmp: {
+          offset: ACCOUNT_HEADER,
+          bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+        }
+      }
+    ]);
+    return entries.map((e: any) => e.account.player as PublicKey);
+  };
+
+  // Mint, vault + owner token account for token games; nulls for SOL games (optional accounts)
+  const getGameTokenAccounts = async (gamePDA: PublicKey, owner: PublicKey) => {
+    const game = await (program!.account as any).game.fetch(gamePDA);
+    const mint = optionalKey(game.mint);
+    if (!mint) {
+      return { mint: null, tokenVault: null, ownerTokenAccount: null, tokenProgram: null };
+    }
+    const tokenProgram = await getMintTokenProgram(program!.provider.connection, mint);
+    return {
+      mint,
+      tokenVault: getAssociatedTokenAddress(mint, gamePDA, tokenProgram),
+      ownerTokenAccount: getAssociatedTokenAddress(mint, owner, tokenProgram),
+      tokenProgram,
+    };
+  };
+
+  // Head of the waitlist, if anyone is queued - lets a leave promote them
+  const getWaitlistPromotionAccounts = async (gameId: number) => {
+    const none = { waitlist: null, waitlistEntry: null, promotedEntry: null };
+    const [waitlistPDA] = getWaitlistPDA(program!.programId, gameId);
+    const waitlist = await (program!.account as any).waitlist.fetchNullable(waitlistPDA);
+    if (!waitlist) {
+      return none;
+    }
+    if (waitlist.head >= waitlist.tail) {
+      return { ...none, waitlist: waitlistPDA };
+    }
+
+    const entries = await (program!.account as any).waitlistEntry.all([
+      {
+        memcmp: {
+          offset: ACCOUNT_HEADER,
+          bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+        }
+      }
+    ]);
+    const head = entries.find((e: any) => e.account.position === waitlist.head);
+    if (!head) {
+      return { ...none, waitlist: waitlistPDA };
+    }
+    return {
+      waitlist: waitlistPDA,
+      waitlistEntry: head.publicKey as PublicKey,
+      promotedEntry: getPlayerEntryPDA(program!.programId, gameId, head.account.player)[0],
+    };
+  };
+
+  // Player's squad membership and squad, or nulls outside squad games
+  const getSquadAccounts = async (gameId: number, player: PublicKey) => {
+    const [memberPDA] = getSquadMemberPDA(program!.programId, gameId, player);
+    const member = await (program!.account as any).squadMember.fetchNullable(memberPDA);
+    return member
+      ? { squadMember: memberPDA, squad: member.squad as PublicKey }
+      : { squadMember: null, squad: null };
+  };
+
+  // Connected wallet's waitlist entry for a game, or null
+  const getMyWaitlistEntry = async (gameId: number) => {
+    if (!program || !wallet.publicKey) return null;
+    const [entryPDA] = getWaitlistEntryPDA(program.programId, gameId, wallet.publicKey);
+    const entry = await (program.account as any).waitlistEntry.fetchNullable(entryPDA);
+    if (!entry) return null;
+    const [waitlistPDA] = getWaitlistPDA(program.programId, gameId);
+    const waitlist = await (program.account as any).waitlist.fetchNullable(waitlistPDA);
+    return {
+      position: entry.position as number,
+      amount: entry.amount.toNumber() as number,
+      // Passed over because the player already held a seat when promoted
+      skipped: !!waitlist && (entry.position as number) < (waitlist.head as number),
+    };
+  };
+
   // ✅ CREATE GAME - cu confirmare
   const createGame = async (params: CreateGameParams) => {
     if (!program || !wallet.publicKey) {
@@ -481,18 +1156,38 @@ export function useSolanaGame() {
       const gameCount = gameRegistry.gameCount;
       const [gamePDA] = getGamePDA(program.programId, gameCount.toNumber());
 
+      const mint = params.mint ? new PublicKey(params.mint) : null;
+      let unit = LAMPORTS_PER_SOL;
+      let tokenProgram: PublicKey | null = null;
+      if (mint) {
+        const supply = await program.provider.connection.getTokenSupply(mint);
+        unit = 10 ** supply.value.decimals;
+        tokenProgram = await getMintTokenProgram(program.provider.connection, mint);
+      }
+
+      const [globalConfigPDA] = getGlobalConfigPDA(program.programId);
+      const globalConfig = await (program.account as any).globalConfig.fetch(globalConfigPDA);
+
       const tx = await program.methods
         .createGame(
           params.gameName,
-          new BN(params.entryFee * LAMPORTS_PER_SOL),
+          new BN(Math.round(params.entryFee * unit)),
           params.maxPlayers,
           new BN(Math.floor(params.startTime.getTime() / 1000)),
-          params.gameDurationHours
+          rulesParam(params.rules, params.gameDurationHours, globalConfig.params.minPlayersToStart),
+          await accessParam(params.access)
         )
         .accounts({
           game: gamePDA,
           gameRegistry: gameRegistryPDA,
+          globalConfig: globalConfigPDA,
+          creatorBond: getCreatorBondPDA(program.programId, gameCount.toNumber())[0],
           creator: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameCount.toNumber())[0],
+          mint,
+          tokenVault: mint && tokenProgram ? getAssociatedTokenAddress(mint, gamePDA, tokenProgram) : null,
+          tokenProgram,
+          associatedTokenProgram: mint ? ASSOCIATED_TOKEN_PROGRAM_ID : null,
           systemProgram: SystemProgram.programId,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
@@ -506,7 +1201,7 @@ export function useSolanaGame() {
   };
 
   // ✅ ENTER GAME - cu confirmare
-  const enterGame = async (gameId: number) => {
+  const enterGame = async (gameId: number, pass?: AccessPassInput) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
@@ -514,12 +1209,20 @@ export function useSolanaGame() {
     setLoading(true);
     try {
       const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
 
       const tx = await program.methods
-        .enterGame()
+        .enterGame(...(await accessArgs(pass, wallet.publicKey)))
         .accounts({
           game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
           player: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          playerTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
           systemProgram: SystemProgram.programId
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
@@ -535,11 +1238,8 @@ export function useSolanaGame() {
     }
   };
 
-  // ✅ BATCH INITIALIZE - cu confirmare pentru fiecare TX
-  const batchInitializeGameStates = async (
-    gameId: number,
-    onProgress?: (progress: InitProgress) => void
-  ): Promise<void> => {
+  // ✅ LEAVE GAME - retragere inainte de start, cu refund
+  const leaveGame = async (gameId: number) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
@@ -547,149 +1247,113 @@ export function useSolanaGame() {
     setLoading(true);
     try {
       const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
+      const promotion = await getWaitlistPromotionAccounts(gameId);
 
-      onProgress?.({
-        status: 'initializing-players',
-        progress: 10,
-        currentStep: 'Loading player list...',
-      });
+      const tx = await program.methods
+        .leaveGame()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
+          player: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          playerTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          waitlist: promotion.waitlist,
+          waitlistEntry: promotion.waitlistEntry,
+          promotedEntry: promotion.promotedEntry,
+          squadMember: getSquadMemberPDA(program.programId, gameId, wallet.publicKey)[0],
+          systemProgram: SystemProgram.programId
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-      const game = await (program.account as any).game.fetch(gamePDA);
-      const players = game.players;
+      await confirmTransaction(program.provider.connection, tx);
 
-      const BATCH_SIZE = 10;
-      const batches = [];
-      for (let i = 0; i < players.length; i += BATCH_SIZE) {
-        batches.push(players.slice(i, i + BATCH_SIZE));
-      }
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
 
-      for (let i = 0; i < batches.length; i++) {
-        const batch = batches[i];
-        const batchProgress = 10 + (70 * (i / batches.length));
+  // ✅ JOIN WAITLIST - depozit pentru un loc intr-un joc plin
+  const joinWaitlist = async (gameId: number, pass?: AccessPassInput) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
 
-        onProgress?.({
-          status: 'initializing-players',
-          progress: batchProgress,
-          currentStep: `Initializing players ${i * BATCH_SIZE + 1}-${Math.min((i + 1) * BATCH_SIZE, players.length)}...`,
-        });
-
-        const initPromises = batch.map(async (player: PublicKey) => {
-          try {
-            const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, player);
-
-            const tx = await program.methods
-              .initializePlayerState()
-              .accounts({
-                playerState: playerStatePDA,
-                game: gamePDA,
-                player: player,
-                systemProgram: SystemProgram.programId,
-              })
-              .rpc({ skipPreflight: false, commitment: 'confirmed' });
-
-            // ✅ Așteaptă confirmarea
-            await confirmTransaction(program.provider.connection, tx);
-
-            return { player: player.toString(), success: true };
-          } catch (error) {
-            console.error(`Failed to init player ${player.toString()}:`, error);
-            return { player: player.toString(), success: false, error };
-          }
-        });
-
-        await Promise.allSettled(initPromises);
-      }
-
-      onProgress?.({
-        status: 'initializing-pool',
-        progress: 90,
-        currentStep: 'Initializing resource pools...',
-      });
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
 
-      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
-      const poolTx = await program.methods
-        .initializePoolState()
+      const tx = await program.methods
+        .joinWaitlist(...(await accessArgs(pass, wallet.publicKey)))
         .accounts({
-          poolState: poolStatePDA,
           game: gamePDA,
-          authority: wallet.publicKey,
-          systemProgram: SystemProgram.programId,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          waitlist: getWaitlistPDA(program.programId, gameId)[0],
+          waitlistEntry: getWaitlistEntryPDA(program.programId, gameId, wallet.publicKey)[0],
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
+          player: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          playerTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          systemProgram: SystemProgram.programId
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-      // ✅ Confirmare
-      await confirmTransaction(program.provider.connection, poolTx);
-
-      onProgress?.({
-        status: 'ready',
-        progress: 100,
-        currentStep: 'Game ready!',
-      });
+      await confirmTransaction(program.provider.connection, tx);
 
       await fetchGames(program);
-    } catch (error: any) {
-      onProgress?.({
-        status: 'error',
-        progress: 0,
-        currentStep: 'Initialization failed',
-        error: error.message,
-      });
-      throw error;
+      return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ START GAME WITH BATCH INIT - cu confirmare
-  const startGameWithBatchInit = async (
-    gameId: number,
-    onProgress?: (progress: InitProgress) => void
-  ): Promise<void> => {
+  // ✅ PROMOTE FROM WAITLIST - oricine poate muta capul cozii pe un loc liber
+  const promoteFromWaitlist = async (gameId: number) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      onProgress?.({
-        status: 'starting',
-        progress: 5,
-        currentStep: 'Starting game...',
-      });
+      const promotion = await getWaitlistPromotionAccounts(gameId);
+      if (!promotion.waitlistEntry) {
+        throw new Error('Nobody is waiting for this game');
+      }
 
-      const [gamePDA] = getGamePDA(program.programId, gameId);
       const tx = await program.methods
-        .startGame()
+        .promoteFromWaitlist()
         .accounts({
-          game: gamePDA,
-          creator: wallet.publicKey,
+          game: getGamePDA(program.programId, gameId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          waitlist: promotion.waitlist,
+          waitlistEntry: promotion.waitlistEntry,
+          playerEntry: promotion.promotedEntry,
+          payer: wallet.publicKey,
+          systemProgram: SystemProgram.programId
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-      // ✅ Confirmare
       await confirmTransaction(program.provider.connection, tx);
 
-      await batchInitializeGameStates(gameId, (progress) => {
-        onProgress?.({
-          ...progress,
-          progress: 5 + (progress.progress * 0.95),
-        });
-      });
-    } catch (error: any) {
-      onProgress?.({
-        status: 'error',
-        progress: 0,
-        currentStep: 'Failed to start game',
-        error: error.message,
-      });
-      throw error;
+      await fetchGames(program);
+      return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ INITIALIZE PLAYER STATE - cu confirmare
-  const initializePlayerState = async (gameId: number): Promise<void> => {
+  // ✅ REFUND WAITLIST - depozitul revine daca jocul a pornit fara jucator
+  const refundWaitlist = async (gameId: number, player: PublicKey = wallet.publicKey!) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
@@ -697,27 +1361,58 @@ export function useSolanaGame() {
     setLoading(true);
     try {
       const [gamePDA] = getGamePDA(program.programId, gameId);
-      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+      const token = await getGameTokenAccounts(gamePDA, player);
 
       const tx = await program.methods
-        .initializePlayerState()
+        .refundWaitlist()
         .accounts({
-          playerState: playerStatePDA,
           game: gamePDA,
-          player: wallet.publicKey,
-          systemProgram: SystemProgram.programId,
+          waitlist: getWaitlistPDA(program.programId, gameId)[0],
+          waitlistEntry: getWaitlistEntryPDA(program.programId, gameId, player)[0],
+          player,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          playerTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          systemProgram: SystemProgram.programId
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
+
       await fetchGames(program);
+      return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ INITIALIZE POOL STATE - cu confirmare
-  const initializePoolState = async (gameId: number): Promise<void> => {
+  // Sponsors of a game, largest contribution first
+  const fetchSponsors = async (gameId: number): Promise<SponsorInfo[]> => {
+    if (!program) return [];
+    const game = games.find((g) => g.gameId === gameId);
+    const unit = game?.mint ? 10 ** mintDecimalsRef.current[game.mint] : LAMPORTS_PER_SOL;
+    const records = await (program.account as any).sponsorRecord.all([
+      {
+        memcmp: {
+          offset: ACCOUNT_HEADER,
+          bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+        }
+      }
+    ]);
+    return records
+      .map((r: any) => ({
+        sponsor: r.account.sponsor.toBase58(),
+        displayName: r.account.displayName,
+        amount: r.account.amount.toNumber() / unit,
+        feeEligible: r.account.feeEligible,
+      }))
+      .sort((a: SponsorInfo, b: SponsorInfo) => b.amount - a.amount);
+  };
+
+  // ✅ SPONSOR GAME - partener care mareste prize pool-ul
+  const sponsorGame = async (gameId: number, amount: number, displayName: string, feeEligible: boolean) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
@@ -725,36 +1420,40 @@ export function useSolanaGame() {
     setLoading(true);
     try {
       const [gamePDA] = getGamePDA(program.programId, gameId);
-      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
+      let unit = LAMPORTS_PER_SOL;
+      if (token.mint) {
+        const supply = await program.provider.connection.getTokenSupply(token.mint);
+        unit = 10 ** supply.value.decimals;
+      }
 
       const tx = await program.methods
-        .initializePoolState()
+        .sponsorGame(new BN(Math.round(amount * unit)), displayName, feeEligible)
         .accounts({
-          poolState: poolStatePDA,
           game: gamePDA,
-          authority: wallet.publicKey,
-          systemProgram: SystemProgram.programId,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          sponsorRecord: getSponsorPDA(program.programId, gameId, wallet.publicKey)[0],
+          sponsor: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          sponsorTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          systemProgram: SystemProgram.programId
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
+
       await fetchGames(program);
+      return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ SUBMIT ALLOCATIONS - cu confirmare
-  const submitAllocations = async (
-    gameId: number,
-    allocations: {
-      mining: number;
-      farming: number;
-      trading: number;
-      research: number;
-      social: number;
-    }
-  ): Promise<string> => {
+  // ✅ REFUND SPONSOR - fondurile revin sponsorului daca jocul a fost anulat
+  const refundSponsor = async (gameId: number, sponsor: PublicKey = wallet.publicKey!) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
@@ -762,225 +1461,196 @@ export function useSolanaGame() {
     setLoading(true);
     try {
       const [gamePDA] = getGamePDA(program.programId, gameId);
-      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
-      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
-
-      const allocationsInLamports = {
-        mining: new BN(Math.floor(allocations.mining * LAMPORTS_PER_SOL)),
-        farming: new BN(Math.floor(allocations.farming * LAMPORTS_PER_SOL)),
-        trading: new BN(Math.floor(allocations.trading * LAMPORTS_PER_SOL)),
-        research: new BN(Math.floor(allocations.research * LAMPORTS_PER_SOL)),
-        social: new BN(Math.floor(allocations.social * LAMPORTS_PER_SOL))
-      };
+      const token = await getGameTokenAccounts(gamePDA, sponsor);
 
       const tx = await program.methods
-        .submitAllocations(
-          allocationsInLamports.mining,
-          allocationsInLamports.farming,
-          allocationsInLamports.trading,
-          allocationsInLamports.research,
-          allocationsInLamports.social
-        )
+        .refundSponsor()
         .accounts({
-          playerState: playerStatePDA,
-          poolState: poolStatePDA,
           game: gamePDA,
-          player: wallet.publicKey,
+          sponsorRecord: getSponsorPDA(program.programId, gameId, sponsor)[0],
+          sponsor,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          sponsorTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          systemProgram: SystemProgram.programId
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
+
+      await fetchGames(program);
       return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ CLAIM REWARDS - cu confirmare
-  const claimRewards = async (gameId: number): Promise<string> => {
+  // Squads of a squad game
+  const fetchSquads = async (gameId: number): Promise<SquadInfo[]> => {
+    if (!program) return [];
+    const squads = await (program.account as any).squad.all([
+      {
+        memcmp: {
+          offset: ACCOUNT_HEADER,
+          bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+        }
+      }
+    ]);
+    return squads.map((s: any) => ({
+      address: s.publicKey.toBase58(),
+      leader: s.account.leader.toBase58(),
+      name: s.account.name,
+      shares: s.account.shares,
+      members: s.account.members.map((m: PublicKey) => m.toBase58()),
+      phase3Ready: s.account.phase3Ready,
+      claimed: s.account.members.map((_: PublicKey, i: number) => (s.account.claimedMask & (1 << i)) !== 0),
+    }));
+  };
+
+  // ✅ CREATE SQUAD - liderul alege cota din premiu pentru fiecare loc
+  const createSquad = async (gameId: number, name: string, sharesPercent: number[]) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
-      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
-      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
+      // Basis points summing to 100%, rounding dust to the leader's slot
+      const shares = sharesPercent.map((percent) => Math.round(percent * 100));
+      shares[0] += 10000 - shares.reduce((sum, share) => sum + share, 0);
 
       const tx = await program.methods
-        .claimRewards()
+        .createSquad(name, shares)
         .accounts({
-          playerState: playerStatePDA,
-          poolState: poolStatePDA,
-          game: gamePDA,
-          player: wallet.publicKey,
+          game: getGamePDA(program.programId, gameId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          squad: getSquadPDA(program.programId, gameId, wallet.publicKey)[0],
+          squadMember: getSquadMemberPDA(program.programId, gameId, wallet.publicKey)[0],
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
+          leader: wallet.publicKey,
+          systemProgram: SystemProgram.programId
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
+
+      await fetchGames(program);
       return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ CLAIM ALL PHASE END REWARDS - cu confirmare per batch
-  const claimAllPhaseEndRewards = async (
-    gameId: number,
-    onProgress?: (current: number, total: number) => void
-  ): Promise<void> => {
-    if (!program) {
-      throw new Error('Program not initialized');
+  // ✅ JOIN SQUAD - urmatorul loc liber din echipa
+  const joinSquad = async (gameId: number, squad: string) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
-      const game = await (program.account as any).game.fetch(gamePDA);
-      const players = game.players;
-      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
-
-      const BATCH_SIZE = 10;
-      const batches = [];
-      for (let i = 0; i < players.length; i += BATCH_SIZE) {
-        batches.push(players.slice(i, i + BATCH_SIZE));
-      }
-
-      let processed = 0;
-
-      for (const batch of batches) {
-        const claimPromises = batch.map(async (player: PublicKey) => {
-          try {
-            const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, player);
-
-            const tx = await program.methods
-              .claimPhaseEndRewards()
-              .accounts({
-                playerState: playerStatePDA,
-                poolState: poolStatePDA,
-                game: gamePDA,
-                player: player,
-              })
-              .rpc({ skipPreflight: false, commitment: 'confirmed' });
-
-            // ✅ Confirmare
-            await confirmTransaction(program.provider.connection, tx);
+      const tx = await program.methods
+        .joinSquad()
+        .accounts({
+          game: getGamePDA(program.programId, gameId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          squad: new PublicKey(squad),
+          squadMember: getSquadMemberPDA(program.programId, gameId, wallet.publicKey)[0],
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
+          player: wallet.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-            processed++;
-            onProgress?.(processed, players.length);
-            return { player: player.toString(), success: true };
-          } catch (error) {
-            console.error(`Failed to claim for ${player.toString()}:`, error);
-            processed++;
-            onProgress?.(processed, players.length);
-            return { player: player.toString(), success: false, error };
-          }
-        });
+      await confirmTransaction(program.provider.connection, tx);
 
-        await Promise.allSettled(claimPromises);
-      }
+      await fetchGames(program);
+      return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ ADVANCE PHASE - cu confirmare
-  const advancePhase = async (
-    gameId: number,
-    onProgress?: (progress: PhaseAdvanceProgress) => void
-  ): Promise<void> => {
+  // ✅ LEAVE SQUAD - iesire din echipa inainte de start
+  const leaveSquad = async (gameId: number, squad: string) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      onProgress?.({ step: 'Advancing to next phase...', progress: 50 });
-
-      const [gamePDA] = getGamePDA(program.programId, gameId);
       const tx = await program.methods
-        .advancePhase()
+        .leaveSquad()
         .accounts({
-          game: gamePDA,
-          caller: wallet.publicKey,
+          game: getGamePDA(program.programId, gameId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          squad: new PublicKey(squad),
+          squadMember: getSquadMemberPDA(program.programId, gameId, wallet.publicKey)[0],
+          player: wallet.publicKey,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
 
-      onProgress?.({ step: 'Claiming your rewards...', progress: 75 });
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
 
-      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
-      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
-
-      const claimTx = await program.methods
-        .claimPhaseEndRewards()
-        .accounts({
-          playerState: playerStatePDA,
-          poolState: poolStatePDA,
-          game: gamePDA,
-          player: wallet.publicKey,
-        })
-        .rpc({ skipPreflight: false, commitment: 'confirmed' });
-
-      await confirmTransaction(program.provider.connection, claimTx);
-
-      onProgress?.({ step: 'Phase advanced!', progress: 100 });
-      await fetchGames(program);
-    } catch (error: any) {
-      onProgress?.({ step: 'Failed to advance phase', progress: 0 });
-      throw error;
-    } finally {
-      setLoading(false);
-    }
-  };
-
-  // ✅ START GAME - cu confirmare
-  const startGame = async (gameId: number) => {
+  // ✅ SHARE SQUAD BALANCE - balanta nealocata trece la un coechipier in Phase 1
+  const shareSquadBalance = async (gameId: number, to: PublicKey, amount: number) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
-
       const tx = await program.methods
-        .startGame()
+        .shareSquadBalance(new BN(Math.round(amount * LAMPORTS_PER_SOL)))
         .accounts({
-          game: gamePDA,
-          creator: wallet.publicKey,
+          game: getGamePDA(program.programId, gameId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          fromState: getPlayerStatePDA(program.programId, gameId, wallet.publicKey)[0],
+          toState: getPlayerStatePDA(program.programId, gameId, to)[0],
+          fromMember: getSquadMemberPDA(program.programId, gameId, wallet.publicKey)[0],
+          toMember: getSquadMemberPDA(program.programId, gameId, to)[0],
+          player: wallet.publicKey,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      await fetchGames(program);
+      toast.success('Balance shared with your squadmate');
       return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ CANCEL GAME - cu confirmare
-  const cancelGame = async (gameId: number) => {
+  // ✅ SUBMIT SQUAD WINNER - arbitrul declara echipa castigatoare
+  const submitPhase3SquadWinner = async (gameId: number, squad: string) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
-
       const tx = await program.methods
-        .creatorCancelGame()
+        .submitPhase3SquadWinner()
         .accounts({
-          game: gamePDA,
-          creator: wallet.publicKey,
+          game: getGamePDA(program.programId, gameId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          roles: await getRolesAccount(program.provider.connection, program.programId),
+          squad: new PublicKey(squad),
+          submitter: wallet.publicKey,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
+      toast.success('🏆 Winning squad declared!');
       await fetchGames(program);
       return tx;
     } finally {
@@ -988,8 +1658,8 @@ export function useSolanaGame() {
     }
   };
 
-  // ✅ CLAIM REFUND - cu confirmare
-  const claimRefund = async (gameId: number) => {
+  // ✅ CLAIM SQUAD PRIZE - fiecare membru isi ia cota din premiu
+  const claimSquadPrize = async (gameId: number, squad: string) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
@@ -997,16 +1667,26 @@ export function useSolanaGame() {
     setLoading(true);
     try {
       const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
 
       const tx = await program.methods
-        .claimRefund()
+        .claimSquadPrize()
         .accounts({
           game: gamePDA,
-          player: wallet.publicKey,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          squad: new PublicKey(squad),
+          member: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          memberTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          systemProgram: SystemProgram.programId
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
+      toast.success('💰 Squad prize share claimed!');
       await fetchGames(program);
       return tx;
     } finally {
@@ -1014,13 +1694,11 @@ export function useSolanaGame() {
     }
   };
 
-  // ✅ CREATE CHALLENGE - cu confirmare
-  const createChallenge = async (
+  // ✅ BATCH INITIALIZE - cu confirmare pentru fiecare TX
+  const batchInitializeGameStates = async (
     gameId: number,
-    opponent: PublicKey,
-    betAmount: number,
-    gameType: MiniGameType
-  ): Promise<string> => {
+    onProgress?: (progress: InitProgress) => void
+  ): Promise<void> => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
@@ -1028,130 +1706,216 @@ export function useSolanaGame() {
     setLoading(true);
     try {
       const [gamePDA] = getGamePDA(program.programId, gameId);
-      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
 
-      const timestamp = Math.floor(Date.now() / 1000);
-      const [challengePDA] = getChallengePDA(program.programId, gameId, wallet.publicKey, opponent, timestamp);
+      onProgress?.({
+        status: 'initializing-players',
+        progress: 10,
+        currentStep: 'Loading player list...',
+      });
 
-      const gameTypeEnum = {
-        CryptoTrivia: { cryptoTrivia: {} },
-        RockPaperScissors: { rockPaperScissors: {} },
-        SpeedTrading: { speedTrading: {} },
-        MemeBattle: { memeBattle: {} },
-      }[gameType];
+      const players = await getGamePlayers(gameId);
 
-      const tx = await program.methods
-        .createChallenge(
-          opponent,
-          new BN(timestamp),
-          new BN(betAmount * LAMPORTS_PER_SOL),
-          gameTypeEnum
-        )
+      const BATCH_SIZE = 10;
+      const batches = [];
+      for (let i = 0; i < players.length; i += BATCH_SIZE) {
+        batches.push(players.slice(i, i + BATCH_SIZE));
+      }
+
+      for (let i = 0; i < batches.length; i++) {
+        const batch = batches[i];
+        const batchProgress = 10 + (70 * (i / batches.length));
+
+        onProgress?.({
+          status: 'initializing-players',
+          progress: batchProgress,
+          currentStep: `Initializing players ${i * BATCH_SIZE + 1}-${Math.min((i + 1) * BATCH_SIZE, players.length)}...`,
+        });
+
+        const initPromises = batch.map(async (player: PublicKey) => {
+          try {
+            const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, player);
+
+            const tx = await program.methods
+              .initializePlayerState()
+              .accounts({
+                playerState: playerStatePDA,
+                game: gamePDA,
+                playerEntry: getPlayerEntryPDA(program.programId, gameId, player)[0],
+                player: player,
+                systemProgram: SystemProgram.programId,
+              })
+              .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+            // ✅ Așteaptă confirmarea
+            await confirmTransaction(program.provider.connection, tx);
+
+            return { player: player.toString(), success: true };
+          } catch (error) {
+            console.error(`Failed to init player ${player.toString()}:`, error);
+            return { player: player.toString(), success: false, error };
+          }
+        });
+
+        await Promise.allSettled(initPromises);
+      }
+
+      onProgress?.({
+        status: 'initializing-pool',
+        progress: 90,
+        currentStep: 'Initializing resource pools...',
+      });
+
+      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
+      const poolTx = await program.methods
+        .initializePoolState()
         .accounts({
-          challenge: challengePDA,
+          poolState: poolStatePDA,
           game: gamePDA,
-          playerState: playerStatePDA,
-          challenger: wallet.publicKey,
-          opponent: opponent,
+          authority: wallet.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-      await confirmTransaction(program.provider.connection, tx);
-      rpcCache.invalidate(`pending_challenges_${gameId}_${opponent.toBase58()}`);
-      rpcCache.invalidate(`my_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
-      return tx;
+      // ✅ Confirmare
+      await confirmTransaction(program.provider.connection, poolTx);
+
+      onProgress?.({
+        status: 'ready',
+        progress: 100,
+        currentStep: 'Game ready!',
+      });
+
+      await fetchGames(program);
+    } catch (error: any) {
+      onProgress?.({
+        status: 'error',
+        progress: 0,
+        currentStep: 'Initialization failed',
+        error: error.message,
+      });
+      throw error;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ RESPOND TO CHALLENGE - cu confirmare
-  const respondToChallenge = async (
-    challengePDA: PublicKey,
+  // ✅ START GAME WITH BATCH INIT - cu confirmare
+  const startGameWithBatchInit = async (
     gameId: number,
-    accept: boolean
-  ): Promise<string> => {
+    onProgress?: (progress: InitProgress) => void
+  ): Promise<void> => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
-      const [opponentStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+      onProgress?.({
+        status: 'starting',
+        progress: 5,
+        currentStep: 'Starting game...',
+      });
 
+      const [gamePDA] = getGamePDA(program.programId, gameId);
       const tx = await program.methods
-        .respondChallenge(accept)
+        .startGame()
         .accounts({
-          challenge: challengePDA,
           game: gamePDA,
-          opponentState: opponentStatePDA,
-          opponent: wallet.publicKey,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          creatorBond: await getCreatorBondAccount(program.provider.connection, program.programId, gameId),
+          creator: wallet.publicKey,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
+      // ✅ Confirmare
       await confirmTransaction(program.provider.connection, tx);
-      rpcCache.invalidate(`pending_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
-      rpcCache.invalidate(`active_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
-      return tx;
+
+      await batchInitializeGameStates(gameId, (progress) => {
+        onProgress?.({
+          ...progress,
+          progress: 5 + (progress.progress * 0.95),
+        });
+      });
+    } catch (error: any) {
+      onProgress?.({
+        status: 'error',
+        progress: 0,
+        currentStep: 'Failed to start game',
+        error: error.message,
+      });
+      throw error;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ MARK READY - cu confirmare
-  const markReady = async (challengePDA: PublicKey): Promise<string> => {
+  // ✅ INITIALIZE PLAYER STATE - cu confirmare
+  const initializePlayerState = async (gameId: number): Promise<void> => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+
       const tx = await program.methods
-        .readyForGame()
+        .initializePlayerState()
         .accounts({
-          challenge: challengePDA,
+          playerState: playerStatePDA,
+          game: gamePDA,
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
           player: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      return tx;
+      await fetchGames(program);
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ START MINI GAME - cu confirmare
-  const startMiniGame = async (challengePDA: PublicKey): Promise<string> => {
+  // ✅ INITIALIZE POOL STATE - cu confirmare
+  const initializePoolState = async (gameId: number): Promise<void> => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
+
       const tx = await program.methods
-        .startMiniGame()
+        .initializePoolState()
         .accounts({
-          challenge: challengePDA,
-          player: wallet.publicKey,
+          poolState: poolStatePDA,
+          game: gamePDA,
+          authority: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      return tx;
+      await fetchGames(program);
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ CLAIM MINI GAME WIN - cu confirmare
-  const claimMiniGameWin = async (
-    challengePDA: PublicKey,
+  // ✅ SUBMIT ALLOCATIONS - cu confirmare
+  const submitAllocations = async (
     gameId: number,
-    winner: PublicKey,
-    loser: PublicKey
+    allocations: {
+      mining: number;
+      farming: number;
+      trading: number;
+      research: number;
+      social: number;
+    }
   ): Promise<string> => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
@@ -1160,260 +1924,1719 @@ export function useSolanaGame() {
     setLoading(true);
     try {
       const [gamePDA] = getGamePDA(program.programId, gameId);
-      const [winnerStatePDA] = getPlayerStatePDA(program.programId, gameId, winner);
-      const [loserStatePDA] = getPlayerStatePDA(program.programId, gameId, loser);
+      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
 
-      const tx = await program.methods
-        .claimMiniGameWin(winner)
-        .accounts({
-          challenge: challengePDA,
-          game: gamePDA,
-          winnerState: winnerStatePDA,
-          loserState: loserStatePDA,
+      const allocationsInLamports = {
+        mining: new BN(Math.floor(allocations.mining * LAMPORTS_PER_SOL)),
+        farming: new BN(Math.floor(allocations.farming * LAMPORTS_PER_SOL)),
+        trading: new BN(Math.floor(allocations.trading * LAMPORTS_PER_SOL)),
+        research: new BN(Math.floor(allocations.research * LAMPORTS_PER_SOL)),
+        social: new BN(Math.floor(allocations.social * LAMPORTS_PER_SOL))
+      };
+
+      const tx = await program.methods
+        .submitAllocations(
+          allocationsInLamports.mining,
+          allocationsInLamports.farming,
+          allocationsInLamports.trading,
+          allocationsInLamports.research,
+          allocationsInLamports.social
+        )
+        .accounts({
+          playerState: playerStatePDA,
+          poolState: poolStatePDA,
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          player: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CLAIM REWARDS - cu confirmare
+  const claimRewards = async (gameId: number): Promise<string> => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
+
+      const tx = await program.methods
+        .claimRewards()
+        .accounts({
+          playerState: playerStatePDA,
+          poolState: poolStatePDA,
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          player: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CLAIM ALL PHASE END REWARDS - cu confirmare per batch
+  const claimAllPhaseEndRewards = async (
+    gameId: number,
+    onProgress?: (current: number, total: number) => void
+  ): Promise<void> => {
+    if (!program) {
+      throw new Error('Program not initialized');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const players = await getGamePlayers(gameId);
+      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
+
+      const BATCH_SIZE = 10;
+      const batches = [];
+      for (let i = 0; i < players.length; i += BATCH_SIZE) {
+        batches.push(players.slice(i, i + BATCH_SIZE));
+      }
+
+      let processed = 0;
+
+      for (const batch of batches) {
+        const claimPromises = batch.map(async (player: PublicKey) => {
+          try {
+            const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, player);
+
+            const tx = await program.methods
+              .claimPhaseEndRewards()
+              .accounts({
+                playerState: playerStatePDA,
+                poolState: poolStatePDA,
+                game: gamePDA,
+                gameRegistry: getGameRegistryPDA(program.programId)[0],
+                player: player,
+              })
+              .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+            // ✅ Confirmare
+            await confirmTransaction(program.provider.connection, tx);
+
+            processed++;
+            onProgress?.(processed, players.length);
+            return { player: player.toString(), success: true };
+          } catch (error) {
+            console.error(`Failed to claim for ${player.toString()}:`, error);
+            processed++;
+            onProgress?.(processed, players.length);
+            return { player: player.toString(), success: false, error };
+          }
+        });
+
+        await Promise.allSettled(claimPromises);
+      }
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ ADVANCE PHASE - cu confirmare
+  const advancePhase = async (
+    gameId: number,
+    onProgress?: (progress: PhaseAdvanceProgress) => void
+  ): Promise<void> => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      onProgress?.({ step: 'Advancing to next phase...', progress: 50 });
+
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const tx = await program.methods
+        .advancePhase()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          caller: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+
+      onProgress?.({ step: 'Claiming your rewards...', progress: 75 });
+
+      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+      const [poolStatePDA] = getPoolStatePDA(program.programId, gameId);
+
+      const claimTx = await program.methods
+        .claimPhaseEndRewards()
+        .accounts({
+          playerState: playerStatePDA,
+          poolState: poolStatePDA,
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          player: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, claimTx);
+
+      onProgress?.({ step: 'Phase advanced!', progress: 100 });
+      await fetchGames(program);
+    } catch (error: any) {
+      onProgress?.({ step: 'Failed to advance phase', progress: 0 });
+      throw error;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ START GAME - cu confirmare
+  const startGame = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+
+      const tx = await program.methods
+        .startGame()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          creatorBond: await getCreatorBondAccount(program.provider.connection, program.programId, gameId),
+          creator: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CANCEL GAME - cu confirmare
+  const cancelGame = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+
+      const tx = await program.methods
+        .creatorCancelGame()
+        .accounts({
+          game: gamePDA,
+          creatorBond: await getCreatorBondAccount(program.provider.connection, program.programId, gameId),
+          creator: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CLAIM REFUND - cu confirmare
+  const claimRefund = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
+
+      const tx = await program.methods
+        .claimRefund()
+        .accounts({
+          game: gamePDA,
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
+          creatorBond: await getCreatorBondAccount(program.provider.connection, program.programId, gameId),
+          player: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          playerTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CREATE CHALLENGE - cu confirmare
+  const createChallenge = async (
+    gameId: number,
+    opponent: PublicKey,
+    betAmount: number,
+    gameType: MiniGameType
+  ): Promise<string> => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+
+      const timestamp = Math.floor(Date.now() / 1000);
+      const [challengePDA] = getChallengePDA(program.programId, gameId, wallet.publicKey, opponent, timestamp);
+      const challengerSquad = await getSquadAccounts(gameId, wallet.publicKey);
+      const opponentSquad = await getSquadAccounts(gameId, opponent);
+
+      const gameTypeEnum = {
+        CryptoTrivia: { cryptoTrivia: {} },
+        RockPaperScissors: { rockPaperScissors: {} },
+        SpeedTrading: { speedTrading: {} },
+        MemeBattle: { memeBattle: {} },
+      }[gameType];
+
+      const tx = await program.methods
+        .createChallenge(
+          opponent,
+          new BN(timestamp),
+          new BN(betAmount * LAMPORTS_PER_SOL),
+          gameTypeEnum
+        )
+        .accounts({
+          challenge: challengePDA,
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          playerState: playerStatePDA,
+          opponentEntry: getPlayerEntryPDA(program.programId, gameId, opponent)[0],
+          challenger: wallet.publicKey,
+          opponent: opponent,
+          challengerSquadMember: challengerSquad.squadMember,
+          opponentSquadMember: opponentSquad.squadMember,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      rpcCache.invalidate(`pending_challenges_${gameId}_${opponent.toBase58()}`);
+      rpcCache.invalidate(`my_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ RESPOND TO CHALLENGE - cu confirmare
+  const respondToChallenge = async (
+    challengePDA: PublicKey,
+    gameId: number,
+    accept: boolean
+  ): Promise<string> => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [opponentStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+      const mySquad = await getSquadAccounts(gameId, wallet.publicKey);
+
+      const tx = await program.methods
+        .respondChallenge(accept)
+        .accounts({
+          challenge: challengePDA,
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          opponentState: opponentStatePDA,
+          opponent: wallet.publicKey,
+          opponentSquadMember: mySquad.squadMember,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      rpcCache.invalidate(`pending_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
+      rpcCache.invalidate(`active_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ MARK READY - cu confirmare
+  const markReady = async (challengePDA: PublicKey): Promise<string> => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .readyForGame()
+        .accounts({
+          challenge: challengePDA,
+          player: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ START MINI GAME - cu confirmare
+  const startMiniGame = async (challengePDA: PublicKey): Promise<string> => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .startMiniGame()
+        .accounts({
+          challenge: challengePDA,
+          player: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CLAIM MINI GAME WIN - cu confirmare
+  const claimMiniGameWin = async (
+    challengePDA: PublicKey,
+    gameId: number,
+    winner: PublicKey,
+    loser: PublicKey
+  ): Promise<string> => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [winnerStatePDA] = getPlayerStatePDA(program.programId, gameId, winner);
+      const [loserStatePDA] = getPlayerStatePDA(program.programId, gameId, loser);
+
+      const tx = await program.methods
+        .claimMiniGameWin(winner)
+        .accounts({
+          challenge: challengePDA,
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          winnerState: winnerStatePDA,
+          loserState: loserStatePDA,
           claimer: wallet.publicKey,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      rpcCache.invalidate(`active_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
-      rpcCache.invalidate(`my_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
-      rpcCache.invalidate(`player_stats_${gameId}_${wallet.publicKey!.toBase58()}`);
+      rpcCache.invalidate(`active_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
+      rpcCache.invalidate(`my_challenges_${gameId}_${wallet.publicKey!.toBase58()}`);
+      rpcCache.invalidate(`player_stats_${gameId}_${wallet.publicKey!.toBase58()}`);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const getPendingChallenges = async (gameId: number): Promise<Challenge[]> => {
+    if (!program || !wallet.publicKey) return [];
+
+    const cacheKey = `pending_challenges_${gameId}_${wallet.publicKey.toBase58()}`;
+
+    return rpcCache.get(
+      cacheKey,
+      async () => {
+        try {
+          const challenges = await (program.account as any).challenge.all([
+            {
+              memcmp: {
+                offset: ACCOUNT_HEADER + 8,
+                bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+              }
+            }
+          ]);
+          // Squad challenges can be answered by anyone in the challenged squad
+          const mySquad = (await getSquadAccounts(gameId, wallet.publicKey!)).squad;
+
+          return challenges
+            .filter((c: any) =>
+              (c.account.opponentSquad
+                ? !!mySquad && c.account.opponentSquad.equals(mySquad)
+                : c.account.opponent.equals(wallet.publicKey)) &&
+              c.account.status.pending
+            )
+            .map((c: any) => ({
+              publicKey: c.publicKey,
+              challengeId: c.account.challengeId.toNumber(),
+              gameId: c.account.gameId.toNumber(),
+              challenger: c.account.challenger,
+              opponent: c.account.opponent,
+              opponentSquad: c.account.opponentSquad ?? null,
+              betAmount: c.account.betAmount.toNumber() / LAMPORTS_PER_SOL,
+              gameType: Object.keys(c.account.gameType)[0] as MiniGameType,
+              status: Object.keys(c.account.status)[0] as ChallengeStatus,
+              createdAt: new Date(c.account.createdAt.toNumber() * 1000),
+              acceptedAt: c.account.acceptedAt ? new Date(c.account.acceptedAt.toNumber() * 1000) : null,
+              gameStartedAt: c.account.gameStartedAt ? new Date(c.account.gameStartedAt.toNumber() * 1000) : null,
+              winner: c.account.winner,
+              opponentDeclineCount: c.account.opponentDeclineCount,
+            }));
+        } catch (error) {
+          return [];
+        }
+      },
+      15000
+    );
+  };
+
+  const getMyChallenges = async (gameId: number): Promise<Challenge[]> => {
+    if (!program || !wallet.publicKey) return [];
+
+    const cacheKey = `my_challenges_${gameId}_${wallet.publicKey.toBase58()}`;
+
+    return rpcCache.get(
+      cacheKey,
+      async () => {
+        try {
+          const challenges = await (program.account as any).challenge.all([
+            {
+              memcmp: {
+                offset: ACCOUNT_HEADER + 8,
+                bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+              }
+            }
+          ]);
+
+          return challenges
+            .filter((c: any) => c.account.challenger.equals(wallet.publicKey))
+            .map((c: any) => ({
+              publicKey: c.publicKey,
+              challengeId: c.account.challengeId.toNumber(),
+              gameId: c.account.gameId.toNumber(),
+              challenger: c.account.challenger,
+              opponent: c.account.opponent,
+              opponentSquad: c.account.opponentSquad ?? null,
+              betAmount: c.account.betAmount.toNumber() / LAMPORTS_PER_SOL,
+              gameType: Object.keys(c.account.gameType)[0] as MiniGameType,
+              status: Object.keys(c.account.status)[0] as ChallengeStatus,
+              createdAt: new Date(c.account.createdAt.toNumber() * 1000),
+              acceptedAt: c.account.acceptedAt ? new Date(c.account.acceptedAt.toNumber() * 1000) : null,
+              gameStartedAt: c.account.gameStartedAt ? new Date(c.account.gameStartedAt.toNumber() * 1000) : null,
+              winner: c.account.winner,
+              opponentDeclineCount: c.account.opponentDeclineCount,
+            }));
+        } catch (error) {
+          return [];
+        }
+      },
+      12000
+    );
+  };
+
+  const getGameEvents = async (gameId: number): Promise<GameEvent[]> => {
+    if (!program) return [];
+
+    const cacheKey = `game_events_${gameId}`;
+
+    return rpcCache.get(
+      cacheKey,
+      async () => {
+        try {
+          const [gamePDA] = getGamePDA(program.programId, gameId);
+          const game = await (program.account as any).game.fetch(gamePDA);
+          const events: GameEvent[] = [];
+
+          if (game.events && Array.isArray(game.events)) {
+            game.events.forEach((event: any) => {
+              const eventType = Object.keys(event.eventType)[0];
+              const poolAffected = Object.keys(event.poolAffected)[0];
+
+              const mappedEvent: GameEvent = {
+                eventType: eventType as GameEvent['eventType'],
+                poolAffected: poolAffected as GameEvent['poolAffected'],
+                timestamp: new Date(event.timestamp.toNumber() * 1000),
+                phaseNumber: event.phaseNumber,
+                newValue: event.newValue?.toNumber(),
+                multiplier: event.multiplier?.toNumber(),
+                participant: event.participant || undefined,
+              };
+
+              events.push(mappedEvent);
+            });
+          }
+
+          return events;
+        } catch (error) {
+          return [];
+        }
+      },
+      15000
+    );
+  };
+
+  const getActiveChallenges = async (gameId: number): Promise<Challenge[]> => {
+    if (!program || !wallet.publicKey) return [];
+
+    const cacheKey = `active_challenges_${gameId}_${wallet.publicKey.toBase58()}`;
+
+    return rpcCache.get(
+      cacheKey,
+      async () => {
+        try {
+          const challenges = await (program.account as any).challenge.all([
+            {
+              memcmp: {
+                offset: ACCOUNT_HEADER + 8,
+                bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+              }
+            }
+          ]);
+
+          return challenges
+            .filter((c: any) =>
+              (c.account.challenger.equals(wallet.publicKey) || c.account.opponent.equals(wallet.publicKey)) &&
+              (c.account.status.accepted || c.account.status.bothReady || c.account.status.inProgress)
+            )
+            .map((c: any) => ({
+              publicKey: c.publicKey,
+              challengeId: c.account.challengeId.toNumber(),
+              gameId: c.account.gameId.toNumber(),
+              challenger: c.account.challenger,
+              opponent: c.account.opponent,
+              opponentSquad: c.account.opponentSquad ?? null,
+              betAmount: c.account.betAmount.toNumber() / LAMPORTS_PER_SOL,
+              gameType: Object.keys(c.account.gameType)[0] as MiniGameType,
+              status: Object.keys(c.account.status)[0] as ChallengeStatus,
+              createdAt: new Date(c.account.createdAt.toNumber() * 1000),
+              acceptedAt: c.account.acceptedAt ? new Date(c.account.acceptedAt.toNumber() * 1000) : null,
+              gameStartedAt: c.account.gameStartedAt ? new Date(c.account.gameStartedAt.toNumber() * 1000) : null,
+              winner: c.account.winner,
+              opponentDeclineCount: c.account.opponentDeclineCount,
+            }));
+        } catch (error) {
+          return [];
+        }
+      },
+      10000
+    );
+  };
+
+  // ✅ ADVANCE TO PHASE 3 - cu confirmare
+  const advanceToPhase3 = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+
+      const tx = await program.methods
+        .advanceToPhase3()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          caller: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('🎮 Phase 3 Started!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ MARK READY PHASE 3 - cu confirmare
+  const markReadyPhase3 = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [readyStatePDA] = getPhase3ReadyStatePDA(
+        program.programId,
+        gameId,
+        wallet.publicKey
+      );
+      const squad = await getSquadAccounts(gameId, wallet.publicKey);
+
+      const tx = await program.methods
+        .markReadyPhase3()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          readyState: readyStatePDA,
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
+          squadMember: squad.squadMember,
+          squad: squad.squad,
+          player: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('Marked as READY! ✓');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ SUBMIT PHASE 3 WINNER - cu confirmare
+  const submitPhase3Winner = async (gameId: number, winner: PublicKey) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    // Squad games declare the winning player's squad
+    if (games.find((g) => g.gameId === gameId)?.squadSize) {
+      const { squad } = await getSquadAccounts(gameId, winner);
+      if (!squad) {
+        throw new Error('Winner is not in a squad');
+      }
+      return submitPhase3SquadWinner(gameId, squad.toBase58());
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+
+      const tx = await program.methods
+        .submitPhase3Winner(winner)
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          roles: await getRolesAccount(program.provider.connection, program.programId),
+          winnerEntry: getPlayerEntryPDA(program.programId, gameId, winner)[0],
+          submitter: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('🏆 Winner declared!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CLAIM PHASE 3 PRIZE - cu confirmare
+  const claimPhase3Prize = async (gameId: number) => {
+    console.log('🏆 [claimPhase3Prize] Starting...');
+
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    // Squad games pay each member their share
+    if (games.find((g) => g.gameId === gameId)?.squadSize) {
+      const { squad } = await getSquadAccounts(gameId, wallet.publicKey);
+      if (!squad) {
+        throw new Error('You are not in a squad');
+      }
+      return claimSquadPrize(gameId, squad.toBase58());
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
+
+      const tx = await program.methods
+        .claimPhase3Prize()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          winner: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          winnerTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      console.log('📝 Transaction sent:', tx);
+      await confirmTransaction(program.provider.connection, tx);
+      console.log('✅ Prize claimed successfully!');
+
+      toast.success('💰 Prize claimed!');
+      await fetchGames(program);
+      return tx;
+    } catch (error: any) {
+      console.error('❌ Error claiming prize:', error);
+      throw error;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ START PHASE 3 GAME - cu confirmare
+  const startPhase3Game = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+
+      const readyStates = await getPhase3ReadyStates(gameId);
+      const remainingAccounts = readyStates.map(state => ({
+        pubkey: getPhase3ReadyStatePDA(
+          program.programId,
+          gameId,
+          new PublicKey(state.player)
+        )[0],
+        isSigner: false,
+        isWritable: false,
+      }));
+      // Squad games find the winner among the ready squads
+      const squads = await fetchSquads(gameId);
+      squads.filter((squad) => squad.phase3Ready).forEach((squad) => remainingAccounts.push({
+        pubkey: new PublicKey(squad.address),
+        isSigner: false,
+        isWritable: false,
+      }));
+
+      const tx = await program.methods
+        .startPhase3Game()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          caller: wallet.publicKey,
+        })
+        .remainingAccounts(remainingAccounts)
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('🎮 THE PURGE BEGINS!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const getPhase3ReadyStates = useCallback(async (gameId: number): Promise<Phase3ReadyState[]> => {
+    if (!program) return [];
+
+    try {
+      const allReadyStates = await program.account.phase3ReadyState.all([
+        {
+          memcmp: {
+            offset: ACCOUNT_HEADER,
+            bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+          }
+        }
+      ]);
+
+      return allReadyStates.map((state: any) => ({
+        gameId: state.account.gameId.toNumber(),
+        player: state.account.player.toBase58(),
+        ready: state.account.ready,
+        virtualBalance: state.account.virtualBalance
+          ? state.account.virtualBalance.toNumber()
+          : 0,
+        markedReadyAt: new Date(state.account.markedReadyAt.toNumber() * 1000),
+      }));
+    } catch (error) {
+      return [];
+    }
+  }, [program]);
+
+  const getPhase3ReadyStatePDA = (
+    programId: PublicKey,
+    gameId: number,
+    player: PublicKey
+  ): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('phase3_ready'),
+        new BN(gameId).toArrayLike(Buffer, 'le', 8),
+        player.toBuffer(),
+      ],
+      programId
+    );
+  };
+
+  // ✅ CLAIM PLATFORM FEE - cu confirmare
+  const claimPlatformFee = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [treasuryPDA] = getTreasuryPDA(program.programId);
+      const token = await getGameTokenAccounts(gamePDA, treasuryPDA);
+
+      const tx = await program.methods
+        .claimPlatformFee()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          roles: await getRolesAccount(program.provider.connection, program.programId),
+          treasury: treasuryPDA,
+          feeRecord: getFeeRecordPDA(program.programId, gameId)[0],
+          treasurer: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          treasuryTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions(token.mint && token.tokenProgram
+          ? [createAssociatedTokenAccountIdempotentInstruction(wallet.publicKey, treasuryPDA, token.mint, token.tokenProgram)]
+          : [])
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('💰 Fee moved to treasury!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CLAIM CREATOR FEE - creatorul isi ia partea din taxa platformei
+  const claimCreatorFee = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
+
+      const tx = await program.methods
+        .claimCreatorFee()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          creator: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          creatorTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('💰 Creator fee claimed!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ FORCE REFUND EXPIRED GAME - cu confirmare
+  const forceRefundExpiredGame = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
+
+      const tx = await program.methods
+        .forceRefundExpiredGame()
+        .accounts({
+          game: gamePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          playerEntry: getPlayerEntryPDA(program.programId, gameId, wallet.publicKey)[0],
+          creatorBond: await getCreatorBondAccount(program.provider.connection, program.programId, gameId),
+          player: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          playerTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('💰 Refund claimed!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CRANK REFUNDS - oricine trimite refund-urile ramase unui joc anulat/expirat (in loturi)
+  const crankRefunds = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const connection = program.provider.connection;
+      const game = games.find((g) => g.gameId === gameId);
+      if (!game) throw new Error(`Game ${gameId} not loaded`);
+
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const token = await getGameTokenAccounts(gamePDA, wallet.publicKey);
+      const groups = await getRefundRemainingAccounts(connection, program.programId, game);
+      const signatures: string[] = [];
+      for (let i = 0; i < groups.length; i += CRANK_BATCH_SIZE) {
+        const tx = await program.methods
+          .crankRefunds()
+          .accounts({
+            game: gamePDA,
+            creatorBond: await getCreatorBondAccount(connection, program.programId, gameId),
+            cranker: wallet.publicKey,
+            vault: getVaultPDA(program.programId, gameId)[0],
+            mint: token.mint,
+            tokenVault: token.tokenVault,
+            tokenProgram: token.tokenProgram,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(groups.slice(i, i + CRANK_BATCH_SIZE).flat())
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+        await confirmTransaction(connection, tx);
+        signatures.push(tx);
+      }
+
+      toast.success(`💸 Refunded ${groups.length} players`);
+      await fetchGames(program);
+      return signatures;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CLOSE GAME - cu confirmare
+  const closeGame = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const game = await (program.account as any).game.fetch(gamePDA);
+      const token = await getGameTokenAccounts(gamePDA, game.creator);
+      // A bond forfeited after a penalty is collected into the treasury
+      const [treasuryPDA] = getTreasuryPDA(program.programId);
+      const collectsBond = decodeGameStatus(game.status) === 'ExpiredWithPenalty'
+        && !!(await program.provider.connection.getAccountInfo(treasuryPDA));
+
+      const tx = await program.methods
+        .closeGame()
+        .accounts({
+          game: gamePDA,
+          creator: game.creator,
+          creatorBond: await getCreatorBondAccount(program.provider.connection, program.programId, gameId),
+          treasury: collectsBond ? treasuryPDA : null,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint: token.mint,
+          tokenVault: token.tokenVault,
+          creatorTokenAccount: token.ownerTokenAccount,
+          tokenProgram: token.tokenProgram,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('🧹 Game closed, rent reclaimed!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ CLOSE MY GAME ACCOUNTS - recupereaza rent-ul dupa terminarea jocului
+  const closeMyGameAccounts = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const connection = program.provider.connection;
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [playerStatePDA] = getPlayerStatePDA(program.programId, gameId, wallet.publicKey);
+      const [readyStatePDA] = getPhase3ReadyStatePDA(program.programId, gameId, wallet.publicKey);
+      const [playerEntryPDA] = getPlayerEntryPDA(program.programId, gameId, wallet.publicKey);
+      const [squadMemberPDA] = getSquadMemberPDA(program.programId, gameId, wallet.publicKey);
+      const [squadPDA] = getSquadPDA(program.programId, gameId, wallet.publicKey);
+      const signatures: string[] = [];
+
+      if (await connection.getAccountInfo(playerStatePDA)) {
+        const tx = await program.methods
+          .closePlayerState()
+          .accounts({ playerState: playerStatePDA, game: gamePDA, player: wallet.publicKey })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      if (await connection.getAccountInfo(readyStatePDA)) {
+        const tx = await program.methods
+          .closePhase3ReadyState()
+          .accounts({ readyState: readyStatePDA, game: gamePDA, player: wallet.publicKey })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      if (await connection.getAccountInfo(playerEntryPDA)) {
+        const tx = await program.methods
+          .closePlayerEntry()
+          .accounts({ playerEntry: playerEntryPDA, game: gamePDA, player: wallet.publicKey })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      if (await connection.getAccountInfo(squadMemberPDA)) {
+        const tx = await program.methods
+          .closeSquadMember()
+          .accounts({ squadMember: squadMemberPDA, game: gamePDA, player: wallet.publicKey })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      if (await connection.getAccountInfo(squadPDA)) {
+        const tx = await program.methods
+          .closeSquad()
+          .accounts({ squad: squadPDA, game: gamePDA, leader: wallet.publicKey })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      const myChallenges = await (program.account as any).challenge.all([
+        {
+          memcmp: {
+            offset: ACCOUNT_HEADER + 8,
+            bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+          }
+        },
+        {
+          memcmp: {
+            offset: ACCOUNT_HEADER + 8 + 8,
+            bytes: wallet.publicKey.toBase58(),
+          }
+        }
+      ]);
+      for (const challenge of myChallenges) {
+        const tx = await program.methods
+          .closeChallenge()
+          .accounts({ challenge: challenge.publicKey, game: gamePDA, challenger: wallet.publicKey })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      if (signatures.length > 0) {
+        toast.success(`🧹 Closed ${signatures.length} account(s), rent reclaimed!`);
+      }
+      return signatures;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ SWEEP GAME ACCOUNTS - creator/admin inchide conturile abandonate dupa perioada de gratie
+  const sweepGameAccounts = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const gameIdBytes = bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8));
+      const accounts = program.account as any;
+      const byGame = (offset: number) => [{ memcmp: { offset, bytes: gameIdBytes } }];
+
+      // (account, rent recipient) pairs - rent goes back to whoever paid it
+      const [
+        entries, playerStates, poolStates, challenges, readyStates,
+        waitlists, waitlistEntries, sponsorRecords, squads, squadMembers,
+      ] = await Promise.all([
+        accounts.playerEntry.all(byGame(ACCOUNT_HEADER)),
+        accounts.playerGameState.all(byGame(ACCOUNT_HEADER + 32)),
+        accounts.gamePoolState.all(byGame(ACCOUNT_HEADER)),
+        accounts.challenge.all(byGame(ACCOUNT_HEADER + 8)),
+        accounts.phase3ReadyState.all(byGame(ACCOUNT_HEADER)),
+        accounts.waitlist.all(byGame(ACCOUNT_HEADER)),
+        accounts.waitlistEntry.all(byGame(ACCOUNT_HEADER)),
+        accounts.sponsorRecord.all(byGame(ACCOUNT_HEADER)),
+        accounts.squad.all(byGame(ACCOUNT_HEADER)),
+        accounts.squadMember.all(byGame(ACCOUNT_HEADER)),
+      ]);
+      const pairs: [PublicKey, PublicKey][] = [
+        ...entries.map((a: any) => [a.publicKey, a.account.player]),
+        ...playerStates.map((a: any) => [a.publicKey, a.account.player]),
+        ...poolStates.map((a: any) => [a.publicKey, a.account.payer]),
+        ...challenges.map((a: any) => [a.publicKey, a.account.challenger]),
+        ...readyStates.map((a: any) => [a.publicKey, a.account.player]),
+        ...waitlists.map((a: any) => [a.publicKey, a.account.payer]),
+        ...waitlistEntries.map((a: any) => [a.publicKey, a.account.player]),
+        ...sponsorRecords.map((a: any) => [a.publicKey, a.account.sponsor]),
+        ...squads.map((a: any) => [a.publicKey, a.account.leader]),
+        ...squadMembers.map((a: any) => [a.publicKey, a.account.player]),
+      ];
+
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [gameRegistryPDA] = getGameRegistryPDA(program.programId);
+      const SWEEP_BATCH_SIZE = 10;
+      const signatures: string[] = [];
+
+      for (let i = 0; i < pairs.length; i += SWEEP_BATCH_SIZE) {
+        const remainingAccounts = pairs.slice(i, i + SWEEP_BATCH_SIZE).flatMap(([account, recipient]) => [
+          { pubkey: account, isWritable: true, isSigner: false },
+          { pubkey: recipient, isWritable: true, isSigner: false },
+        ]);
+
+        const tx = await program.methods
+          .sweepGameAccounts(new BN(gameId))
+          .accounts({
+            game: gamePDA,
+            gameRegistry: gameRegistryPDA,
+            authority: wallet.publicKey,
+          })
+          .remainingAccounts(remainingAccounts)
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      toast.success(`🧹 Swept ${pairs.length} account(s)`);
+      return signatures;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // Simulates audit_game and returns its AuditResult event (read-only, no signature)
+  const auditGame = async (gameId: number) => {
+    if (!program) {
+      throw new Error('Program not initialized');
+    }
+
+    const [gamePDA] = getGamePDA(program.programId, gameId);
+    const game = await (program.account as any).game.fetch(gamePDA);
+    const token = await getGameTokenAccounts(gamePDA, game.creator);
+
+    const result = await program.methods
+      .auditGame()
+      .accounts({
+        game: gamePDA,
+        vault: getVaultPDA(program.programId, gameId)[0],
+        mint: token.mint,
+        tokenVault: token.tokenVault,
+        tokenProgram: token.tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .simulate();
+
+    const event = result.events.find((e: any) => e.name === 'auditResult');
+    return event ? event.data : null;
+  };
+
+  // ✅ MIGRATE GAME - admin rescrie jocurile vechi in layout-ul zero-copy
+  const migrateGame = async (gameId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [gameRegistryPDA] = getGameRegistryPDA(program.programId);
+
+      const tx = await program.methods
+        .migrateGame(new BN(gameId))
+        .accounts({
+          game: gamePDA,
+          gameRegistry: gameRegistryPDA,
+          globalConfig: getGlobalConfigPDA(program.programId)[0],
+          vault: getVaultPDA(program.programId, gameId)[0],
+          roster: getLegacyRosterPDA(program.programId, gameId)[0],
+          admin: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      await migrateRoster(program, wallet.publicKey, gameId);
+      toast.success('🛠️ Game migrated!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ MIGRATE LEGACY ACCOUNTS - admin aduce toate conturile vechi la layout-ul curent
+  const migrateLegacyAccounts = async () => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const connection = program.provider.connection;
+      const coder = program.coder.accounts as any;
+      const [gameRegistryPDA] = getGameRegistryPDA(program.programId);
+      const signatures: string[] = [];
+
+      // Registry first - every other instruction reads it
+      const registry = await connection.getAccountInfo(gameRegistryPDA);
+      if (registry && registry.data.length === LEGACY_REGISTRY_SIZE) {
+        const tx = await program.methods
+          .migrateRegistry()
+          .accounts({ gameRegistry: gameRegistryPDA, admin: wallet.publicKey, systemProgram: SystemProgram.programId })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      // Games snapshot the global config while migrating, so it must exist
+      const [globalConfigPDA] = getGlobalConfigPDA(program.programId);
+      const globalConfig = await connection.getAccountInfo(globalConfigPDA);
+      if (!globalConfig) {
+        const tx = await program.methods
+          .initializeGlobalConfig()
+          .accounts({
+            globalConfig: globalConfigPDA,
+            gameRegistry: gameRegistryPDA,
+            admin: wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx);
+      }
+
+      const legacyGames = await connection.getProgramAccounts(program.programId, {
+        filters: [coder.memcmp('Game'), { dataSize: LEGACY_GAME_SIZE }],
+      });
+      for (const { account } of legacyGames) {
+        const gameId = new BN(account.data.subarray(8, 16), 'le');
+        const tx = await program.methods
+          .migrateGame(gameId)
+          .accounts({
+            game: getGamePDA(program.programId, gameId.toNumber())[0],
+            gameRegistry: gameRegistryPDA,
+            globalConfig: globalConfigPDA,
+            vault: getVaultPDA(program.programId, gameId.toNumber())[0],
+            roster: getLegacyRosterPDA(program.programId, gameId.toNumber())[0],
+            admin: wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+        signatures.push(tx, ...await migrateRoster(program, wallet.publicKey, gameId.toNumber()));
+      }
+
+      const migrations: Record<string, string> = {
+        PlayerGameState: 'migratePlayerState',
+        GamePoolState: 'migratePoolState',
+        Challenge: 'migrateChallenge',
+        Phase3ReadyState: 'migratePhase3ReadyState',
+      };
+      for (const [accountName, method] of Object.entries(migrations)) {
+        const legacy = await connection.getProgramAccounts(program.programId, {
+          filters: [coder.memcmp(accountName), { dataSize: LEGACY_ACCOUNT_SIZES[accountName] }],
+        });
+        for (const { pubkey } of legacy) {
+          const tx = await (program.methods as any)[method]()
+            .accounts({
+              account: pubkey,
+              gameRegistry: gameRegistryPDA,
+              admin: wallet.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc({ skipPreflight: false, commitment: 'confirmed' });
+          signatures.push(tx);
+        }
+      }
+
+      toast.success(`🛠️ Migrated ${signatures.length} accounts`);
+      await fetchGames(program);
+      return signatures;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ PROPOSE ADMIN - adminul curent nominalizeaza un nou admin (pas 1 din 2)
+  const proposeAdmin = async (newAdmin: string, expiresAt?: Date) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .proposeAdmin(
+          new PublicKey(newAdmin),
+          expiresAt ? new BN(Math.floor(expiresAt.getTime() / 1000)) : null
+        )
+        .accounts({
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          admin: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('👑 Admin transfer proposed!');
       return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  const getPendingChallenges = async (gameId: number): Promise<Challenge[]> => {
-    if (!program || !wallet.publicKey) return [];
-
-    const cacheKey = `pending_challenges_${gameId}_${wallet.publicKey.toBase58()}`;
+  // ✅ ACCEPT ADMIN - adminul propus accepta transferul (pas 2 din 2)
+  const acceptAdmin = async () => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
 
-    return rpcCache.get(
-      cacheKey,
-      async () => {
-        try {
-          const challenges = await (program.account as any).challenge.all([
-            {
-              memcmp: {
-                offset: 8 + 8,
-                bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
-              }
-            }
-          ]);
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .acceptAdmin()
+        .accounts({
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          newAdmin: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-          return challenges
-            .filter((c: any) =>
-              c.account.opponent.equals(wallet.publicKey) &&
-              c.account.status.pending
-            )
-            .map((c: any) => ({
-              publicKey: c.publicKey,
-              challengeId: c.account.challengeId.toNumber(),
-              gameId: c.account.gameId.toNumber(),
-              challenger: c.account.challenger,
-              opponent: c.account.opponent,
-              betAmount: c.account.betAmount.toNumber() / LAMPORTS_PER_SOL,
-              gameType: Object.keys(c.account.gameType)[0] as MiniGameType,
-              status: Object.keys(c.account.status)[0] as ChallengeStatus,
-              createdAt: new Date(c.account.createdAt.toNumber() * 1000),
-              acceptedAt: c.account.acceptedAt ? new Date(c.account.acceptedAt.toNumber() * 1000) : null,
-              gameStartedAt: c.account.gameStartedAt ? new Date(c.account.gameStartedAt.toNumber() * 1000) : null,
-              winner: c.account.winner,
-              opponentDeclineCount: c.account.opponentDeclineCount,
-            }));
-        } catch (error) {
-          return [];
-        }
-      },
-      15000
-    );
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('👑 You are now the admin!');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
   };
 
-  const getMyChallenges = async (gameId: number): Promise<Challenge[]> => {
-    if (!program || !wallet.publicKey) return [];
+  // ✅ FETCH ROLES - cheile cu roluri privilegiate (operator, referee, treasurer, admin)
+  const fetchRoles = useCallback(async (): Promise<RoleMember[]> => {
+    if (!program) return [];
+    const rolesAccount = await (program.account as any).roleRegistry.fetchNullable(getRolesPDA(program.programId)[0]);
+    if (!rolesAccount) return [];
+    return rolesAccount.members.map((member: any) => ({
+      key: member.key.toBase58(),
+      roles: ROLES.filter((_, i) => member.roles & (1 << i)),
+    }));
+  }, [program]);
 
-    const cacheKey = `my_challenges_${gameId}_${wallet.publicKey.toBase58()}`;
+  // ✅ SET ROLES - adminul de roluri seteaza rolurile unei chei (lista goala = revocare)
+  const setRoles = async (member: string, roles: Role[]) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
 
-    return rpcCache.get(
-      cacheKey,
-      async () => {
-        try {
-          const challenges = await (program.account as any).challenge.all([
-            {
-              memcmp: {
-                offset: 8 + 8,
-                bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
-              }
-            }
-          ]);
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .setRoles(new PublicKey(member), roles.reduce((mask, role) => mask | (1 << ROLES.indexOf(role)), 0))
+        .accounts({
+          roleRegistry: getRolesPDA(program.programId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          authority: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-          return challenges
-            .filter((c: any) => c.account.challenger.equals(wallet.publicKey))
-            .map((c: any) => ({
-              publicKey: c.publicKey,
-              challengeId: c.account.challengeId.toNumber(),
-              gameId: c.account.gameId.toNumber(),
-              challenger: c.account.challenger,
-              opponent: c.account.opponent,
-              betAmount: c.account.betAmount.toNumber() / LAMPORTS_PER_SOL,
-              gameType: Object.keys(c.account.gameType)[0] as MiniGameType,
-              status: Object.keys(c.account.status)[0] as ChallengeStatus,
-              createdAt: new Date(c.account.createdAt.toNumber() * 1000),
-              acceptedAt: c.account.acceptedAt ? new Date(c.account.acceptedAt.toNumber() * 1000) : null,
-              gameStartedAt: c.account.gameStartedAt ? new Date(c.account.gameStartedAt.toNumber() * 1000) : null,
-              winner: c.account.winner,
-              opponentDeclineCount: c.account.opponentDeclineCount,
-            }));
-        } catch (error) {
-          return [];
-        }
-      },
-      12000
-    );
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('🔑 Roles updated!');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
   };
 
-  const getGameEvents = async (gameId: number): Promise<GameEvent[]> => {
+  // ✅ FETCH TREASURY - sold, totaluri cumulate si taxele colectate pe fiecare joc
+  const fetchTreasury = useCallback(async (): Promise<TreasuryInfo | null> => {
+    if (!program) return null;
+    const [treasuryPDA] = getTreasuryPDA(program.programId);
+    const treasury = await (program.account as any).treasury.fetchNullable(treasuryPDA);
+    if (!treasury) return null;
+    const records = await (program.account as any).gameFeeRecord.all();
+    return {
+      balance: (await program.provider.connection.getBalance(treasuryPDA)) / LAMPORTS_PER_SOL,
+      totalSolCollected: treasury.totalSolCollected.toNumber() / LAMPORTS_PER_SOL,
+      totalSolWithdrawn: treasury.totalSolWithdrawn.toNumber() / LAMPORTS_PER_SOL,
+      feeRecords: records
+        .map(({ account }: any) => ({
+          gameId: account.gameId.toNumber(),
+          mint: optionalKey(account.mint)?.toBase58() ?? null,
+          totalCollected: account.totalCollected.toNumber(),
+          lastCollectedAt: new Date(account.lastCollectedAt.toNumber() * 1000),
+        }))
+        .sort((a: GameFeeRecord, b: GameFeeRecord) => b.gameId - a.gameId),
+    };
+  }, [program]);
+
+  // ✅ TEMPLATES - jocuri recurente create de operatori
+  const fetchTemplates = useCallback(async (): Promise<GameTemplateInfo[]> => {
     if (!program) return [];
+    const templates = await (program.account as any).gameTemplate.all();
+    const balances = await program.provider.connection.getMultipleAccountsInfo(templates.map((t: any) => t.publicKey));
+    // Every template has the same fixed size
+    const rentExempt = balances[0]
+      ? await program.provider.connection.getMinimumBalanceForRentExemption(balances[0].data.length)
+      : 0;
+    return templates
+      .map(({ publicKey, account }: any, i: number) => ({
+        publicKey: publicKey.toBase58(),
+        templateId: account.templateId.toNumber(),
+        operator: account.operator.toBase58(),
+        name: account.name,
+        entryFee: account.entryFee.toNumber(),
+        maxPlayers: account.maxPlayers,
+        mint: account.mint?.toBase58() ?? null,
+        cadenceHours: account.cadence.toNumber() / 3600,
+        leadTimeMinutes: account.leadTime.toNumber() / 60,
+        nextStartTime: new Date(account.nextStartTime.toNumber() * 1000),
+        gamesSpawned: account.gamesSpawned.toNumber(),
+        lastGameId: account.lastGameId?.toNumber() ?? null,
+        active: account.active,
+        budget: Math.max(0, (balances[i]?.lamports ?? 0) - rentExempt) / LAMPORTS_PER_SOL,
+      }))
+      .sort((a: GameTemplateInfo, b: GameTemplateInfo) => a.nextStartTime.getTime() - b.nextStartTime.getTime());
+  }, [program]);
 
-    const cacheKey = `game_events_${gameId}`;
+  const createTemplate = async (params: CreateTemplateParams) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
 
-    return rpcCache.get(
-      cacheKey,
-      async () => {
-        try {
-          const [gamePDA] = getGamePDA(program.programId, gameId);
-          const game = await (program.account as any).game.fetch(gamePDA);
-          const events: GameEvent[] = [];
+    setLoading(true);
+    try {
+      const mint = params.mint ? new PublicKey(params.mint) : null;
+      let unit = LAMPORTS_PER_SOL;
+      if (mint) {
+        const supply = await program.provider.connection.getTokenSupply(mint);
+        unit = 10 ** supply.value.decimals;
+      }
+      const globalConfig = await (program.account as any).globalConfig.fetch(getGlobalConfigPDA(program.programId)[0]);
+      const mine = (await fetchTemplates()).filter((t) => t.operator === wallet.publicKey!.toBase58());
+      const templateId = mine.reduce((next, t) => Math.max(next, t.templateId + 1), 0);
+      const [templatePDA] = getTemplatePDA(program.programId, wallet.publicKey, templateId);
+      const [rolesPDA] = getRolesPDA(program.programId);
+
+      const fundIx = await program.methods
+        .fundTemplate(new BN(Math.round(params.budget * LAMPORTS_PER_SOL)))
+        .accounts({
+          template: templatePDA,
+          funder: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
 
-          if (game.events && Array.isArray(game.events)) {
-            game.events.forEach((event: any) => {
-              const eventType = Object.keys(event.eventType)[0];
-              const poolAffected = Object.keys(event.poolAffected)[0];
+      const tx = await program.methods
+        .createTemplate(new BN(templateId), {
+          name: params.gameName,
+          entryFee: new BN(Math.round(params.entryFee * unit)),
+          maxPlayers: params.maxPlayers,
+          rules: rulesParam(params.rules, params.gameDurationHours, globalConfig.params.minPlayersToStart),
+          access: await accessParam(params.access),
+          cadence: new BN(Math.round(params.cadenceHours * 3600)),
+          leadTime: new BN(Math.round(params.leadTimeMinutes * 60)),
+          nextStartTime: new BN(Math.floor(params.firstStartTime.getTime() / 1000)),
+        })
+        .accounts({
+          template: templatePDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          globalConfig: getGlobalConfigPDA(program.programId)[0],
+          roles: (await program.provider.connection.getAccountInfo(rolesPDA)) ? rolesPDA : null,
+          operator: wallet.publicKey,
+          mint,
+          systemProgram: SystemProgram.programId,
+        })
+        .postInstructions(params.budget > 0 ? [fundIx] : [])
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-              const mappedEvent: GameEvent = {
-                eventType: eventType as GameEvent['eventType'],
-                poolAffected: poolAffected as GameEvent['poolAffected'],
-                timestamp: new Date(event.timestamp.toNumber() * 1000),
-                phaseNumber: event.phaseNumber,
-                newValue: event.newValue?.toNumber(),
-                multiplier: event.multiplier?.toNumber(),
-                participant: event.participant || undefined,
-              };
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('Template created');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
 
-              events.push(mappedEvent);
-            });
-          }
+  // Pause or resume a template; a stale start time moves to the next future slot
+  const setTemplateActive = async (templateKey: string, active: boolean) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
 
-          return events;
-        } catch (error) {
-          return [];
-        }
-      },
-      15000
-    );
-  };
+    setLoading(true);
+    try {
+      const template = new PublicKey(templateKey);
+      const account = await (program.account as any).gameTemplate.fetch(template);
+      const [rolesPDA] = getRolesPDA(program.programId);
+      const now = Math.floor(Date.now() / 1000) + 60;
+      const cadence = account.cadence.toNumber();
+      let nextStart = account.nextStartTime.toNumber();
+      if (nextStart <= now) {
+        nextStart += (Math.floor((now - nextStart) / cadence) + 1) * cadence;
+      }
 
-  const getActiveChallenges = async (gameId: number): Promise<Challenge[]> => {
-    if (!program || !wallet.publicKey) return [];
+      const tx = await program.methods
+        .updateTemplate({
+          name: account.name,
+          entryFee: account.entryFee,
+          maxPlayers: account.maxPlayers,
+          rules: account.rules,
+          access: account.access,
+          cadence: account.cadence,
+          leadTime: account.leadTime,
+          nextStartTime: new BN(nextStart),
+        }, active)
+        .accounts({
+          template,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          globalConfig: getGlobalConfigPDA(program.programId)[0],
+          roles: (await program.provider.connection.getAccountInfo(rolesPDA)) ? rolesPDA : null,
+          operator: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-    const cacheKey = `active_challenges_${gameId}_${wallet.publicKey.toBase58()}`;
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success(active ? 'Template resumed' : 'Template paused');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
 
-    return rpcCache.get(
-      cacheKey,
-      async () => {
-        try {
-          const challenges = await (program.account as any).challenge.all([
-            {
-              memcmp: {
-                offset: 8 + 8,
-                bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
-              }
-            }
-          ]);
+  const fundTemplate = async (templateKey: string, amountSol: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
 
-          return challenges
-            .filter((c: any) =>
-              (c.account.challenger.equals(wallet.publicKey) || c.account.opponent.equals(wallet.publicKey)) &&
-              (c.account.status.accepted || c.account.status.bothReady || c.account.status.inProgress)
-            )
-            .map((c: any) => ({
-              publicKey: c.publicKey,
-              challengeId: c.account.challengeId.toNumber(),
-              gameId: c.account.gameId.toNumber(),
-              challenger: c.account.challenger,
-              opponent: c.account.opponent,
-              betAmount: c.account.betAmount.toNumber() / LAMPORTS_PER_SOL,
-              gameType: Object.keys(c.account.gameType)[0] as MiniGameType,
-              status: Object.keys(c.account.status)[0] as ChallengeStatus,
-              createdAt: new Date(c.account.createdAt.toNumber() * 1000),
-              acceptedAt: c.account.acceptedAt ? new Date(c.account.acceptedAt.toNumber() * 1000) : null,
-              gameStartedAt: c.account.gameStartedAt ? new Date(c.account.gameStartedAt.toNumber() * 1000) : null,
-              winner: c.account.winner,
-              opponentDeclineCount: c.account.opponentDeclineCount,
-            }));
-        } catch (error) {
-          return [];
-        }
-      },
-      10000
-    );
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .fundTemplate(new BN(Math.round(amountSol * LAMPORTS_PER_SOL)))
+        .accounts({
+          template: new PublicKey(templateKey),
+          funder: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success(`Added ${amountSol} SOL to the template budget`);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
   };
 
-  // ✅ ADVANCE TO PHASE 3 - cu confirmare
-  const advanceToPhase3 = async (gameId: number) => {
+  const closeTemplate = async (templateKey: string) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
-
       const tx = await program.methods
-        .advanceToPhase3()
+        .closeTemplate()
         .accounts({
-          game: gamePDA,
-          caller: wallet.publicKey,
+          template: new PublicKey(templateKey),
+          operator: wallet.publicKey,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      toast.success('🎮 Phase 3 Started!');
-      await fetchGames(program);
+      toast.success('Template closed');
       return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ MARK READY PHASE 3 - cu confirmare
-  const markReadyPhase3 = async (gameId: number) => {
+  // Anyone can spawn the next game once due - the template pays back the rent
+  const spawnFromTemplate = async (templateKey: string) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
+      const template = new PublicKey(templateKey);
+      const account = await (program.account as any).gameTemplate.fetch(template);
+      const [gameRegistryPDA] = getGameRegistryPDA(program.programId);
+      const gameRegistry = await (program.account as any).gameRegistry.fetch(gameRegistryPDA);
+      const gameId = gameRegistry.gameCount.toNumber();
       const [gamePDA] = getGamePDA(program.programId, gameId);
-      const [readyStatePDA] = getPhase3ReadyStatePDA(
-        program.programId,
-        gameId,
-        wallet.publicKey
-      );
+      const mint: PublicKey | null = account.mint ?? null;
+      const tokenProgram = mint ? await getMintTokenProgram(program.provider.connection, mint) : null;
+      const [rolesPDA] = getRolesPDA(program.programId);
 
       const tx = await program.methods
-        .markReadyPhase3()
+        .spawnFromTemplate()
         .accounts({
+          template,
           game: gamePDA,
-          readyState: readyStatePDA,
-          player: wallet.publicKey,
+          gameRegistry: gameRegistryPDA,
+          globalConfig: getGlobalConfigPDA(program.programId)[0],
+          roles: (await program.provider.connection.getAccountInfo(rolesPDA)) ? rolesPDA : null,
+          creatorBond: getCreatorBondPDA(program.programId, gameId)[0],
+          spawner: wallet.publicKey,
+          vault: getVaultPDA(program.programId, gameId)[0],
+          mint,
+          tokenVault: mint && tokenProgram ? getAssociatedTokenAddress(mint, gamePDA, tokenProgram) : null,
+          tokenProgram,
+          associatedTokenProgram: mint ? ASSOCIATED_TOKEN_PROGRAM_ID : null,
           systemProgram: SystemProgram.programId,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      toast.success('Marked as READY! ✓');
+      toast.success(`Game #${gameId} spawned`);
       await fetchGames(program);
       return tx;
     } finally {
@@ -1421,26 +3644,97 @@ export function useSolanaGame() {
     }
   };
 
-  // ✅ SUBMIT PHASE 3 WINNER - cu confirmare
-  const submitPhase3Winner = async (gameId: number, winner: PublicKey) => {
+  // ✅ WITHDRAW TREASURY - trezorierul retrage SOL (sau tokenul `mint`) din trezorerie
+  const withdrawTreasury = async (recipient: string, amount: number, mint?: string) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const connection = program.provider.connection;
+      const [treasuryPDA] = getTreasuryPDA(program.programId);
+      const recipientKey = new PublicKey(recipient);
+      const mintKey = mint ? new PublicKey(mint) : null;
+      let unit = LAMPORTS_PER_SOL;
+      let tokenProgram: PublicKey | null = null;
+      if (mintKey) {
+        const supply = await connection.getTokenSupply(mintKey);
+        unit = 10 ** supply.value.decimals;
+        tokenProgram = await getMintTokenProgram(connection, mintKey);
+      }
 
       const tx = await program.methods
-        .submitPhase3Winner(winner)
+        .withdrawTreasury(new BN(Math.round(amount * unit)))
         .accounts({
-          game: gamePDA,
-          submitter: wallet.publicKey,
+          treasury: treasuryPDA,
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          roles: await getRolesAccount(connection, program.programId),
+          treasurer: wallet.publicKey,
+          recipient: recipientKey,
+          mint: mintKey,
+          treasuryTokenAccount: mintKey && tokenProgram ? getAssociatedTokenAddress(mintKey, treasuryPDA, tokenProgram) : null,
+          recipientTokenAccount: mintKey && tokenProgram ? getAssociatedTokenAddress(mintKey, recipientKey, tokenProgram) : null,
+          tokenProgram,
+        })
+        .preInstructions(mintKey && tokenProgram
+          ? [createAssociatedTokenAccountIdempotentInstruction(wallet.publicKey, recipientKey, mintKey, tokenProgram)]
+          : [])
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(connection, tx);
+      toast.success('🏦 Treasury withdrawal sent!');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ SET PROGRAM PAUSED - oprire de urgenta pentru toate jocurile (fara timelock)
+  const setProgramPaused = async (paused: boolean) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .setProgramPaused(paused)
+        .accounts({
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          roles: await getRolesAccount(program.provider.connection, program.programId),
+          admin: wallet.publicKey,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      toast.success('🏆 Winner declared!');
+      toast.success(paused ? '⏸️ Program paused' : '▶️ Program resumed');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ SET GAME PAUSED - oprire de urgenta pentru un singur joc; reluarea prelungeste termenele
+  const setGamePaused = async (gameId: number, paused: boolean) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .setGamePaused(paused)
+        .accounts({
+          game: getGamePDA(program.programId, gameId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          roles: await getRolesAccount(program.provider.connection, program.programId),
+          admin: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success(paused ? `⏸️ Game #${gameId} paused` : `▶️ Game #${gameId} resumed`);
       await fetchGames(program);
       return tx;
     } finally {
@@ -1448,170 +3742,340 @@ export function useSolanaGame() {
     }
   };
 
-  // ✅ CLAIM PHASE 3 PRIZE - cu confirmare
-  const claimPhase3Prize = async (gameId: number) => {
-    console.log('🏆 [claimPhase3Prize] Starting...');
+  // ✅ SET ADMIN COUNCIL - adminul seteaza membrii si pragul M-of-N
+  const setAdminCouncil = async (members: string[], threshold: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      // Open proposals get approvals of removed members dropped
+      const now = Date.now() / 1000;
+      const proposals = await (program.account as any).adminProposal.all();
+      const openProposals = proposals.filter((p: any) => p.account.expiresAt.toNumber() > now);
+
+      const tx = await program.methods
+        .setAdminCouncil(members.map((member) => new PublicKey(member)), threshold)
+        .accounts({
+          adminCouncil: getAdminCouncilPDA(program.programId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          admin: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(openProposals.map((p: any) => ({ pubkey: p.publicKey, isWritable: true, isSigner: false })))
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('🏛️ Council updated!');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ FETCH ADMIN PROPOSALS - propunerile consiliului inca neexecutate
+  const fetchAdminProposals = useCallback(async (): Promise<AdminProposal[]> => {
+    if (!program) return [];
+    const proposals = await (program.account as any).adminProposal.all();
+    return proposals.map((p: any) => ({
+      publicKey: p.publicKey,
+      proposalId: p.account.proposalId.toNumber(),
+      gameId: p.account.gameId.toNumber(),
+      action: (Object.keys(p.account.action)[0].charAt(0).toUpperCase() + Object.keys(p.account.action)[0].slice(1)) as AdminAction,
+      proposer: p.account.proposer.toBase58(),
+      approvals: p.account.approvals.map((key: PublicKey) => key.toBase58()),
+      expiresAt: new Date(p.account.expiresAt.toNumber() * 1000),
+    }));
+  }, [program]);
 
+  // ✅ CREATE ADMIN PROPOSAL - un membru al consiliului propune o actiune de urgenta
+  const createAdminProposal = async (gameId: number, action: AdminAction) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const [adminCouncilPDA] = getAdminCouncilPDA(program.programId);
+      const council = await (program.account as any).adminCouncil.fetch(adminCouncilPDA);
 
       const tx = await program.methods
-        .claimPhase3Prize()
+        .createAdminProposal(new BN(gameId), { [action.charAt(0).toLowerCase() + action.slice(1)]: {} })
         .accounts({
-          game: gamePDA,
-          winner: wallet.publicKey,
+          adminCouncil: adminCouncilPDA,
+          proposal: getAdminProposalPDA(program.programId, council.proposalCount.toNumber())[0],
+          game: getGamePDA(program.programId, gameId)[0],
+          proposer: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-      console.log('📝 Transaction sent:', tx);
       await confirmTransaction(program.provider.connection, tx);
-      console.log('✅ Prize claimed successfully!');
+      toast.success('🏛️ Proposal created!');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
 
-      toast.success('💰 Prize claimed!');
-      await fetchGames(program);
+  // ✅ APPROVE ADMIN PROPOSAL
+  const approveAdminProposal = async (proposalId: number) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
+      const tx = await program.methods
+        .approveAdminProposal()
+        .accounts({
+          adminCouncil: getAdminCouncilPDA(program.programId)[0],
+          proposal: getAdminProposalPDA(program.programId, proposalId)[0],
+          approver: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
+
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('🏛️ Proposal approved!');
       return tx;
-    } catch (error: any) {
-      console.error('❌ Error claiming prize:', error);
-      throw error;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ START PHASE 3 GAME - cu confirmare
-  const startPhase3Game = async (gameId: number) => {
+  // ✅ CANCEL ADMIN PROPOSAL - doar cel care a propus
+  const cancelAdminProposal = async (proposalId: number) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const tx = await program.methods
+        .cancelAdminProposal()
+        .accounts({
+          proposal: getAdminProposalPDA(program.programId, proposalId)[0],
+          proposer: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-      const readyStates = await getPhase3ReadyStates(gameId);
-      const remainingAccounts = readyStates.map(state => ({
-        pubkey: getPhase3ReadyStatePDA(
-          program.programId,
-          gameId,
-          new PublicKey(state.player)
-        )[0],
-        isSigner: false,
-        isWritable: false,
-      }));
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('🏛️ Proposal cancelled');
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ QUEUE ADMIN ACTION - adminul anunta public o interventie, executabila dupa perioada de notificare
+  const queueAdminAction = async (gameId: number, action: AdminAction) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
 
+    setLoading(true);
+    try {
       const tx = await program.methods
-        .startPhase3Game()
+        .queueAdminAction(new BN(gameId), { [action.charAt(0).toLowerCase() + action.slice(1)]: {} })
         .accounts({
-          game: gamePDA,
-          caller: wallet.publicKey,
+          queuedAction: getQueuedAdminActionPDA(program.programId, gameId, action)[0],
+          game: getGamePDA(program.programId, gameId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          roles: await getRolesAccount(program.provider.connection, program.programId),
+          adminCouncil: getAdminCouncilPDA(program.programId)[0],
+          admin: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
         })
-        .remainingAccounts(remainingAccounts)
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      toast.success('🎮 THE PURGE BEGINS!');
-      await fetchGames(program);
+      toast.success('⏳ Admin action queued!');
       return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  const getPhase3ReadyStates = useCallback(async (gameId: number): Promise<Phase3ReadyState[]> => {
-    if (!program) return [];
+  // ✅ CANCEL ADMIN ACTION - adminul retrage o interventie din coada
+  const cancelAdminAction = async (gameId: number, action: AdminAction) => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
 
+    setLoading(true);
     try {
-      const allReadyStates = await program.account.phase3ReadyState.all([
-        {
-          memcmp: {
-            offset: 8,
-            bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
-          }
-        }
-      ]);
+      const tx = await program.methods
+        .cancelAdminAction()
+        .accounts({
+          queuedAction: getQueuedAdminActionPDA(program.programId, gameId, action)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          roles: await getRolesAccount(program.provider.connection, program.programId),
+          admin: wallet.publicKey,
+        })
+        .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
-      return allReadyStates.map((state: any) => ({
-        gameId: state.account.gameId.toNumber(),
-        player: state.account.player.toBase58(),
-        ready: state.account.ready,
-        virtualBalance: state.account.virtualBalance
-          ? state.account.virtualBalance.toNumber()
-          : 0,
-        markedReadyAt: new Date(state.account.markedReadyAt.toNumber() * 1000),
-      }));
-    } catch (error) {
-      return [];
+      await confirmTransaction(program.provider.connection, tx);
+      toast.success('⏳ Admin action cancelled');
+      return tx;
+    } finally {
+      setLoading(false);
     }
-  }, [program]);
+  };
 
-  const getPhase3ReadyStatePDA = (
-    programId: PublicKey,
-    gameId: number,
-    player: PublicKey
-  ): [PublicKey, number] => {
-    return PublicKey.findProgramAddressSync(
-      [
-        Buffer.from('phase3_ready'),
-        new BN(gameId).toArrayLike(Buffer, 'le', 8),
-        player.toBuffer(),
-      ],
-      programId
+  // ✅ FETCH PENDING ADMIN ACTIONS - interventiile anuntate pentru un joc (coada + propuneri consiliu)
+  const fetchPendingAdminActions = useCallback(async (gameId?: number): Promise<PendingAdminAction[]> => {
+    if (!program) return [];
+    const filters = gameId === undefined ? [] : [{
+      memcmp: {
+        offset: ACCOUNT_HEADER,
+        bytes: bs58.encode(new BN(gameId).toArrayLike(Buffer, "le", 8)),
+      },
+    }];
+    const toAction = (action: any) =>
+      (Object.keys(action)[0].charAt(0).toUpperCase() + Object.keys(action)[0].slice(1)) as AdminAction;
+
+    const [queued, allProposals] = await Promise.all([
+      (program.account as any).queuedAdminAction.all(filters),
+      (program.account as any).adminProposal.all(),
+    ]);
+    const proposals = allProposals.filter((p: any) => gameId === undefined || p.account.gameId.toNumber() === gameId);
+
+    // Proposals wait out the notice period snapshotted in their game
+    const proposalGames = await (program.account as any).game.fetchMultiple(
+      proposals.map((p: any) => getGamePDA(program.programId, p.account.gameId.toNumber())[0])
     );
-  };
 
-  // ✅ CLAIM PLATFORM FEE - cu confirmare
-  const claimPlatformFee = async (gameId: number) => {
+    return [
+      ...queued.map((q: any) => ({
+        gameId: q.account.gameId.toNumber(),
+        action: toAction(q.account.action),
+        source: 'queue' as const,
+        executableAt: new Date(q.account.executableAt.toNumber() * 1000),
+        expiresAt: new Date((q.account.executableAt.toNumber() + ADMIN_ACTION_GRACE) * 1000),
+      })),
+      ...proposals
+        .map((p: any, i: number) => [p, proposalGames[i]])
+        // A proposal for a closed game can never run
+        .filter(([, game]: any) => game)
+        .map(([p, game]: any) => ({
+          gameId: p.account.gameId.toNumber(),
+          action: toAction(p.account.action),
+          source: 'council' as const,
+          executableAt: new Date((p.account.createdAt.toNumber() + game.config.adminActionDelay.toNumber()) * 1000),
+          expiresAt: new Date(p.account.expiresAt.toNumber() * 1000),
+        })),
+    ].filter((pending) => pending.expiresAt.getTime() > Date.now());
+  }, [program]);
+
+  // ✅ EXECUTE ADMIN PROPOSAL - oricine poate executa o propunere aprobata
+  const executeAdminProposal = async (proposal: AdminProposal) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
+      const connection = program.provider.connection;
+      const [gamePDA] = getGamePDA(program.programId, proposal.gameId);
+      const base = {
+        adminCouncil: getAdminCouncilPDA(program.programId)[0],
+        proposal: proposal.publicKey,
+        proposer: new PublicKey(proposal.proposer),
+        game: gamePDA,
+        gameRegistry: getGameRegistryPDA(program.programId)[0],
+        executor: wallet.publicKey,
+      };
 
+      let tx: string;
+      if (proposal.action === 'ClosePurgeNoReady') {
+        const game = games.find((g) => g.gameId === proposal.gameId);
+        if (!game) throw new Error(`Game ${proposal.gameId} not loaded`);
+
+        const mint = game.mint ? new PublicKey(game.mint) : null;
+        const tokenProgram = mint ? await getMintTokenProgram(connection, mint) : null;
+
+        tx = await program.methods
+          .executeClosePurgeNoReady()
+          .accounts({
+            ...base,
+            vault: getVaultPDA(program.programId, proposal.gameId)[0],
+            mint,
+            tokenVault: mint && tokenProgram ? getAssociatedTokenAddress(mint, gamePDA, tokenProgram) : null,
+            tokenProgram,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(await getPurgeRemainingAccounts(connection, program.programId, game))
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+      } else {
+        const method = proposal.action === 'StartGame' ? 'executeStartGame' : 'executeAdvancePhase';
+        tx = await (program.methods as any)[method]()
+          .accounts(base)
+          .rpc({ skipPreflight: false, commitment: 'confirmed' });
+      }
+
+      await confirmTransaction(connection, tx);
+      toast.success('🏛️ Proposal executed!');
+      await fetchGames(program);
+      return tx;
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // ✅ GLOBAL CONFIG - parametrii de tuning folositi de jocurile noi
+  const fetchGlobalConfig = useCallback(async () => {
+    if (!program) return null;
+    const [globalConfigPDA] = getGlobalConfigPDA(program.programId);
+    const config = await (program.account as any).globalConfig.fetchNullable(globalConfigPDA);
+    return config ? config.params : null;
+  }, [program]);
+
+  // ✅ INITIALIZE GLOBAL CONFIG - admin creeaza config-ul cu valorile implicite
+  const initializeGlobalConfig = async () => {
+    if (!program || !wallet.publicKey) {
+      throw new Error('Wallet not connected');
+    }
+
+    setLoading(true);
+    try {
       const tx = await program.methods
-        .claimPlatformFee()
+        .initializeGlobalConfig()
         .accounts({
-          game: gamePDA,
+          globalConfig: getGlobalConfigPDA(program.programId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
           admin: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      toast.success('💰 Fee claimed!');
-      await fetchGames(program);
+      toast.success('⚙️ Global config initialized!');
       return tx;
     } finally {
       setLoading(false);
     }
   };
 
-  // ✅ FORCE REFUND EXPIRED GAME - cu confirmare
-  const forceRefundExpiredGame = async (gameId: number) => {
+  // ✅ UPDATE GLOBAL CONFIG - admin modifica parametrii (validati on-chain)
+  const updateGlobalConfig = async (params: any) => {
     if (!program || !wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     setLoading(true);
     try {
-      const [gamePDA] = getGamePDA(program.programId, gameId);
-
       const tx = await program.methods
-        .forceRefundExpiredGame()
+        .updateGlobalConfig(params)
         .accounts({
-          game: gamePDA,
-          player: wallet.publicKey,
+          globalConfig: getGlobalConfigPDA(program.programId)[0],
+          gameRegistry: getGameRegistryPDA(program.programId)[0],
+          admin: wallet.publicKey,
         })
         .rpc({ skipPreflight: false, commitment: 'confirmed' });
 
       await confirmTransaction(program.provider.connection, tx);
-      toast.success('💰 Refund claimed!');
-      await fetchGames(program);
+      toast.success('⚙️ Global config updated!');
       return tx;
     } finally {
       setLoading(false);
@@ -1637,6 +4101,21 @@ export function useSolanaGame() {
     loading,
     createGame,
     enterGame,
+    leaveGame,
+    joinWaitlist,
+    promoteFromWaitlist,
+    refundWaitlist,
+    getMyWaitlistEntry,
+    fetchSponsors,
+    sponsorGame,
+    refundSponsor,
+    fetchSquads,
+    createSquad,
+    joinSquad,
+    leaveSquad,
+    shareSquadBalance,
+    submitPhase3SquadWinner,
+    claimSquadPrize,
     startGame,
     startGameWithBatchInit,
     batchInitializeGameStates,
@@ -1666,9 +4145,43 @@ export function useSolanaGame() {
     startPhase3Game,
     getPhase3ReadyStates,
     claimPlatformFee,
+    claimCreatorFee,
     submitPhase3Winner,
     claimPhase3Prize,
     forceRefundExpiredGame,
+    crankRefunds,
+    closeGame,
+    auditGame,
+    migrateGame,
+    migrateLegacyAccounts,
+    fetchGlobalConfig,
+    proposeAdmin,
+    acceptAdmin,
+    setProgramPaused,
+    setGamePaused,
+    fetchRoles,
+    setRoles,
+    fetchTreasury,
+    withdrawTreasury,
+    fetchTemplates,
+    createTemplate,
+    setTemplateActive,
+    fundTemplate,
+    closeTemplate,
+    spawnFromTemplate,
+    setAdminCouncil,
+    fetchAdminProposals,
+    createAdminProposal,
+    approveAdminProposal,
+    cancelAdminProposal,
+    executeAdminProposal,
+    queueAdminAction,
+    cancelAdminAction,
+    fetchPendingAdminActions,
+    initializeGlobalConfig,
+    updateGlobalConfig,
+    closeMyGameAccounts,
+    sweepGameAccounts,
     getPlayerBalance,
   };
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 import { useState, useEffect } from 'react';
 import { Connection, PublicKey } from '@solana/web3.js';
-import { Program, AnchorProvider } from '@coral-xyz/anchor';
+import { Program, AnchorProvider, BorshAccountsCoder } from '@coral-xyz/anchor';
 import { useWallet } from '@solana/wallet-adapter-react';
 import { useNavigate } from 'react-router-dom';
 import Navbar from '@/components/Navbar';
@@ -9,8 +9,18 @@ import ParticleBackground from '@/components/ParticleBackground';
 import { Card } from '@/components/ui/card';
 import { Button } from '@/components/ui/button';
 import solanaIdl from '../lib/solana_survivor.json';
-import { useSolanaGame } from '@/hooks/useSolanaGame';
-import type { Game } from '@/hooks/useSolanaGame';
+import {
+    useSolanaGame,
+    getAssociatedTokenAddress,
+    getMintTokenProgram,
+    getAdminCouncilPDA,
+    getQueuedAdminActionPDA,
+    getRolesAccount,
+    ROLES,
+    getPurgeRemainingAccounts,
+    getVaultPDA,
+} from '@/hooks/useSolanaGame';
+import type { AdminAction, AdminProposal, Game, GameTemplateInfo, PendingAdminAction, Role, RoleMember, TreasuryInfo } from '@/hooks/useSolanaGame';
 import { executeTransaction } from '@/utils/transactionHelper';
 import {
     Shield,
@@ -60,6 +70,33 @@ export default function AdminDashboard() {
     const solanaGame = useSolanaGame();
     const [adminPubkey, setAdminPubkey] = useState<string | null>(null);
     const [isAdmin, setIsAdmin] = useState(false);
+    const [pendingAdmin, setPendingAdmin] = useState<string | null>(null);
+    const [newAdminInput, setNewAdminInput] = useState('');
+    const [programPaused, setProgramPaused] = useState(false);
+    const [isCouncilMember, setIsCouncilMember] = useState(false);
+    const [councilThreshold, setCouncilThreshold] = useState(0);
+    const [proposals, setProposals] = useState<AdminProposal[]>([]);
+    const [proposalGameId, setProposalGameId] = useState('');
+    const [proposalAction, setProposalAction] = useState<AdminProposal['action']>('AdvancePhase');
+    const [roleMembers, setRoleMembers] = useState<RoleMember[]>([]);
+    const [roleKeyInput, setRoleKeyInput] = useState('');
+    const [roleSelection, setRoleSelection] = useState<Role[]>([]);
+    const [treasury, setTreasury] = useState<TreasuryInfo | null>(null);
+    const [withdrawRecipient, setWithdrawRecipient] = useState('');
+    const [withdrawAmount, setWithdrawAmount] = useState('');
+    const [withdrawMint, setWithdrawMint] = useState('');
+    const [queuedActions, setQueuedActions] = useState<PendingAdminAction[]>([]);
+    const [templates, setTemplates] = useState<GameTemplateInfo[]>([]);
+    const [templateForm, setTemplateForm] = useState({
+        gameName: '',
+        entryFee: '0.1',
+        maxPlayers: '10',
+        gameDurationHours: '2',
+        cadenceHours: '4',
+        leadTimeMinutes: '60',
+        firstStartTime: '',
+        budget: '0.5',
+    });
     const [actionLoading, setActionLoading] = useState<string | null>(null);
 
     useEffect(() => {
@@ -70,6 +107,69 @@ export default function AdminDashboard() {
         checkAdminStatus();
     }, [wallet.publicKey]);
 
+    useEffect(() => {
+        loadProposals();
+    }, [solanaGame.program]);
+
+    const loadProposals = async () => {
+        try {
+            setProposals(await solanaGame.fetchAdminProposals());
+            setRoleMembers(await solanaGame.fetchRoles());
+            setTreasury(await solanaGame.fetchTreasury());
+            setTemplates(await solanaGame.fetchTemplates());
+            const pending = await solanaGame.fetchPendingAdminActions();
+            setQueuedActions(pending.filter(p => p.source === 'queue'));
+        } catch (error) {
+            console.error('Error loading proposals:', error);
+        }
+    };
+
+    const myRoles = roleMembers.find(m => m.key === wallet.publicKey?.toString())?.roles ?? [];
+    const isRoleAdmin = isAdmin || myRoles.includes('Admin');
+    const isTreasurer = isAdmin || myRoles.includes('Treasurer');
+    const isOperator = isAdmin || myRoles.includes('Operator');
+
+    const handleSetRoles = async () => {
+        try {
+            await solanaGame.setRoles(roleKeyInput.trim(), roleSelection);
+            setRoleKeyInput('');
+            setRoleSelection([]);
+            setRoleMembers(await solanaGame.fetchRoles());
+        } catch (error: any) {
+            console.error('Error setting roles:', error);
+            toast.error('Failed to set roles: ' + error.message);
+        }
+    };
+
+    const findQueued = (gameId: number, action: AdminAction) =>
+        queuedActions.find(q => q.gameId === gameId && q.action === action);
+
+    // Admin game controls go through the timelock - queue first, run once the notice is over
+    const queueOrRun = async (gameId: number, action: AdminAction, run: () => Promise<void>) => {
+        // Single-key controls are disabled once a council is set
+        if (councilThreshold > 0) {
+            toast.error('A council is configured - propose this action in Council Proposals instead');
+            return;
+        }
+        if (findQueued(gameId, action)) {
+            await run();
+        } else {
+            await runProposalAction(() => solanaGame.queueAdminAction(gameId, action));
+        }
+    };
+
+    const actionLabel = (gameId: number, action: AdminAction, label: string) => {
+        const queued = findQueued(gameId, action);
+        if (!queued) return `Queue ${label}`;
+        if (queued.executableAt.getTime() > Date.now()) return `${label} after ${queued.executableAt.toLocaleTimeString()}`;
+        return label;
+    };
+
+    const isInNotice = (gameId: number, action: AdminAction) => {
+        const queued = findQueued(gameId, action);
+        return !!queued && queued.executableAt.getTime() > Date.now();
+    };
+
     const checkAdminStatus = async () => {
         if (!wallet.publicKey) return;
 
@@ -90,21 +190,128 @@ export default function AdminDashboard() {
                 return;
             }
 
-            // Manually decode admin pubkey (skip 8 byte discriminator + 8 bytes gameCount + 8 bytes totalGamesCreated)
-            const adminPubkeyBytes = accountInfo.data.slice(24, 56);
-            const adminPubkey = new PublicKey(adminPubkeyBytes);
+            const registry = new BorshAccountsCoder(solanaIdl as any).decode('GameRegistry', accountInfo.data);
+            const adminPubkey: PublicKey = registry.admin;
+            const expiresAt = registry.pendingAdminExpiresAt?.toNumber();
+            const proposalLive = registry.pendingAdmin && (!expiresAt || expiresAt * 1000 > Date.now());
+            setPendingAdmin(proposalLive ? registry.pendingAdmin.toString() : null);
+            setProgramPaused(registry.paused);
 
             console.log('Admin wallet:', adminPubkey.toString());
             console.log('Your wallet:', wallet.publicKey.toString());
 
             setAdminPubkey(adminPubkey.toString());
             setIsAdmin(adminPubkey.toString() === wallet.publicKey.toString());
+
+            const councilInfo = await connection.getAccountInfo(getAdminCouncilPDA(PROGRAM_ID)[0]);
+            if (councilInfo) {
+                const council = new BorshAccountsCoder(solanaIdl as any).decode('AdminCouncil', councilInfo.data);
+                setCouncilThreshold(council.threshold);
+                setIsCouncilMember(council.members.some((member: PublicKey) => member.equals(wallet.publicKey!)));
+            }
         } catch (error) {
             console.error('Error checking admin status:', error);
             setIsAdmin(false);
         }
     };
 
+    const handleProposeAdmin = async () => {
+        try {
+            await solanaGame.proposeAdmin(newAdminInput.trim());
+            setNewAdminInput('');
+            await checkAdminStatus();
+        } catch (error: any) {
+            console.error('Error proposing admin:', error);
+            toast.error('Failed to propose admin: ' + error.message);
+        }
+    };
+
+    const handleAcceptAdmin = async () => {
+        try {
+            await solanaGame.acceptAdmin();
+            await checkAdminStatus();
+        } catch (error: any) {
+            console.error('Error accepting admin:', error);
+            toast.error('Failed to accept admin: ' + error.message);
+        }
+    };
+
+    const handleCollectFee = async (gameId: number) => {
+        try {
+            await solanaGame.claimPlatformFee(gameId);
+            setTreasury(await solanaGame.fetchTreasury());
+        } catch (error: any) {
+            console.error('Error collecting fee:', error);
+            toast.error('Failed to collect fee: ' + error.message);
+        }
+    };
+
+    const handleWithdrawTreasury = async () => {
+        try {
+            await solanaGame.withdrawTreasury(withdrawRecipient.trim(), Number(withdrawAmount), withdrawMint.trim() || undefined);
+            setWithdrawAmount('');
+            setTreasury(await solanaGame.fetchTreasury());
+        } catch (error: any) {
+            console.error('Error withdrawing from treasury:', error);
+            toast.error('Failed to withdraw: ' + error.message);
+        }
+    };
+
+    const handleCreateTemplate = async () => {
+        try {
+            await solanaGame.createTemplate({
+                gameName: templateForm.gameName.trim(),
+                entryFee: Number(templateForm.entryFee),
+                maxPlayers: Number(templateForm.maxPlayers),
+                gameDurationHours: Number(templateForm.gameDurationHours),
+                cadenceHours: Number(templateForm.cadenceHours),
+                leadTimeMinutes: Number(templateForm.leadTimeMinutes),
+                firstStartTime: new Date(templateForm.firstStartTime),
+                budget: Number(templateForm.budget),
+            });
+            setTemplateForm({ ...templateForm, gameName: '', firstStartTime: '' });
+            setTemplates(await solanaGame.fetchTemplates());
+        } catch (error: any) {
+            console.error('Error creating template:', error);
+            toast.error('Failed to create template: ' + error.message);
+        }
+    };
+
+    const handleTemplateAction = async (action: () => Promise<unknown>, label: string) => {
+        try {
+            await action();
+            setTemplates(await solanaGame.fetchTemplates());
+        } catch (error: any) {
+            console.error(`Error trying to ${label}:`, error);
+            toast.error(`Failed to ${label}: ` + error.message);
+        }
+    };
+
+    // Emergency pause skips the timelock - it only halts play, never moves funds
+    const handleTogglePause = async (gameId?: number, paused?: boolean) => {
+        try {
+            if (gameId === undefined) {
+                await solanaGame.setProgramPaused(!programPaused);
+                await checkAdminStatus();
+            } else {
+                await solanaGame.setGamePaused(gameId, !paused);
+            }
+        } catch (error: any) {
+            console.error('Error toggling pause:', error);
+            toast.error('Failed to toggle pause: ' + error.message);
+        }
+    };
+
+    const runProposalAction = async (action: () => Promise<unknown>) => {
+        try {
+            await action();
+            await loadProposals();
+        } catch (error: any) {
+            console.error('Council action failed:', error);
+            toast.error('Council action failed: ' + error.message);
+        }
+    };
+
     const adminStartGame = async (gamePubkey: PublicKey, gameId: number) => {
         if (!solanaGame.program || !wallet.publicKey) return;
 
@@ -120,6 +327,9 @@ export default function AdminDashboard() {
                     .accounts({
                         game: gamePubkey,
                         gameRegistry: gameRegistryPDA,
+                        queuedAction: getQueuedAdminActionPDA(PROGRAM_ID, gameId, 'StartGame')[0],
+                        roles: await getRolesAccount(solanaGame.program!.provider.connection, PROGRAM_ID),
+                        adminCouncil: getAdminCouncilPDA(PROGRAM_ID)[0],
                         admin: wallet.publicKey!,
                     })
                     .rpc();
@@ -128,6 +338,7 @@ export default function AdminDashboard() {
             onSuccess: async () => {
                 toast.success('Game started successfully!');
                 await solanaGame.refreshGames();
+                await loadProposals();
             },
             onError: (error) => {
                 console.error('Error starting game:', error);
@@ -154,6 +365,9 @@ export default function AdminDashboard() {
                     .accounts({
                         game: gamePubkey,
                         gameRegistry: gameRegistryPDA,
+                        queuedAction: getQueuedAdminActionPDA(PROGRAM_ID, gameId, 'AdvancePhase')[0],
+                        roles: await getRolesAccount(solanaGame.program!.provider.connection, PROGRAM_ID),
+                        adminCouncil: getAdminCouncilPDA(PROGRAM_ID)[0],
                         admin: wallet.publicKey!,
                     })
                     .rpc();
@@ -162,6 +376,7 @@ export default function AdminDashboard() {
             onSuccess: async () => {
                 toast.success('Phase advanced successfully!');
                 await solanaGame.refreshGames();
+                await loadProposals();
             },
             onError: (error) => {
                 console.error('Error advancing phase:', error);
@@ -183,19 +398,33 @@ export default function AdminDashboard() {
                     PROGRAM_ID
                 );
 
+                const mint = game.mint ? new PublicKey(game.mint) : null;
+                const connection = solanaGame.program!.provider.connection;
+                const tokenProgram = mint ? await getMintTokenProgram(connection, mint) : null;
+                const remainingAccounts = await getPurgeRemainingAccounts(connection, PROGRAM_ID, game);
+
                 return await solanaGame.program!.methods
                     .adminClosePurgeNoReady()
                     .accounts({
                         game: gamePubkey,
                         gameRegistry: gameRegistryPDA,
+                        queuedAction: getQueuedAdminActionPDA(PROGRAM_ID, game.gameId, 'ClosePurgeNoReady')[0],
+                        roles: await getRolesAccount(connection, PROGRAM_ID),
+                        adminCouncil: getAdminCouncilPDA(PROGRAM_ID)[0],
                         admin: wallet.publicKey!,
+                        vault: getVaultPDA(PROGRAM_ID, game.gameId)[0],
+                        mint,
+                        tokenVault: mint && tokenProgram ? getAssociatedTokenAddress(mint, gamePubkey, tokenProgram) : null,
+                        tokenProgram,
                     })
+                    .remainingAccounts(remainingAccounts)
                     .rpc();
             },
             setLoading: (loading) => setActionLoading(loading ? `close-${game.gameId}` : null),
             onSuccess: async () => {
                 toast.success('Game closed and funds redistributed!');
                 await solanaGame.refreshGames();
+                await loadProposals();
             },
             onError: (error) => {
                 console.error('Error closing game:', error);
@@ -264,7 +493,7 @@ export default function AdminDashboard() {
         );
     }
 
-    if (!isAdmin) {
+    if (!isAdmin && !isCouncilMember && myRoles.length === 0) {
         return (
             <div className="min-h-screen bg-background">
                 <ParticleBackground />
@@ -275,6 +504,17 @@ export default function AdminDashboard() {
                             <Shield className="w-20 h-20 text-sol-orange mx-auto mb-6 animate-pulse" />
                             <h1 className="text-3xl font-bold mb-4 gradient-text">Access Denied</h1>
                             <p className="text-muted-foreground mb-6">You do not have admin privileges.</p>
+                            {pendingAdmin === wallet.publicKey?.toString() && (
+                                <Button
+                                    onClick={handleAcceptAdmin}
+                                    variant="sol"
+                                    className="rounded-full mb-4"
+                                    disabled={solanaGame.loading}
+                                >
+                                    <Shield className="w-4 h-4 mr-2" />
+                                    Accept Admin Role
+                                </Button>
+                            )}
                             <Button
                                 onClick={() => navigate('/')}
                                 variant="sol"
@@ -331,6 +571,368 @@ export default function AdminDashboard() {
                         </div>
                     </div>
 
+                    {/* Admin Transfer */}
+                    <Card className="p-6 mb-8">
+                        <div className="flex flex-col md:flex-row md:items-center gap-3">
+                            <span className="text-sm text-muted-foreground">Transfer admin to</span>
+                            <input
+                                value={newAdminInput}
+                                onChange={e => setNewAdminInput(e.target.value)}
+                                placeholder="New admin wallet address"
+                                className="flex-1 px-3 py-2 rounded bg-background border border-border"
+                            />
+                            <Button
+                                onClick={handleProposeAdmin}
+                                variant="sol-outline"
+                                size="sm"
+                                disabled={solanaGame.loading || !newAdminInput.trim()}
+                                className="rounded-full"
+                            >
+                                Propose
+                            </Button>
+                        </div>
+                        {pendingAdmin && (
+                            <p className="text-xs text-muted-foreground mt-2">
+                                Pending admin: {pendingAdmin} (must accept from that wallet)
+                            </p>
+                        )}
+                    </Card>
+
+                    {/* Roles */}
+                    {isRoleAdmin && (
+                        <Card className="p-6 mb-8">
+                            <h2 className="font-bold mb-3">Roles</h2>
+                            <div className="space-y-1 mb-4">
+                                {roleMembers.length === 0 ? (
+                                    <p className="text-sm text-muted-foreground">No roles granted - only the registry admin is privileged</p>
+                                ) : (
+                                    roleMembers.map(member => (
+                                        <div key={member.key} className="text-sm flex justify-between gap-3">
+                                            <span className="font-mono truncate">{member.key}</span>
+                                            <span className="text-muted-foreground">{member.roles.join(', ')}</span>
+                                        </div>
+                                    ))
+                                )}
+                            </div>
+                            <div className="flex flex-col md:flex-row md:items-center gap-3">
+                                <input
+                                    value={roleKeyInput}
+                                    onChange={e => setRoleKeyInput(e.target.value)}
+                                    placeholder="Wallet address"
+                                    className="flex-1 px-3 py-2 rounded bg-background border border-border"
+                                />
+                                {ROLES.map(role => (
+                                    <label key={role} className="text-sm flex items-center gap-1">
+                                        <input
+                                            type="checkbox"
+                                            checked={roleSelection.includes(role)}
+                                            onChange={e => setRoleSelection(e.target.checked
+                                                ? [...roleSelection, role]
+                                                : roleSelection.filter(r => r !== role))}
+                                        />
+                                        {role}
+                                    </label>
+                                ))}
+                                <Button
+                                    onClick={handleSetRoles}
+                                    variant="sol-outline"
+                                    size="sm"
+                                    disabled={solanaGame.loading || !roleKeyInput.trim()}
+                                    className="rounded-full"
+                                >
+                                    Save Roles
+                                </Button>
+                            </div>
+                        </Card>
+                    )}
+
+                    {/* Treasury */}
+                    {isTreasurer && (
+                        <Card className="p-6 mb-8">
+                            <h2 className="font-bold mb-3">Treasury</h2>
+                            {treasury ? (
+                                <div className="space-y-1 mb-4 text-sm">
+                                    <div>Balance: {treasury.balance.toFixed(4)} SOL</div>
+                                    <div className="text-muted-foreground">
+                                        Collected {treasury.totalSolCollected.toFixed(4)} SOL, withdrawn {treasury.totalSolWithdrawn.toFixed(4)} SOL
+                                    </div>
+                                    {treasury.feeRecords.map(record => (
+                                        <div key={record.gameId} className="flex justify-between gap-3">
+                                            <span>Game #{record.gameId}{record.mint ? ` (${record.mint.slice(0, 4)}…)` : ''}</span>
+                                            <span className="text-muted-foreground">
+                                                {record.totalCollected} base units, last {record.lastCollectedAt.toLocaleDateString()}
+                                            </span>
+                                        </div>
+                                    ))}
+                                </div>
+                            ) : (
+                                <p className="text-sm text-muted-foreground mb-4">No fees collected yet</p>
+                            )}
+                            <div className="flex flex-col md:flex-row md:items-center gap-3">
+                                <input
+                                    value={withdrawRecipient}
+                                    onChange={e => setWithdrawRecipient(e.target.value)}
+                                    placeholder="Recipient wallet"
+                                    className="flex-1 px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    type="number"
+                                    value={withdrawAmount}
+                                    onChange={e => setWithdrawAmount(e.target.value)}
+                                    placeholder="Amount"
+                                    className="w-32 px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    value={withdrawMint}
+                                    onChange={e => setWithdrawMint(e.target.value)}
+                                    placeholder="Mint (empty for SOL)"
+                                    className="flex-1 px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <Button
+                                    onClick={handleWithdrawTreasury}
+                                    variant="sol-outline"
+                                    size="sm"
+                                    disabled={solanaGame.loading || !treasury || !withdrawRecipient.trim() || !(Number(withdrawAmount) > 0)}
+                                    className="rounded-full"
+                                >
+                                    Withdraw
+                                </Button>
+                            </div>
+                        </Card>
+                    )}
+
+                    {/* Game Templates */}
+                    {isOperator && (
+                        <Card className="p-6 mb-8">
+                            <h2 className="font-bold mb-3">Recurring Games</h2>
+                            {templates.length === 0 ? (
+                                <p className="text-sm text-muted-foreground mb-4">No templates yet</p>
+                            ) : (
+                                <div className="space-y-2 mb-4 text-sm">
+                                    {templates.map(template => {
+                                        const isMine = template.operator === wallet.publicKey?.toString();
+                                        const due = template.active &&
+                                            Date.now() >= template.nextStartTime.getTime() - template.leadTimeMinutes * 60000;
+                                        return (
+                                            <div key={template.publicKey} className="flex flex-col md:flex-row md:items-center md:justify-between gap-2">
+                                                <div>
+                                                    <span className="font-semibold">{template.name}</span>
+                                                    <span className="text-muted-foreground">
+                                                        {' '}every {template.cadenceHours}h, next {template.nextStartTime.toLocaleString()},
+                                                        {' '}{template.gamesSpawned} spawned, budget {template.budget.toFixed(4)} SOL
+                                                        {!template.active && ' (paused)'}
+                                                    </span>
+                                                </div>
+                                                <div className="flex gap-2">
+                                                    <Button
+                                                        onClick={() => handleTemplateAction(() => solanaGame.spawnFromTemplate(template.publicKey), 'spawn game')}
+                                                        variant="sol-outline"
+                                                        size="sm"
+                                                        disabled={solanaGame.loading || !due}
+                                                        className="rounded-full"
+                                                    >
+                                                        Spawn Next
+                                                    </Button>
+                                                    <Button
+                                                        onClick={() => handleTemplateAction(() => solanaGame.fundTemplate(template.publicKey, 0.05), 'fund template')}
+                                                        variant="outline"
+                                                        size="sm"
+                                                        disabled={solanaGame.loading}
+                                                        className="rounded-full"
+                                                    >
+                                                        +0.05 SOL
+                                                    </Button>
+                                                    {isMine && (
+                                                        <>
+                                                            <Button
+                                                                onClick={() => handleTemplateAction(() => solanaGame.setTemplateActive(template.publicKey, !template.active), 'update template')}
+                                                                variant="outline"
+                                                                size="sm"
+                                                                disabled={solanaGame.loading}
+                                                                className="rounded-full"
+                                                            >
+                                                                {template.active ? 'Pause' : 'Resume'}
+                                                            </Button>
+                                                            <Button
+                                                                onClick={() => handleTemplateAction(() => solanaGame.closeTemplate(template.publicKey), 'close template')}
+                                                                variant="destructive"
+                                                                size="sm"
+                                                                disabled={solanaGame.loading}
+                                                                className="rounded-full"
+                                                            >
+                                                                Close
+                                                            </Button>
+                                                        </>
+                                                    )}
+                                                </div>
+                                            </div>
+                                        );
+                                    })}
+                                </div>
+                            )}
+                            <div className="grid grid-cols-2 md:grid-cols-4 gap-3">
+                                <input
+                                    value={templateForm.gameName}
+                                    onChange={e => setTemplateForm({ ...templateForm, gameName: e.target.value })}
+                                    placeholder="Game name"
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    type="number"
+                                    value={templateForm.entryFee}
+                                    onChange={e => setTemplateForm({ ...templateForm, entryFee: e.target.value })}
+                                    placeholder="Entry fee (SOL)"
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    type="number"
+                                    value={templateForm.maxPlayers}
+                                    onChange={e => setTemplateForm({ ...templateForm, maxPlayers: e.target.value })}
+                                    placeholder="Max players"
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    type="number"
+                                    value={templateForm.gameDurationHours}
+                                    onChange={e => setTemplateForm({ ...templateForm, gameDurationHours: e.target.value })}
+                                    placeholder="Game duration (hours)"
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    type="number"
+                                    value={templateForm.cadenceHours}
+                                    onChange={e => setTemplateForm({ ...templateForm, cadenceHours: e.target.value })}
+                                    placeholder="Every (hours)"
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    type="number"
+                                    value={templateForm.leadTimeMinutes}
+                                    onChange={e => setTemplateForm({ ...templateForm, leadTimeMinutes: e.target.value })}
+                                    placeholder="Open entries (minutes before start)"
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    type="datetime-local"
+                                    value={templateForm.firstStartTime}
+                                    onChange={e => setTemplateForm({ ...templateForm, firstStartTime: e.target.value })}
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <input
+                                    type="number"
+                                    value={templateForm.budget}
+                                    onChange={e => setTemplateForm({ ...templateForm, budget: e.target.value })}
+                                    placeholder="Spawn budget (SOL) - rent and creator bond per game"
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                            </div>
+                            <Button
+                                onClick={handleCreateTemplate}
+                                variant="sol-outline"
+                                size="sm"
+                                disabled={solanaGame.loading || !templateForm.gameName.trim() || !templateForm.firstStartTime}
+                                className="rounded-full mt-3"
+                            >
+                                Create Template
+                            </Button>
+                        </Card>
+                    )}
+
+                    {/* Emergency Pause */}
+                    {isRoleAdmin && (
+                        <Card className={`p-6 mb-8 ${programPaused ? 'border-red-500/50' : ''}`}>
+                            <div className="flex flex-col md:flex-row md:items-center md:justify-between gap-3">
+                                <div>
+                                    <div className="font-bold">
+                                        {programPaused ? '⏸️ Program paused' : 'Program running'}
+                                    </div>
+                                    <p className="text-xs text-muted-foreground">
+                                        Pausing blocks entries, allocations, challenges and settlements. Refunds stay open
+                                        and deadlines are extended by the paused time.
+                                    </p>
+                                </div>
+                                <Button
+                                    onClick={() => handleTogglePause()}
+                                    variant={programPaused ? 'sol' : 'destructive'}
+                                    size="sm"
+                                    disabled={solanaGame.loading}
+                                    className="rounded-full"
+                                >
+                                    {programPaused ? 'Resume Program' : 'Pause Program'}
+                                </Button>
+                            </div>
+                        </Card>
+                    )}
+
+                    {/* Council Proposals */}
+                    {isCouncilMember && (
+                        <Card className="p-6 mb-8">
+                            <h2 className="text-xl font-bold mb-4">Council Proposals ({councilThreshold} approvals needed)</h2>
+                            <div className="flex flex-col md:flex-row gap-3 mb-4">
+                                <input
+                                    type="number"
+                                    value={proposalGameId}
+                                    onChange={e => setProposalGameId(e.target.value)}
+                                    placeholder="Game ID"
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                />
+                                <select
+                                    value={proposalAction}
+                                    onChange={e => setProposalAction(e.target.value as AdminProposal['action'])}
+                                    className="px-3 py-2 rounded bg-background border border-border"
+                                >
+                                    <option value="StartGame">Start game</option>
+                                    <option value="AdvancePhase">Advance phase</option>
+                                    <option value="ClosePurgeNoReady">Close purge (no ready)</option>
+                                </select>
+                                <Button
+                                    onClick={() => runProposalAction(() => solanaGame.createAdminProposal(Number(proposalGameId), proposalAction))}
+                                    variant="sol-outline"
+                                    size="sm"
+                                    disabled={solanaGame.loading || proposalGameId === ''}
+                                    className="rounded-full"
+                                >
+                                    Propose
+                                </Button>
+                            </div>
+                            {proposals.length === 0 ? (
+                                <p className="text-sm text-muted-foreground">No pending proposals</p>
+                            ) : (
+                                <div className="space-y-2">
+                                    {proposals.map(proposal => {
+                                        const approved = proposal.approvals.includes(wallet.publicKey!.toBase58());
+                                        return (
+                                            <div key={proposal.proposalId} className="flex flex-wrap items-center gap-3 text-sm">
+                                                <span>#{proposal.proposalId} {proposal.action} on game {proposal.gameId}</span>
+                                                <span className="text-muted-foreground">
+                                                    {proposal.approvals.length}/{councilThreshold} approvals, expires {proposal.expiresAt.toLocaleString()}
+                                                </span>
+                                                {!approved && (
+                                                    <Button size="sm" variant="outline" className="rounded-full"
+                                                        onClick={() => runProposalAction(() => solanaGame.approveAdminProposal(proposal.proposalId))}>
+                                                        Approve
+                                                    </Button>
+                                                )}
+                                                {proposal.approvals.length >= councilThreshold && (
+                                                    <Button size="sm" variant="sol" className="rounded-full"
+                                                        onClick={() => runProposalAction(() => solanaGame.executeAdminProposal(proposal))}>
+                                                        Execute
+                                                    </Button>
+                                                )}
+                                                {proposal.proposer === wallet.publicKey!.toBase58() && (
+                                                    <Button size="sm" variant="outline" className="rounded-full"
+                                                        onClick={() => runProposalAction(() => solanaGame.cancelAdminProposal(proposal.proposalId))}>
+                                                        Cancel
+                                                    </Button>
+                                                )}
+                                            </div>
+                                        );
+                                    })}
+                                </div>
+                            )}
+                        </Card>
+                    )}
+
                     {/* Stats Overview */}
                     <div className="grid grid-cols-1 md:grid-cols-4 gap-4 mb-8">
                         <Card className="p-6">
@@ -468,9 +1070,9 @@ export default function AdminDashboard() {
                                                                 [Buffer.from('game'), Buffer.from(new Uint8Array(new BigUint64Array([BigInt(game.gameId)]).buffer))],
                                                                 PROGRAM_ID
                                                             );
-                                                            adminStartGame(gamePDA, game.gameId);
+                                                            queueOrRun(game.gameId, 'StartGame', () => adminStartGame(gamePDA, game.gameId));
                                                         }}
-                                                        disabled={actionLoading === `start-${game.gameId}`}
+                                                        disabled={actionLoading === `start-${game.gameId}` || isInNotice(game.gameId, 'StartGame')}
                                                         className="rounded-full"
                                                     >
                                                         {actionLoading === `start-${game.gameId}` ? (
@@ -478,7 +1080,7 @@ export default function AdminDashboard() {
                                                         ) : (
                                                             <Play className="w-4 h-4 mr-2" />
                                                         )}
-                                                        Start Game
+                                                        {actionLabel(game.gameId, 'StartGame', 'Start Game')}
                                                     </Button>
                                                 )}
 
@@ -491,9 +1093,9 @@ export default function AdminDashboard() {
                                                                 [Buffer.from('game'), Buffer.from(new Uint8Array(new BigUint64Array([BigInt(game.gameId)]).buffer))],
                                                                 PROGRAM_ID
                                                             );
-                                                            adminAdvancePhase(gamePDA, game.gameId);
+                                                            queueOrRun(game.gameId, 'AdvancePhase', () => adminAdvancePhase(gamePDA, game.gameId));
                                                         }}
-                                                        disabled={actionLoading === `advance-${game.gameId}`}
+                                                        disabled={actionLoading === `advance-${game.gameId}` || isInNotice(game.gameId, 'AdvancePhase')}
                                                         className="rounded-full"
                                                     >
                                                         {actionLoading === `advance-${game.gameId}` ? (
@@ -501,7 +1103,7 @@ export default function AdminDashboard() {
                                                         ) : (
                                                             <FastForward className="w-4 h-4 mr-2" />
                                                         )}
-                                                        Advance Phase
+                                                        {actionLabel(game.gameId, 'AdvancePhase', 'Advance Phase')}
                                                     </Button>
                                                 )}
 
@@ -514,9 +1116,9 @@ export default function AdminDashboard() {
                                                                 [Buffer.from('game'), Buffer.from(new Uint8Array(new BigUint64Array([BigInt(game.gameId)]).buffer))],
                                                                 PROGRAM_ID
                                                             );
-                                                            adminClosePurge(gamePDA, game);
+                                                            queueOrRun(game.gameId, 'ClosePurgeNoReady', () => adminClosePurge(gamePDA, game));
                                                         }}
-                                                        disabled={actionLoading === `close-${game.gameId}`}
+                                                        disabled={actionLoading === `close-${game.gameId}` || isInNotice(game.gameId, 'ClosePurgeNoReady')}
                                                         className="rounded-full"
                                                     >
                                                         {actionLoading === `close-${game.gameId}` ? (
@@ -524,7 +1126,50 @@ export default function AdminDashboard() {
                                                         ) : (
                                                             <XCircle className="w-4 h-4 mr-2" />
                                                         )}
-                                                        Close & Redistribute
+                                                        {actionLabel(game.gameId, 'ClosePurgeNoReady', 'Close & Redistribute')}
+                                                    </Button>
+                                                )}
+
+                                                {['Cancelled', 'ExpiredWithPenalty'].includes(normalizeStatus(game.status))
+                                                    && game.players.some(p => !game.refundedPlayers.includes(p)
+                                                        && !(normalizeStatus(game.status) === 'ExpiredWithPenalty' && p === game.creator)) && (
+                                                    <Button
+                                                        variant="outline"
+                                                        size="sm"
+                                                        onClick={() => solanaGame.crankRefunds(game.gameId).catch((error: any) => {
+                                                            console.error('Error cranking refunds:', error);
+                                                            toast.error('Failed to push refunds: ' + error.message);
+                                                        })}
+                                                        disabled={solanaGame.loading}
+                                                        className="rounded-full"
+                                                    >
+                                                        <RefreshCw className="w-4 h-4 mr-2" />
+                                                        Push Refunds
+                                                    </Button>
+                                                )}
+
+                                                {isTreasurer && game.platformFeeCollected > 0 && (
+                                                    <Button
+                                                        variant="outline"
+                                                        size="sm"
+                                                        onClick={() => handleCollectFee(game.gameId)}
+                                                        disabled={solanaGame.loading}
+                                                        className="rounded-full"
+                                                    >
+                                                        <DollarSign className="w-4 h-4 mr-2" />
+                                                        Collect Fee
+                                                    </Button>
+                                                )}
+
+                                                {isRoleAdmin && !['Completed', 'Cancelled', 'ExpiredWithPenalty'].includes(normalizeStatus(game.status)) && (
+                                                    <Button
+                                                        variant="outline"
+                                                        size="sm"
+                                                        onClick={() => handleTogglePause(game.gameId, game.paused)}
+                                                        disabled={solanaGame.loading}
+                                                        className="rounded-full"
+                                                    >
+                                                        {game.paused ? 'Resume Game' : 'Pause Game'}
                                                     </Button>
                                                 )}
 
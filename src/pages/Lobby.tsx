@@ -6,8 +6,8 @@ import Navbar from "../components/Navbar";
 import Footer from "../components/Footer";
 import FirstTimeTutorial from "../components/FirstTimeTutorial";
 import CountdownTimer from "../components/CountdownTimer";
-import { useSolanaGame } from "../hooks/useSolanaGame";
-import type { Game } from "../hooks/useSolanaGame";
+import { useSolanaGame, CREATOR_BOND_SOL } from "../hooks/useSolanaGame";
+import type { AccessMode, Game, SponsorInfo, SquadInfo } from "../hooks/useSolanaGame";
 import WalletConnectModal from "../components/WalletConnectModal";
 import { useWallet } from "@solana/wallet-adapter-react";
 import { useToast } from "@/hooks/use-toast";
@@ -24,6 +24,10 @@ interface ButtonProps {
 }
 
 // Helper to normalize status
+// Wallets pasted one per line (or comma separated)
+const parseWalletList = (text: string): string[] =>
+  text.split(/[\s,]+/).map((w) => w.trim()).filter(Boolean);
+
 const normalizeStatus = (status: any): string => {
   if (typeof status === 'string') return status;
   if (typeof status === 'object' && status !== null) {
@@ -113,6 +117,12 @@ export default function Lobby() {
   const { toast } = useToast();
   const [selectedGameId, setSelectedGameId] = useState<number | null>(null);
   const [selectedGame, setSelectedGame] = useState<Game | null>(null);
+  const [myWaitlistEntry, setMyWaitlistEntry] = useState<{ position: number; amount: number; skipped: boolean } | null>(null);
+  const [sponsors, setSponsors] = useState<SponsorInfo[]>([]);
+  const [sponsorForm, setSponsorForm] = useState({ amount: 1, displayName: '', feeEligible: false });
+  const [squads, setSquads] = useState<SquadInfo[]>([]);
+  // Prize split as comma separated percents, one per slot (the leader first)
+  const [squadForm, setSquadForm] = useState({ name: '', shares: '50, 50' });
   const [showCreateModal, setShowCreateModal] = useState(false);
   const [showGameDetailsModal, setShowGameDetailsModal] = useState(false);
   const [isClaiming, setIsClaiming] = useState(false);
@@ -124,7 +134,17 @@ export default function Lobby() {
     maxPlayers: 10,
     startTime: new Date(Date.now() + 30 * 60 * 1000),
     gameDurationHours: 2,
+    mint: '',
+    minPlayers: 3,
+    maxBetPercentage: 100,
+    creatorFeeBps: 0,
+    leaveLockMinutes: 10,
+    squadSize: 0,
+    accessMode: 'Public' as AccessMode,
+    accessList: '',
   });
+  // Invite code / allowlist typed in the details modal for private games
+  const [accessPass, setAccessPass] = useState('');
 
   // Filtering and sorting state
   const [searchQuery, setSearchQuery] = useState('');
@@ -278,6 +298,42 @@ export default function Lobby() {
 
   // Fetch Solana price
   const [solPrice, setSolPrice] = useState<number>(0);
+  const [creatorBondSol, setCreatorBondSol] = useState(CREATOR_BOND_SOL);
+
+  // Bond the program currently charges, shown in the create modal
+  useEffect(() => {
+    if (!showCreateModal) return;
+    solanaGame.fetchGlobalConfig()
+      .then((params) => params && setCreatorBondSol(params.creatorBond.toNumber() / 1e9))
+      .catch((error) => console.error('Failed to fetch global config:', error));
+  }, [showCreateModal]);
+
+  // Waitlist entry for the game shown in the details modal
+  useEffect(() => {
+    setMyWaitlistEntry(null);
+    if (!selectedGame || !wallet.connected) return;
+    solanaGame.getMyWaitlistEntry(selectedGame.gameId)
+      .then(setMyWaitlistEntry)
+      .catch((error) => console.error('Failed to fetch waitlist entry:', error));
+  }, [selectedGame?.gameId, wallet.connected]);
+
+  // Sponsors of the game shown in the details modal
+  useEffect(() => {
+    setSponsors([]);
+    if (!selectedGame) return;
+    solanaGame.fetchSponsors(selectedGame.gameId)
+      .then(setSponsors)
+      .catch((error) => console.error('Failed to fetch sponsors:', error));
+  }, [selectedGame?.gameId, selectedGame?.sponsoredAmount]);
+
+  // Squads of the squad game shown in the details modal
+  useEffect(() => {
+    setSquads([]);
+    if (!selectedGame?.squadSize) return;
+    solanaGame.fetchSquads(selectedGame.gameId)
+      .then(setSquads)
+      .catch((error) => console.error('Failed to fetch squads:', error));
+  }, [selectedGame?.gameId, selectedGame?.squadPlayers, selectedGame?.phase3Winner]);
 
   useEffect(() => {
     const fetchSolPrice = async () => {
@@ -405,7 +461,20 @@ export default function Lobby() {
     }
   };
 
+  // Pass for the selected private game, built from what the player typed
+  const selectedAccessPass = () => {
+    switch (selectedGame?.accessMode) {
+      case 'Allowlist':
+        return { allowlist: parseWalletList(accessPass) };
+      case 'InviteCode':
+        return { inviteCode: accessPass.trim() };
+      default:
+        return undefined;
+    }
+  };
+
   const handleJoinGame = async (game: any) => {
+    setAccessPass('');
     setSelectedGame(game);
     setShowGameDetailsModal(true);
   };
@@ -414,7 +483,7 @@ export default function Lobby() {
   const performGameJoin = async (gameId: number) => {
     try {
       console.log('🚀 Calling solanaGame.enterGame with gameId:', gameId);
-      const result = await solanaGame.enterGame(gameId);
+      const result = await solanaGame.enterGame(gameId, selectedAccessPass());
 
       if (result === 'already_processed') {
         console.log('🎉 Join was already successful!');
@@ -535,6 +604,196 @@ export default function Lobby() {
     }
   };
 
+  const handleClaimCreatorFee = async () => {
+    if (!selectedGame || !wallet.connected) return;
+
+    try {
+      await solanaGame.claimCreatorFee(selectedGame.gameId);
+      toast({
+        title: "Creator Fee Claimed!",
+        description: "Your share of the platform fee was sent to your wallet",
+      });
+      await solanaGame.fetchGames();
+      setShowGameDetailsModal(false);
+      setSelectedGame(null);
+    } catch (error: any) {
+      console.error('❌ Failed to claim creator fee:', error);
+      toast({
+        variant: "destructive",
+        title: "Failed to claim creator fee",
+        description: error.message || "Unknown error",
+      });
+    }
+  };
+
+  // ✅ LEAVE GAME - retragere inainte de start
+  const handleLeaveGame = async () => {
+    if (!selectedGame || !wallet.connected) return;
+
+    try {
+      await solanaGame.leaveGame(selectedGame.gameId);
+      toast({
+        title: "Left the Game",
+        description: `Your ${selectedGame.entryFee.toFixed(2)} SOL entry was refunded`,
+      });
+      await solanaGame.fetchGames();
+      setShowGameDetailsModal(false);
+      setSelectedGame(null);
+    } catch (error: any) {
+      console.error('❌ Failed to leave game:', error);
+      toast({
+        variant: "destructive",
+        title: "Failed to leave game",
+        description: error.message || "Unknown error",
+      });
+    }
+  };
+
+  // ✅ WAITLIST - coada pentru jocurile pline
+  const handleJoinWaitlist = async () => {
+    if (!selectedGame || !wallet.connected) return;
+
+    try {
+      await solanaGame.joinWaitlist(selectedGame.gameId, selectedAccessPass());
+      toast({
+        title: "Joined the Waitlist",
+        description: `Your ${selectedGame.entryFee.toFixed(2)} SOL deposit holds your place - you're in as soon as a seat frees up`,
+      });
+      setMyWaitlistEntry(await solanaGame.getMyWaitlistEntry(selectedGame.gameId));
+    } catch (error: any) {
+      console.error('❌ Failed to join waitlist:', error);
+      toast({
+        variant: "destructive",
+        title: "Failed to join waitlist",
+        description: error.message || "Unknown error",
+      });
+    }
+  };
+
+  const handlePromoteFromWaitlist = async () => {
+    if (!selectedGame || !wallet.connected) return;
+
+    try {
+      await solanaGame.promoteFromWaitlist(selectedGame.gameId);
+      toast({
+        title: "Seat Filled",
+        description: "The next player on the waitlist joined the game",
+      });
+      await solanaGame.fetchGames();
+      setShowGameDetailsModal(false);
+      setSelectedGame(null);
+    } catch (error: any) {
+      console.error('❌ Failed to promote from waitlist:', error);
+      toast({
+        variant: "destructive",
+        title: "Failed to fill seat",
+        description: error.message || "Unknown error",
+      });
+    }
+  };
+
+  const handleRefundWaitlist = async () => {
+    if (!selectedGame || !wallet.connected) return;
+
+    try {
+      await solanaGame.refundWaitlist(selectedGame.gameId);
+      toast({
+        title: "Deposit Refunded",
+        description: "Your waitlist deposit was sent back to your wallet",
+      });
+      setMyWaitlistEntry(null);
+    } catch (error: any) {
+      console.error('❌ Failed to refund waitlist deposit:', error);
+      toast({
+        variant: "destructive",
+        title: "Failed to refund deposit",
+        description: error.message || "Unknown error",
+      });
+    }
+  };
+
+  // ✅ SPONSOR - partenerii adauga fonduri in prize pool
+  const handleSponsorGame = async () => {
+    if (!selectedGame || !wallet.connected) return;
+
+    try {
+      await solanaGame.sponsorGame(
+        selectedGame.gameId,
+        sponsorForm.amount,
+        sponsorForm.displayName.trim(),
+        sponsorForm.feeEligible
+      );
+      toast({
+        title: "Game Sponsored",
+        description: `Added ${sponsorForm.amount} SOL to the prize pool - refunded if the game is cancelled`,
+      });
+      setSponsors(await solanaGame.fetchSponsors(selectedGame.gameId));
+    } catch (error: any) {
+      console.error('❌ Failed to sponsor game:', error);
+      toast({
+        variant: "destructive",
+        title: "Failed to sponsor game",
+        description: error.message || "Unknown error",
+      });
+    }
+  };
+
+  const handleRefundSponsor = async () => {
+    if (!selectedGame || !wallet.connected) return;
+
+    try {
+      await solanaGame.refundSponsor(selectedGame.gameId);
+      toast({
+        title: "Sponsorship Refunded",
+        description: "Your sponsorship was sent back to your wallet",
+      });
+      setSponsors(await solanaGame.fetchSponsors(selectedGame.gameId));
+    } catch (error: any) {
+      console.error('❌ Failed to refund sponsorship:', error);
+      toast({
+        variant: "destructive",
+        title: "Failed to refund sponsorship",
+        description: error.message || "Unknown error",
+      });
+    }
+  };
+
+  // ✅ SQUADS - echipele dintr-un joc pe echipe
+  const runSquadAction = async (action: () => Promise<unknown>, title: string, description: string) => {
+    if (!selectedGame || !wallet.connected) return;
+
+    try {
+      await action();
+      toast({ title, description });
+      setSquads(await solanaGame.fetchSquads(selectedGame.gameId));
+    } catch (error: any) {
+      console.error(`❌ ${title} failed:`, error);
+      toast({
+        variant: "destructive",
+        title: `${title} failed`,
+        description: error.message || "Unknown error",
+      });
+    }
+  };
+
+  const handleCreateSquad = () => {
+    const shares = squadForm.shares.split(',').map((share) => parseFloat(share.trim()) || 0);
+    return runSquadAction(
+      () => solanaGame.createSquad(selectedGame!.gameId, squadForm.name.trim(), shares),
+      "Squad Created",
+      `Prize split ${shares.join(' / ')}% - share the game so your squadmates can join`
+    );
+  };
+
+  const handleJoinSquad = (squad: string) =>
+    runSquadAction(() => solanaGame.joinSquad(selectedGame!.gameId, squad), "Joined Squad", "You'll enter and win together");
+
+  const handleLeaveSquad = (squad: string) =>
+    runSquadAction(() => solanaGame.leaveSquad(selectedGame!.gameId, squad), "Left Squad", "You can join another squad before the start");
+
+  const handleClaimSquadPrize = (squad: string) =>
+    runSquadAction(() => solanaGame.claimSquadPrize(selectedGame!.gameId, squad), "Prize Share Claimed", "Your squad's winnings were sent to your wallet");
+
   // ✅ NOUĂ FUNCȚIE - Force Refund
   const handleForceRefund = async () => {
     if (!selectedGame || !wallet.connected) return;
@@ -702,7 +961,13 @@ export default function Lobby() {
 
   const handleCreateGame = async () => {
     try {
-      const result = await solanaGame.createGame(createGameParams);
+      const { minPlayers, maxBetPercentage, creatorFeeBps, leaveLockMinutes, squadSize, accessMode, accessList, ...params } = createGameParams;
+      const result = await solanaGame.createGame({
+        ...params,
+        mint: params.mint.trim() || undefined,
+        rules: { minPlayers, maxBetPercentage, creatorFeeBps, leaveLockMinutes, squadSize },
+        access: { mode: accessMode, allowlist: parseWalletList(accessList), inviteCode: accessList.trim() },
+      });
 
       if (result === 'already_processed' || result) {
         console.log('✅ Game created successfully!');
@@ -717,6 +982,14 @@ export default function Lobby() {
           maxPlayers: 10,
           startTime: new Date(Date.now() + 30 * 60 * 1000),
           gameDurationHours: 2,
+          mint: '',
+          minPlayers: 3,
+          maxBetPercentage: 100,
+          creatorFeeBps: 0,
+          leaveLockMinutes: 10,
+          squadSize: 0,
+          accessMode: 'Public',
+          accessList: '',
         });
 
         setShowCreateModal(false);
@@ -1303,7 +1576,7 @@ export default function Lobby() {
                   />
                 </div>
                 <div>
-                  <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Entry Fee (SOL)</label>
+                  <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Entry Fee ({createGameParams.mint.trim() ? 'tokens' : 'SOL'})</label>
                   <input
                     type="number"
                     min={0.1}
@@ -1318,12 +1591,27 @@ export default function Lobby() {
                     }}
                   />
                 </div>
+                <div>
+                  <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Token Mint (optional)</label>
+                  <input
+                    type="text"
+                    placeholder="Leave empty for SOL"
+                    value={createGameParams.mint}
+                    onChange={e => setCreateGameParams({ ...createGameParams, mint: e.target.value })}
+                    className="w-full px-3 py-2 rounded"
+                    style={{
+                      background: 'hsla(280, 100%, 35%, 0.1)',
+                      border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                      color: 'white'
+                    }}
+                  />
+                </div>
                 <div>
                   <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Max Players</label>
                   <input
                     type="number"
                     min={2}
-                    max={100}
+                    max={1000}
                     value={createGameParams.maxPlayers}
                     onChange={e => setCreateGameParams({ ...createGameParams, maxPlayers: parseInt(e.target.value) })}
                     className="w-full px-3 py-2 rounded"
@@ -1334,6 +1622,140 @@ export default function Lobby() {
                     }}
                   />
                 </div>
+                <div className="grid grid-cols-2 gap-2">
+                  <div>
+                    <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Min Players</label>
+                    <input
+                      type="number"
+                      min={3}
+                      max={createGameParams.maxPlayers}
+                      value={createGameParams.minPlayers}
+                      onChange={e => setCreateGameParams({ ...createGameParams, minPlayers: parseInt(e.target.value) })}
+                      className="w-full px-3 py-2 rounded"
+                      style={{
+                        background: 'hsla(280, 100%, 35%, 0.1)',
+                        border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                        color: 'white'
+                      }}
+                    />
+                  </div>
+                  <div>
+                    <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Max Bet (% of balance)</label>
+                    <input
+                      type="number"
+                      min={1}
+                      max={100}
+                      value={createGameParams.maxBetPercentage}
+                      onChange={e => setCreateGameParams({ ...createGameParams, maxBetPercentage: parseInt(e.target.value) })}
+                      className="w-full px-3 py-2 rounded"
+                      style={{
+                        background: 'hsla(280, 100%, 35%, 0.1)',
+                        border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                        color: 'white'
+                      }}
+                    />
+                  </div>
+                  <div>
+                    <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Creator Share (bps of platform fee)</label>
+                    <input
+                      type="number"
+                      min={0}
+                      max={10000}
+                      value={createGameParams.creatorFeeBps}
+                      onChange={e => setCreateGameParams({ ...createGameParams, creatorFeeBps: parseInt(e.target.value) || 0 })}
+                      className="w-full px-3 py-2 rounded"
+                      style={{
+                        background: 'hsla(280, 100%, 35%, 0.1)',
+                        border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                        color: 'white'
+                      }}
+                    />
+                  </div>
+                  <div>
+                    <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Leave Lock (minutes before start)</label>
+                    <input
+                      type="number"
+                      min={0}
+                      max={1440}
+                      value={createGameParams.leaveLockMinutes}
+                      onChange={e => setCreateGameParams({ ...createGameParams, leaveLockMinutes: parseInt(e.target.value) || 0 })}
+                      className="w-full px-3 py-2 rounded"
+                      style={{
+                        background: 'hsla(280, 100%, 35%, 0.1)',
+                        border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                        color: 'white'
+                      }}
+                    />
+                  </div>
+                </div>
+                <div>
+                  <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Mode</label>
+                  <select
+                    value={createGameParams.squadSize}
+                    onChange={e => {
+                      const squadSize = parseInt(e.target.value);
+                      // Squad games need room for two squads of two
+                      const minPlayers = squadSize ? Math.max(createGameParams.minPlayers, 4) : createGameParams.minPlayers;
+                      setCreateGameParams({ ...createGameParams, squadSize, minPlayers });
+                    }}
+                    className="w-full px-3 py-2 rounded"
+                    style={{
+                      background: 'hsla(280, 100%, 35%, 0.1)',
+                      border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                      color: 'white'
+                    }}
+                  >
+                    <option value={0}>Solo - every player for themselves</option>
+                    {[2, 3, 4, 5].map((size) => (
+                      <option key={size} value={size}>Squads - teams of up to {size}</option>
+                    ))}
+                  </select>
+                </div>
+                <div>
+                  <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Access</label>
+                  <select
+                    value={createGameParams.accessMode}
+                    onChange={e => setCreateGameParams({ ...createGameParams, accessMode: e.target.value as AccessMode, accessList: '' })}
+                    className="w-full px-3 py-2 rounded"
+                    style={{
+                      background: 'hsla(280, 100%, 35%, 0.1)',
+                      border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                      color: 'white'
+                    }}
+                  >
+                    <option value="Public">Public - anyone can enter</option>
+                    <option value="Allowlist">Private - wallet allowlist</option>
+                    <option value="InviteCode">Private - invite code</option>
+                  </select>
+                  {createGameParams.accessMode === 'Allowlist' && (
+                    <textarea
+                      placeholder="Allowed wallets, one per line"
+                      value={createGameParams.accessList}
+                      onChange={e => setCreateGameParams({ ...createGameParams, accessList: e.target.value })}
+                      rows={4}
+                      className="w-full px-3 py-2 rounded mt-2"
+                      style={{
+                        background: 'hsla(280, 100%, 35%, 0.1)',
+                        border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                        color: 'white'
+                      }}
+                    />
+                  )}
+                  {createGameParams.accessMode === 'InviteCode' && (
+                    <input
+                      type="text"
+                      placeholder="Invite code to share with players"
+                      value={createGameParams.accessList}
+                      onChange={e => setCreateGameParams({ ...createGameParams, accessList: e.target.value })}
+                      className="w-full px-3 py-2 rounded mt-2"
+                      style={{
+                        background: 'hsla(280, 100%, 35%, 0.1)',
+                        border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                        color: 'white'
+                      }}
+                    />
+                  )}
+                </div>
                 <div>
                   <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>Game Duration</label>
                   <select
@@ -1346,13 +1768,13 @@ export default function Lobby() {
                       color: 'white'
                     }}
                   >
-                    <option value={0.5}>30m (12/18/30 min phase)</option>
-                    <option value={1}>1h (12/18/30 min phase)</option>
-                    <option value={2}>2h (24/36/60 min phase)</option>
-                    <option value={3}>3h (36/54/90 min phase)</option>
-                    <option value={5}>5h (60/90/150 min phase)</option>
-                    <option value={8}>8h (96/144/240 min phase)</option>
-                    <option value={24}>24h (288/432/720 min phase)</option>
+                    <option value={0.5}>30m (10/10/10 min phase)</option>
+                    <option value={1}>1h (20/20/20 min phase)</option>
+                    <option value={2}>2h (40/40/40 min phase)</option>
+                    <option value={3}>3h (60/60/60 min phase)</option>
+                    <option value={5}>5h (100/100/100 min phase)</option>
+                    <option value={8}>8h (160/160/160 min phase)</option>
+                    <option value={24}>24h (480/480/480 min phase)</option>
                   </select>
                 </div>
                 <div>
@@ -1373,6 +1795,10 @@ export default function Lobby() {
                     Minimum 30 minutes from now
                   </p>
                 </div>
+                <p className="text-xs" style={{ color: 'hsl(0, 0%, 60%)' }}>
+                  Creating a game locks a {creatorBondSol} SOL bond. You get it back when you start or cancel in time;
+                  otherwise it is split between the players.
+                </p>
               </div>
 
               <div className="flex gap-3 mt-6">
@@ -1534,6 +1960,251 @@ export default function Lobby() {
                 </div>
               </div>
 
+              {/* Sponsors - cine a adaugat fonduri in prize pool */}
+              {(() => {
+                const normalized = normalizeStatus(selectedGame.status);
+                const acceptsSponsors = !['Completed', 'Cancelled', 'ExpiredWithPenalty'].includes(normalized) &&
+                  !selectedGame.phase3Winner;
+                if (sponsors.length === 0 && !acceptsSponsors) return null;
+
+                return (
+                  <div className="mb-6">
+                    <h3 className="text-lg font-semibold mb-3" style={{ color: 'white' }}>
+                      Sponsors {selectedGame.sponsoredAmount > 0 && `(${selectedGame.sponsoredAmount.toFixed(2)} SOL)`}
+                    </h3>
+                    <div className="space-y-2 max-h-32 overflow-y-auto mb-3">
+                      {sponsors.map((s) => (
+                        <div
+                          key={s.sponsor}
+                          className="flex items-center justify-between p-2 rounded"
+                          style={{
+                            background: 'hsla(50, 100%, 50%, 0.1)',
+                            border: '1px solid hsla(50, 100%, 50%, 0.3)'
+                          }}
+                        >
+                          <span className="text-sm" style={{ color: 'hsl(0, 0%, 80%)' }}>
+                            {s.displayName || `${s.sponsor.substring(0, 6)}...${s.sponsor.slice(-6)}`}
+                          </span>
+                          <span className="text-sm font-medium" style={{ color: 'hsl(50, 100%, 60%)' }}>
+                            {s.amount.toFixed(2)} SOL
+                          </span>
+                        </div>
+                      ))}
+                    </div>
+                    {acceptsSponsors && (
+                      <div className="grid grid-cols-2 gap-2">
+                        <input
+                          type="text"
+                          placeholder="Display name"
+                          maxLength={32}
+                          value={sponsorForm.displayName}
+                          onChange={e => setSponsorForm({ ...sponsorForm, displayName: e.target.value })}
+                          className="px-3 py-2 rounded"
+                          style={{
+                            background: 'hsla(280, 100%, 35%, 0.1)',
+                            border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                            color: 'white'
+                          }}
+                        />
+                        <input
+                          type="number"
+                          min="0"
+                          step="0.1"
+                          value={sponsorForm.amount}
+                          onChange={e => setSponsorForm({ ...sponsorForm, amount: parseFloat(e.target.value) || 0 })}
+                          className="px-3 py-2 rounded"
+                          style={{
+                            background: 'hsla(280, 100%, 35%, 0.1)',
+                            border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                            color: 'white'
+                          }}
+                        />
+                        <label className="col-span-2 flex items-center gap-2 text-sm" style={{ color: 'hsl(0, 0%, 70%)' }}>
+                          <input
+                            type="checkbox"
+                            checked={sponsorForm.feeEligible}
+                            onChange={e => setSponsorForm({ ...sponsorForm, feeEligible: e.target.checked })}
+                          />
+                          Let the platform fee apply to my sponsorship
+                        </label>
+                        <button
+                          onClick={handleSponsorGame}
+                          disabled={!wallet.connected || solanaGame.loading || sponsorForm.amount <= 0}
+                          className="col-span-2 py-2 rounded-lg font-semibold transition-all duration-300 flex items-center justify-center gap-2"
+                          style={{
+                            background: 'hsla(0, 0%, 100%, 0.1)',
+                            color: 'white',
+                            border: '1px solid hsla(0, 0%, 100%, 0.3)'
+                          }}
+                        >
+                          <Coins className="w-4 h-4" />
+                          {solanaGame.loading ? 'Sponsoring...' : 'Sponsor this Game'}
+                        </button>
+                      </div>
+                    )}
+                  </div>
+                );
+              })()}
+
+              {/* Squads - echipele si cotele lor din premiu */}
+              {selectedGame.squadSize > 0 && (() => {
+                const me = wallet.publicKey?.toBase58() || '';
+                const normalized = normalizeStatus(selectedGame.status);
+                const squadsOpen = !selectedGame.gameStarted && (normalized === 'WaitingForPlayers' || normalized === 'ReadyToStart');
+                const inGame = selectedGame.players.includes(me);
+                const mySquad = squads.find((s) => s.members.includes(me));
+
+                return (
+                  <div className="mb-6">
+                    <h3 className="text-lg font-semibold mb-1" style={{ color: 'white' }}>
+                      Squads ({selectedGame.squadPlayers}/{selectedGame.currentPlayers} players in a squad)
+                    </h3>
+                    <p className="text-xs mb-3" style={{ color: 'hsl(0, 0%, 60%)' }}>
+                      Teams of 2-{selectedGame.squadSize}. Every player needs a squad of at least two before the start.
+                    </p>
+                    <div className="space-y-2 max-h-48 overflow-y-auto mb-3">
+                      {squads.map((s) => {
+                        const myIndex = s.members.indexOf(me);
+                        const won = selectedGame.phase3Winner === s.address;
+                        return (
+                          <div
+                            key={s.address}
+                            className="p-2 rounded"
+                            style={{
+                              background: won ? 'hsla(50, 100%, 50%, 0.15)' : 'hsla(280, 100%, 35%, 0.1)',
+                              border: `1px solid ${won ? 'hsla(50, 100%, 50%, 0.5)' : 'hsla(280, 100%, 35%, 0.3)'}`
+                            }}
+                          >
+                            <div className="flex items-center justify-between">
+                              <span className="text-sm font-medium" style={{ color: 'white' }}>
+                                {won && <Trophy className="w-3 h-3 inline mr-1" style={{ color: 'hsl(50, 100%, 60%)' }} />}
+                                {s.name || `Squad of ${s.leader.substring(0, 6)}`} ({s.members.length}/{s.shares.length})
+                              </span>
+                              {squadsOpen && inGame && !mySquad && s.members.length < s.shares.length && (
+                                <button
+                                  onClick={() => handleJoinSquad(s.address)}
+                                  disabled={solanaGame.loading}
+                                  className="text-xs px-2 py-1 rounded"
+                                  style={{ background: 'hsl(280, 100%, 50%)', color: 'white' }}
+                                >
+                                  Join ({s.shares[s.members.length] / 100}% share)
+                                </button>
+                              )}
+                              {squadsOpen && myIndex >= 0 && (myIndex > 0 || s.members.length === 1) && (
+                                <button
+                                  onClick={() => handleLeaveSquad(s.address)}
+                                  disabled={solanaGame.loading}
+                                  className="text-xs px-2 py-1 rounded"
+                                  style={{ background: 'hsla(0, 0%, 100%, 0.1)', color: 'white' }}
+                                >
+                                  {s.members.length === 1 ? 'Disband' : 'Leave'}
+                                </button>
+                              )}
+                              {won && myIndex >= 0 && !s.claimed[myIndex] && (
+                                <button
+                                  onClick={() => handleClaimSquadPrize(s.address)}
+                                  disabled={solanaGame.loading}
+                                  className="text-xs px-2 py-1 rounded"
+                                  style={{ background: 'hsl(50, 100%, 50%)', color: 'black' }}
+                                >
+                                  Claim {s.shares[myIndex] / 100}% Share
+                                </button>
+                              )}
+                            </div>
+                            <div className="text-xs mt-1" style={{ color: 'hsl(0, 0%, 70%)' }}>
+                              {s.shares.map((share, i) => (
+                                <span key={i} className="mr-3">
+                                  {s.members[i] ? `${s.members[i].substring(0, 4)}...${s.members[i].slice(-4)}` : 'open'}: {share / 100}%
+                                </span>
+                              ))}
+                            </div>
+                          </div>
+                        );
+                      })}
+                    </div>
+                    {squadsOpen && inGame && !mySquad && (
+                      <div className="grid grid-cols-2 gap-2">
+                        <input
+                          type="text"
+                          placeholder="Squad name"
+                          maxLength={32}
+                          value={squadForm.name}
+                          onChange={e => setSquadForm({ ...squadForm, name: e.target.value })}
+                          className="px-3 py-2 rounded"
+                          style={{
+                            background: 'hsla(280, 100%, 35%, 0.1)',
+                            border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                            color: 'white'
+                          }}
+                        />
+                        <input
+                          type="text"
+                          placeholder="Prize split %, e.g. 40, 30, 30"
+                          value={squadForm.shares}
+                          onChange={e => setSquadForm({ ...squadForm, shares: e.target.value })}
+                          className="px-3 py-2 rounded"
+                          style={{
+                            background: 'hsla(280, 100%, 35%, 0.1)',
+                            border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                            color: 'white'
+                          }}
+                        />
+                        <button
+                          onClick={handleCreateSquad}
+                          disabled={solanaGame.loading}
+                          className="col-span-2 py-2 rounded-lg font-semibold transition-all duration-300 flex items-center justify-center gap-2"
+                          style={{
+                            background: 'hsla(0, 0%, 100%, 0.1)',
+                            color: 'white',
+                            border: '1px solid hsla(0, 0%, 100%, 0.3)'
+                          }}
+                        >
+                          <Users className="w-4 h-4" />
+                          {solanaGame.loading ? 'Creating...' : 'Create Squad'}
+                        </button>
+                      </div>
+                    )}
+                  </div>
+                );
+              })()}
+
+              {/* Private game - codul sau allowlist-ul pentru intrare */}
+              {selectedGame.accessMode !== 'Public' &&
+                !selectedGame.players.includes(wallet.publicKey?.toBase58() || '') &&
+                !myWaitlistEntry &&
+                !selectedGame.gameStarted && (
+                  <div className="mb-4">
+                    <label className="block mb-1" style={{ color: 'hsl(0, 0%, 80%)' }}>
+                      {selectedGame.accessMode === 'InviteCode' ? 'Invite Code' : 'Allowlist (shared by the creator, one wallet per line)'}
+                    </label>
+                    {selectedGame.accessMode === 'InviteCode' ? (
+                      <input
+                        type="text"
+                        value={accessPass}
+                        onChange={e => setAccessPass(e.target.value)}
+                        className="w-full px-3 py-2 rounded"
+                        style={{
+                          background: 'hsla(280, 100%, 35%, 0.1)',
+                          border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                          color: 'white'
+                        }}
+                      />
+                    ) : (
+                      <textarea
+                        value={accessPass}
+                        onChange={e => setAccessPass(e.target.value)}
+                        rows={3}
+                        className="w-full px-3 py-2 rounded"
+                        style={{
+                          background: 'hsla(280, 100%, 35%, 0.1)',
+                          border: '1px solid hsla(280, 100%, 35%, 0.3)',
+                          color: 'white'
+                        }}
+                      />
+                    )}
+                  </div>
+                )}
+
               {/* Action Buttons */}
               <div className="flex gap-3 flex-wrap">
                 {/* Claim Prize Button */}
@@ -1558,6 +2229,7 @@ export default function Lobby() {
 
                 {/* Join Button */}
                 {normalizeStatus(selectedGame.status) === 'WaitingForPlayers' &&
+                  selectedGame.waitlistEscrow === 0 &&
                   !selectedGame.players.includes(wallet.publicKey?.toBase58() || '') &&
                   Date.now() < selectedGame.startTime.getTime() && (
                     <button
@@ -1575,6 +2247,117 @@ export default function Lobby() {
                     </button>
                   )}
 
+                {/* Waitlist - depozit pentru un loc cand jocul e plin sau coada nu e goala */}
+                {(normalizeStatus(selectedGame.status) === 'ReadyToStart' ||
+                  (normalizeStatus(selectedGame.status) === 'WaitingForPlayers' && selectedGame.waitlistEscrow > 0)) &&
+                  !selectedGame.gameStarted &&
+                  !selectedGame.players.includes(wallet.publicKey?.toBase58() || '') &&
+                  !myWaitlistEntry &&
+                  Date.now() < selectedGame.startTime.getTime() && (
+                    <button
+                      onClick={handleJoinWaitlist}
+                      disabled={!wallet.connected || solanaGame.loading}
+                      className="flex-1 py-3 rounded-lg font-semibold transition-all duration-300 flex items-center justify-center gap-2"
+                      style={{
+                        background: 'linear-gradient(135deg, hsl(280, 100%, 35%), hsl(15, 100%, 50%))',
+                        color: 'white',
+                        border: 'none'
+                      }}
+                    >
+                      <Clock className="w-4 h-4" />
+                      {solanaGame.loading ? 'Joining...' : `Join Waitlist (${selectedGame.entryFee.toFixed(2)} SOL deposit)`}
+                    </button>
+                  )}
+
+                {(() => {
+                  if (!myWaitlistEntry) return null;
+                  const normalized = normalizeStatus(selectedGame.status);
+                  const stillOpen = !myWaitlistEntry.skipped &&
+                    !selectedGame.gameStarted &&
+                    (normalized === 'WaitingForPlayers' || normalized === 'ReadyToStart') &&
+                    Date.now() < selectedGame.startTime.getTime();
+
+                  return stillOpen ? (
+                    <div className="flex-1 py-3 text-center text-sm" style={{ color: 'hsl(0, 0%, 70%)' }}>
+                      On the waitlist (#{myWaitlistEntry.position + 1}) - you'll be promoted when a seat frees up
+                    </div>
+                  ) : (
+                    <button
+                      onClick={handleRefundWaitlist}
+                      disabled={solanaGame.loading}
+                      className="flex-1 py-3 rounded-lg font-semibold transition-all duration-300 flex items-center justify-center gap-2"
+                      style={{
+                        background: 'linear-gradient(135deg, hsl(50, 100%, 35%), hsl(50, 100%, 50%))',
+                        color: 'black',
+                        border: 'none',
+                        boxShadow: '0 5px 15px hsla(50, 100%, 35%, 0.4)'
+                      }}
+                    >
+                      <Trophy className="w-4 h-4" />
+                      {solanaGame.loading ? 'Claiming...' : 'Refund Waitlist Deposit'}
+                    </button>
+                  );
+                })()}
+
+                {/* Refund Sponsorship - sponsorii isi recupereaza fondurile daca jocul nu a avut loc */}
+                {['Cancelled', 'ExpiredWithPenalty'].includes(normalizeStatus(selectedGame.status)) &&
+                  sponsors.some((s) => s.sponsor === wallet.publicKey?.toBase58()) && (
+                    <button
+                      onClick={handleRefundSponsor}
+                      disabled={solanaGame.loading}
+                      className="flex-1 py-3 rounded-lg font-semibold transition-all duration-300 flex items-center justify-center gap-2"
+                      style={{
+                        background: 'linear-gradient(135deg, hsl(50, 100%, 35%), hsl(50, 100%, 50%))',
+                        color: 'black',
+                        border: 'none',
+                        boxShadow: '0 5px 15px hsla(50, 100%, 35%, 0.4)'
+                      }}
+                    >
+                      <Trophy className="w-4 h-4" />
+                      {solanaGame.loading ? 'Claiming...' : 'Refund Sponsorship'}
+                    </button>
+                  )}
+
+                {/* Fill Seat - oricine poate promova capul cozii intr-un loc liber */}
+                {normalizeStatus(selectedGame.status) === 'WaitingForPlayers' &&
+                  !selectedGame.gameStarted &&
+                  selectedGame.waitlistEscrow > 0 &&
+                  Date.now() < selectedGame.startTime.getTime() && (
+                    <button
+                      onClick={handlePromoteFromWaitlist}
+                      disabled={!wallet.connected || solanaGame.loading}
+                      className="flex-1 py-3 rounded-lg font-semibold transition-all duration-300 flex items-center justify-center gap-2"
+                      style={{
+                        background: 'hsla(0, 0%, 100%, 0.1)',
+                        color: 'white',
+                        border: '1px solid hsla(0, 0%, 100%, 0.3)'
+                      }}
+                    >
+                      <UserPlus className="w-4 h-4" />
+                      {solanaGame.loading ? 'Promoting...' : 'Fill Seat from Waitlist'}
+                    </button>
+                  )}
+
+                {/* Leave Button - jucatorii se pot retrage pana la fereastra de blocare */}
+                {(normalizeStatus(selectedGame.status) === 'WaitingForPlayers' || normalizeStatus(selectedGame.status) === 'ReadyToStart') &&
+                  !selectedGame.gameStarted &&
+                  selectedGame.players.includes(wallet.publicKey?.toBase58() || '') &&
+                  Date.now() < selectedGame.startTime.getTime() - selectedGame.leaveLockWindow * 1000 && (
+                    <button
+                      onClick={handleLeaveGame}
+                      disabled={solanaGame.loading}
+                      className="flex-1 py-3 rounded-lg font-semibold transition-all duration-300 flex items-center justify-center gap-2"
+                      style={{
+                        background: 'hsla(0, 0%, 100%, 0.1)',
+                        color: 'white',
+                        border: '1px solid hsla(0, 0%, 100%, 0.3)'
+                      }}
+                    >
+                      <XCircle className="w-4 h-4" />
+                      {solanaGame.loading ? 'Leaving...' : 'Leave Game'}
+                    </button>
+                  )}
+
                 {/* Start Button - Creator poate porni jocul cu ≥3 jucători în primele 30 min */}
                 {(() => {
                   const thirtyMinutesAfterStart = selectedGame.startTime.getTime() + 30 * 60 * 1000;
@@ -1675,7 +2458,6 @@ export default function Lobby() {
 
                   const canClaimRefund = isPlayerInGame && !hasClaimedRefund && (
                     normalized === 'Cancelled' ||
-                    normalized === 'Expired' ||
                     normalized === 'Expiredwithpenalty' ||
                     normalized === 'ExpiredWithPenalty'
                   );
@@ -1698,6 +2480,24 @@ export default function Lobby() {
                   ) : null;
                 })()}
 
+                {/* Creator Fee - partea creatorului din taxa platformei */}
+                {selectedGame.creator === wallet.publicKey?.toBase58() && selectedGame.creatorFeeCollected > 0 && (
+                  <button
+                    onClick={handleClaimCreatorFee}
+                    disabled={solanaGame.loading}
+                    className="flex-1 py-3 rounded-lg font-semibold transition-all duration-300 flex items-center justify-center gap-2"
+                    style={{
+                      background: 'linear-gradient(135deg, hsl(50, 100%, 35%), hsl(50, 100%, 50%))',
+                      color: 'black',
+                      border: 'none',
+                      boxShadow: '0 5px 15px hsla(50, 100%, 35%, 0.4)'
+                    }}
+                  >
+                    <Trophy className="w-4 h-4" />
+                    {solanaGame.loading ? 'Claiming...' : 'Claim Creator Fee'}
+                  </button>
+                )}
+
                 <button
                   onClick={() => {
                     setShowGameDetailsModal(false);
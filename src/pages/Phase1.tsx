@@ -8,6 +8,7 @@ import Footer from "../components/Footer";
 import { useSolanaGame, type PlayerGameState, type GamePoolState } from "../hooks/useSolanaGame";
 import type { Game } from "../hooks/useSolanaGame";
 import { useWallet } from "@solana/wallet-adapter-react";
+import { PublicKey } from "@solana/web3.js";
 import { Button } from "@/components/ui/button";
 import PoolCard from "@/components/phase1/PoolCard";
 import AllocationBar from "@/components/phase1/AllocationBar";
@@ -16,6 +17,7 @@ import { useToast } from "@/hooks/use-toast";
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert";
 import EventsTicker, { GameEvent as UIGameEvent } from "@/components/phase1/EventsTicker";
 import EventsNewsBar from '@/components/EventsNewsBar';
+import PendingAdminActions from '@/components/PendingAdminActions';
 import { generateChaosEvents, getUpcomingEvents, ChaosEvent } from '@/utils/mockChaosEvents';
 
 export default function Phase1() {
@@ -40,6 +42,9 @@ export default function Phase1() {
   const [chaosEvents, setChaosEvents] = useState<ChaosEvent[]>([]);
   const [currentTime, setCurrentTime] = useState(new Date());
   const [upcomingEvents, setUpcomingEvents] = useState<ChaosEvent[]>([]);
+  // Squad games: teammates who can receive part of the unallocated balance
+  const [squadmates, setSquadmates] = useState<string[]>([]);
+  const [shareForm, setShareForm] = useState({ to: '', amount: 0 });
 
   // Resource allocations (percentages, converted to amounts when submitting)
   const [allocations, setAllocations] = useState({
@@ -566,6 +571,35 @@ export default function Phase1() {
     }
   };
 
+  // ✅ SQUAD SHARE - balanta nealocata trece la un coechipier
+  useEffect(() => {
+    if (!currentGame?.squadSize || !wallet.publicKey) return;
+    const me = wallet.publicKey.toBase58();
+    solanaGame.fetchSquads(currentGame.gameId)
+      .then((squads) => {
+        const mates = squads.find((s) => s.members.includes(me))?.members.filter((m) => m !== me) ?? [];
+        setSquadmates(mates);
+        setShareForm((form) => ({ ...form, to: form.to || mates[0] || '' }));
+      })
+      .catch((error) => console.error('Failed to fetch squads:', error));
+  }, [currentGame?.gameId, wallet.publicKey?.toBase58()]);
+
+  const handleShareBalance = async () => {
+    if (!currentGame || !shareForm.to) return;
+
+    try {
+      await solanaGame.shareSquadBalance(currentGame.gameId, new PublicKey(shareForm.to), shareForm.amount);
+      setPlayerState((state) => state && { ...state, virtualBalance: state.virtualBalance - shareForm.amount });
+    } catch (error: any) {
+      console.error('Failed to share balance:', error);
+      toast({
+        variant: "destructive",
+        title: "Share Failed",
+        description: error.message || "Could not share balance",
+      });
+    }
+  };
+
   const handleAdvancePhase = async () => {
     if (!currentGame) return;
 
@@ -864,6 +898,8 @@ export default function Phase1() {
 
       <main className="relative pb-16 px-4" style={{ zIndex: 10, paddingTop: '6rem' }}>
         <div className="max-w-7xl mx-auto">
+          <PendingAdminActions gameId={currentGame.gameId} paused={currentGame.paused} />
+
           {/* Header */}
           <div className="mb-8">
             <Button
@@ -1105,6 +1141,39 @@ export default function Phase1() {
             ))}
           </div>
 
+          {/* Squad Share - doar inainte de prima alocare */}
+          {squadmates.length > 0 && playerState && !playerState.hasActiveAllocation && (
+            <div className="flex flex-wrap items-center justify-center gap-2 mb-6">
+              <span className="text-sm text-muted-foreground">Give unallocated balance to a squadmate:</span>
+              <select
+                value={shareForm.to}
+                onChange={e => setShareForm({ ...shareForm, to: e.target.value })}
+                className="px-3 py-2 rounded bg-background border"
+              >
+                {squadmates.map((mate) => (
+                  <option key={mate} value={mate}>{mate.slice(0, 6)}...{mate.slice(-6)}</option>
+                ))}
+              </select>
+              <input
+                type="number"
+                min="0"
+                step="0.1"
+                max={playerState.virtualBalance}
+                value={shareForm.amount}
+                onChange={e => setShareForm({ ...shareForm, amount: parseFloat(e.target.value) || 0 })}
+                className="w-28 px-3 py-2 rounded bg-background border"
+              />
+              <Button
+                variant="outline"
+                onClick={handleShareBalance}
+                disabled={solanaGame.loading || shareForm.amount <= 0 || shareForm.amount > playerState.virtualBalance}
+              >
+                <Users className="w-4 h-4 mr-2" />
+                Share
+              </Button>
+            </div>
+          )}
+
           {/* Confirm Button */}
           <div className="flex justify-center">
             <Button
@@ -10,6 +10,7 @@ import { Alert, AlertDescription, AlertTitle } from '@/components/ui/alert';
 import ParticleBackground from '@/components/ParticleBackground';
 import Navbar from '@/components/Navbar';
 import PhaseCountdown from '@/components/PhaseCountdown';
+import PendingAdminActions from '@/components/PendingAdminActions';
 import GameModal from '@/components/phase2/GameModal';
 import TransactionOverlay from '@/components/TransactionOverlay';
 import { retryTransaction } from '@/utils/transactionHelper';
@@ -560,6 +561,8 @@ export default function Phase2() {
                     Back to Lobby
                 </Button>
 
+                <PendingAdminActions gameId={currentGame?.gameId} paused={currentGame?.paused} />
+
                 <div className="text-center mb-12">
                     <h1 className="text-5xl md:text-7xl font-display font-black mb-4">
                         <span className="gradient-text">⚔️ Phase 2: Battle Arena</span>
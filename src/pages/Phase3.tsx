@@ -16,6 +16,7 @@ import { useWallet } from '@solana/wallet-adapter-react';
 import ParticleBackground from '@/components/ParticleBackground';
 import Navbar from '@/components/Navbar';
 import Footer from '@/components/Footer';
+import PendingAdminActions from '@/components/PendingAdminActions';
 import PurgeGameMultiplayer from '@/pages/PurgeGameMultiplayer';
 import { wsManager } from '@/utils/websocketManager';
 import { retryTransaction } from '@/utils/transactionHelper';
@@ -679,7 +680,11 @@ const Phase3 = () => {
 
             <main className="container mx-auto px-4 py-8 relative z-10">
                 <div className="max-w-6xl mx-auto">
-                    <div className="text-center mb-12 pt-20">
+                    <div className="pt-20">
+                        <PendingAdminActions gameId={gameId} paused={currentGame?.paused} />
+                    </div>
+
+                    <div className="text-center mb-12">
                         <h1 className="text-5xl md:text-7xl font-black mb-4 text-transparent bg-clip-text bg-gradient-to-r from-red-600 via-orange-500 to-yellow-500">
                             FINAL BATTLE
                         </h1>